@@ -0,0 +1,48 @@
+use std::time::Duration;
+use termset::core::{ClipboardSelection, StdoutEscSeq};
+
+mod tsc {
+    pub use termset::core::*;
+}
+
+/// `cargo run --example clipboard -- some text` copies "some text" to the clipboard,
+/// `cargo run --example clipboard` prints what's on it (if your terminal lets us see it)
+fn main() {
+    let mut stdout = tsc::TsStdout::new();
+    let text = std::env::args().skip(1).collect::<Vec<_>>().join(" ");
+    if !text.is_empty() {
+        stdout
+            .exec([&StdoutEscSeq::SetClipboard(
+                ClipboardSelection::Clipboard,
+                &text,
+            )])
+            .flush();
+        return;
+    }
+
+    let mut termset = tsc::Termset::new().unwrap();
+    termset.disable_lflag(tsc::ECHO | tsc::ICANON);
+    termset.update(None);
+
+    let mut event_loop = tsc::EventLoop::new().unwrap();
+    stdout
+        .exec([&StdoutEscSeq::RequestClipboard(
+            ClipboardSelection::Clipboard,
+        )])
+        .flush();
+    loop {
+        match event_loop.next_timeout(Duration::from_secs(1)).unwrap() {
+            Some(tsc::Event::Token(tsc::Token::Clipboard(_, contents))) => {
+                stdout.write_str(&format!("clipboard = {:?}\n", contents));
+                break;
+            }
+            Some(_) => continue,
+            None => {
+                stdout.write_str("the terminal didn't reply\n");
+                break;
+            }
+        }
+    }
+    stdout.flush();
+    termset.restore_through(&mut stdout);
+}
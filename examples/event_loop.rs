@@ -0,0 +1,54 @@
+use std::{thread, time::Duration};
+
+mod tsc {
+    pub use termset::core::*;
+}
+
+fn main() {
+    let mut termset = tsc::Termset::new().unwrap();
+    termset.disable_lflag(tsc::ECHO | tsc::ICANON);
+    termset.update(None);
+
+    let mut stdout = tsc::TsStdout::new();
+    let mut event_loop = tsc::EventLoop::new().unwrap();
+
+    // wake the loop up from another thread every couple of seconds
+    let waker = event_loop.waker();
+    thread::spawn(move || loop {
+        thread::sleep(Duration::from_secs(2));
+        if waker.wake().is_err() {
+            break;
+        }
+    });
+
+    let tick = event_loop.add_interval(Duration::from_millis(500));
+    let mut ticks = 0;
+
+    stdout
+        .write_str(&format!("size = {:?}\r\n", tsc::window_size()))
+        .write_str("press q to quit\r\n")
+        .flush();
+
+    loop {
+        let event = event_loop.next().unwrap();
+        match event {
+            tsc::Event::Token(tsc::Token::Char("q")) => break,
+            tsc::Event::Timer(id) if id == tick => {
+                ticks += 1;
+                stdout.write_str(&format!("\rticks = {}", ticks));
+            }
+            tsc::Event::Suspend => {
+                termset.reset();
+                tsc::suspend_process();
+            }
+            tsc::Event::Continue => termset.update(None),
+            event => {
+                stdout.write_str(&format!("\r\nevent = {:?}\r\n", event));
+            }
+        }
+        stdout.flush();
+    }
+
+    stdout.write_str("\r\n").flush();
+    termset.restore_through(&mut stdout);
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use termset::core::{Cell, Color, Style};
+
+mod tsc {
+    pub use termset::core::*;
+}
+
+/// A progress bar drawn in 3 rows under whatever's already on the terminal, with log lines
+/// printed above it. It's left on the terminal when it's done.
+fn main() {
+    let mut termset = tsc::Termset::new().unwrap();
+    termset.disable_lflag(tsc::ECHO | tsc::ICANON);
+    termset.update(None);
+
+    let mut stdout = tsc::TsStdout::new();
+    let mut reader = tsc::TokenReader::new();
+    // so the bar starts on this line if it's empty, rather than the next one
+    let _ = stdout.sync_cursor_position(&mut reader, Duration::from_millis(100));
+    let mut screen = tsc::Screen::inline(3).unwrap();
+    let cols = screen.size().1;
+
+    for percent in 0..=100u16 {
+        screen.clear();
+        screen.draw_text(0, 0, "downloading things", Style::new().bold());
+        screen.fill(
+            1,
+            0,
+            1,
+            cols * percent / 100,
+            Cell::new(" ", Style::new().bg(Color::Green)),
+        );
+        screen.draw_text(2, 0, &format!("{}%", percent), Style::new().dim());
+        screen.present(&mut stdout);
+        if percent % 20 == 0 {
+            screen.print_above(&mut stdout, &format!("got file {}", percent / 20));
+        }
+        std::thread::sleep(Duration::from_millis(30));
+    }
+
+    screen.leave_inline(&mut stdout);
+    termset.restore_through(&mut stdout);
+}
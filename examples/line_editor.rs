@@ -0,0 +1,39 @@
+mod tsc {
+    pub use termset::core::*;
+}
+
+const COMMANDS: [&str; 6] = ["help", "history", "hello", "exit", "echo", "clear"];
+
+fn main() {
+    let mut stdout = tsc::TsStdout::new();
+    let mut reader = tsc::TokenReader::new();
+
+    let mut editor = tsc::LineEditor::new("> ");
+    let history_path = std::env::temp_dir().join("termset_line_editor_history");
+    editor.set_history(tsc::History::with_file(history_path).unwrap());
+    editor.set_completer(|line, cursor| {
+        // complete the word under the cursor from `COMMANDS`
+        let start = line[..cursor]
+            .rfind(char::is_whitespace)
+            .map_or(0, |i| i + 1);
+        tsc::Completion {
+            start,
+            candidates: COMMANDS
+                .iter()
+                .filter(|command| command.starts_with(&line[start..cursor]))
+                .map(|command| command.to_string())
+                .collect(),
+        }
+    });
+
+    loop {
+        match editor.read_line(&mut stdout, &mut reader) {
+            Ok(line) if line == "exit" => break,
+            Ok(line) => {
+                stdout.write_str(&format!("you said {:?}\n", line)).flush();
+            }
+            Err(tsc::ReadLineError::Interrupted) => continue,
+            Err(_) => break,
+        }
+    }
+}
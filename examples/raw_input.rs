@@ -1,8 +1,4 @@
 use rtc::rtc_println;
-use std::{
-    io::{self, Stdout, Write},
-    thread, time,
-};
 use termset::core::StdoutEscSeq;
 
 mod tsc {
@@ -15,23 +11,14 @@ fn prepare_terminal() -> (tsc::Termset, tsc::TsStdout) {
     termset.update(None);
 
     let mut stdout = tsc::TsStdout::new();
-    stdout
-        .exec([
-            &StdoutEscSeq::SaveCursorPosition,
-            &StdoutEscSeq::SaveScreen,
-            &StdoutEscSeq::EraseEntireScreen,
-        ])
-        .flush();
+    stdout.exec([&StdoutEscSeq::EnterAlternateScreen]).flush();
 
     (termset, stdout)
 }
 
 fn restore_terminal(termset: tsc::Termset, mut ts_stdout: tsc::TsStdout) {
     ts_stdout
-        .exec([
-            &StdoutEscSeq::RestorCursorPosition,
-            &StdoutEscSeq::RestoreScreen,
-        ])
+        .exec([&StdoutEscSeq::LeaveAlternateScreen])
         .flush();
     termset.restore();
 }
@@ -61,8 +48,11 @@ fn main() {
                 }
             },
             tsc::Token::Esc(seq) => {
-                stdout.exec([&seq.as_stdout_esc_seq().unwrap()]);
+                if let Some(seq) = seq.as_stdout_esc_seq() {
+                    stdout.exec([&seq]);
+                }
             }
+            _ => (),
         }
 
         stdout.flush();
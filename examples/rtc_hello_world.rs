@@ -1,5 +1,4 @@
 use once_cell::sync::Lazy;
-use rtc;
 
 static RT: Lazy<rtc::RemoteTerminal> = Lazy::new(|| {
     rtc::RemoteTerminal::new("127.0.0.1:7777").expect("could not initialise remote terminal")
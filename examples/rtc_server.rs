@@ -1,5 +1,3 @@
-use rtc;
-
 fn main() {
        
 }
\ No newline at end of file
@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use termset::core::{Cell, Color, StdoutEscSeq, Style};
+
+mod tsc {
+    pub use termset::core::*;
+}
+
+/// A box bouncing around the screen, redrawn 30 times a second. Only the cells that changed
+/// get sent each frame.
+fn main() {
+    let mut termset = tsc::Termset::new().unwrap();
+    termset.disable_lflag(tsc::ECHO | tsc::ICANON);
+    termset.update(None);
+
+    let mut stdout = tsc::TsStdout::new();
+    // without this, big redraws can tear on some terminals
+    let mut reader = tsc::TokenReader::new();
+    let _ = stdout.detect_synchronized_output(&mut reader, Duration::from_millis(100));
+    stdout.exec([&StdoutEscSeq::EnterAlternateScreen]);
+    let mut screen = tsc::Screen::new().unwrap();
+    let mut event_loop = tsc::EventLoop::new().unwrap();
+    let frame = event_loop.add_interval(Duration::from_millis(33));
+
+    let (mut row, mut col, mut d_row, mut d_col) = (0i32, 0i32, 1i32, 2i32);
+    loop {
+        match event_loop.next().unwrap() {
+            tsc::Event::Token(tsc::Token::Char("q")) => break,
+            tsc::Event::Resize(size) => screen.resize(size.rows, size.cols),
+            tsc::Event::Timer(id) if id == frame => (),
+            _ => continue,
+        }
+
+        let (rows, cols) = screen.size();
+        let (max_row, max_col) = (rows as i32 - 3, cols as i32 - 6);
+        if !(0..=max_row).contains(&(row + d_row)) {
+            d_row = -d_row;
+        }
+        if !(0..=max_col).contains(&(col + d_col)) {
+            d_col = -d_col;
+        }
+        row = (row + d_row).clamp(0, max_row.max(0));
+        col = (col + d_col).clamp(0, max_col.max(0));
+
+        screen.clear();
+        screen.draw_text(0, 0, "press q to quit", Style::new().dim());
+        let color = Color::Rgb((col * 255 / cols as i32) as u8, 120, 200);
+        screen.fill(
+            row as u16,
+            col as u16,
+            3,
+            6,
+            Cell::new(" ", Style::new().bg(color)),
+        );
+        screen.draw_text(
+            row as u16 + 1,
+            col as u16 + 1,
+            "hi!",
+            Style::new().bg(color).bold(),
+        );
+        screen.present(&mut stdout);
+    }
+
+    stdout
+        .reset_style()
+        .exec([
+            &StdoutEscSeq::ShowCursor,
+            &StdoutEscSeq::LeaveAlternateScreen,
+        ])
+        .flush();
+    termset.restore_through(&mut stdout);
+}
@@ -0,0 +1,147 @@
+#!/usr/bin/env python3
+# Generate src/core/unicode_tables.rs from the Unicode Character Database.
+#
+#     ./scripts/gen_unicode_tables.py path/to/ucd > src/core/unicode_tables.rs
+#
+# where `path/to/ucd` is an unzipped UCD.zip (https://www.unicode.org/Public/<version>/ucd/),
+# with the emoji data from .../ucd/emoji/ (or .../emoji/<version>/ for older versions) in
+# `emoji/`. The files used are:
+#
+#     EastAsianWidth.txt
+#     auxiliary/GraphemeBreakProperty.txt
+#     emoji/emoji-data.txt
+#     extracted/DerivedGeneralCategory.txt
+
+import os
+import re
+import sys
+
+# unassigned code points in these default to Wide (see UAX #11)
+DEFAULT_WIDE = [
+    (0x3400, 0x4DBF), (0x4E00, 0x9FFF), (0xF900, 0xFAFF), (0x20000, 0x2FFFD), (0x30000, 0x3FFFD),
+]
+
+# Grapheme_Cluster_Break values, and their names in `GraphemeCat`
+GRAPHEME_CATS = {
+    'CR': 'Cr', 'LF': 'Lf', 'Control': 'Control', 'Extend': 'Extend', 'ZWJ': 'Zwj',
+    'Regional_Indicator': 'RegionalIndicator', 'Prepend': 'Prepend', 'SpacingMark': 'SpacingMark',
+    'L': 'L', 'V': 'V', 'T': 'T', 'LV': 'Lv', 'LVT': 'Lvt',
+}
+
+
+def parse(ucd, path):
+    """Yield `(start, end, value)` for every line of a UCD property file"""
+    with open(os.path.join(ucd, path), encoding='utf-8') as f:
+        for line in f:
+            line = line.split('#', 1)[0].strip()
+            if not line:
+                continue
+            codepoints, value = [part.strip() for part in line.split(';')[:2]]
+            start, _, end = codepoints.partition('..')
+            yield int(start, 16), int(end or start, 16), value
+
+
+def version(ucd):
+    with open(os.path.join(ucd, 'EastAsianWidth.txt'), encoding='utf-8') as f:
+        match = re.search(r'EastAsianWidth-(\d+)\.(\d+)\.(\d+)\.txt', f.readline())
+    if not match:
+        sys.exit('EastAsianWidth.txt has no version header')
+    return tuple(int(n) for n in match.groups())
+
+
+def merge(ranges):
+    """Sort ranges, and join up the ones that touch (and have the same value)"""
+    merged = []
+    for start, end, *value in sorted(ranges):
+        if merged and merged[-1][2:] == value and merged[-1][1] + 1 >= start:
+            merged[-1][1] = max(merged[-1][1], end)
+        else:
+            merged.append([start, end, *value])
+    return merged
+
+
+def table(name, doc, ranges, value_type=None):
+    item = f'(u32, u32, {value_type})' if value_type else '(u32, u32)'
+    lines = [f'/// {doc}', f'pub(crate) const {name}: &[{item}] = &[']
+    for start, end, *value in merge(ranges):
+        value = f', {value_type}::{value[0]}' if value else ''
+        lines.append(f'    (0x{start:04X}, 0x{end:04X}{value}),')
+    lines.append('];')
+    return '\n'.join(lines) + '\n'
+
+
+def main():
+    if len(sys.argv) != 2:
+        sys.exit(f'usage: {sys.argv[0]} path/to/ucd')
+    ucd = sys.argv[1]
+
+    categories = list(parse(ucd, 'extracted/DerivedGeneralCategory.txt'))
+    east_asian_width = list(parse(ucd, 'EastAsianWidth.txt'))
+    grapheme_break = [
+        (start, end, GRAPHEME_CATS[value])
+        for start, end, value in parse(ucd, 'auxiliary/GraphemeBreakProperty.txt')
+        if value in GRAPHEME_CATS
+    ]
+    emoji = list(parse(ucd, 'emoji/emoji-data.txt'))
+
+    # marks and format characters, apart from the soft hyphen which terminals draw, and the
+    # Hangul vowels and trailing consonants that join onto a leading consonant
+    zero_width = [
+        (start, end) for start, end, gc in categories if gc in ('Mn', 'Me', 'Cf')
+    ] + [(start, end) for start, end, cat in grapheme_break if cat in ('V', 'T')]
+    zero_width = [
+        r for start, end in zero_width
+        for r in ([(start, 0xAC), (0xAE, end)] if start <= 0xAD <= end else [(start, end)])
+        if r[0] <= r[1]
+    ]
+
+    listed = merge([(start, end) for start, end, _ in east_asian_width])
+    wide = [(start, end) for start, end, value in east_asian_width if value in ('W', 'F')]
+    for start, end in DEFAULT_WIDE:
+        # fill in the gaps the file doesn't list
+        for listed_start, listed_end in listed:
+            if listed_start > start:
+                wide.append((start, min(end, listed_start - 1)))
+            start = max(start, listed_end + 1)
+            if start > end:
+                break
+        if start <= end:
+            wide.append((start, end))
+    wide = [(start, end) for start, end in wide if start <= end]
+
+    major, minor, update = version(ucd)
+    header = (
+        f'// Generated by scripts/gen_unicode_tables.py from the Unicode {major}.{minor}.{update}\n'
+        '// data files, don\'t edit this by hand\n'
+        '\n'
+        'use super::GraphemeCat;\n'
+        '\n'
+        '/// The version of Unicode the tables come from\n'
+        f'pub const UNICODE_VERSION: (u8, u8, u8) = ({major}, {minor}, {update});\n'
+    )
+    tables = [
+        table('ZERO_WIDTH', 'Characters that take up no columns', zero_width),
+        table(
+            'WIDE', 'East Asian Wide and Fullwidth characters, which take up two columns', wide,
+        ),
+        table(
+            'EMOJI', 'Characters that can be drawn as emoji',
+            [(start, end) for start, end, prop in emoji if prop == 'Emoji'],
+        ),
+        table(
+            'EMOJI_PRESENTATION', 'Emoji that are drawn as emoji (not text) by default',
+            [(start, end) for start, end, prop in emoji if prop == 'Emoji_Presentation'],
+        ),
+        table(
+            'EXTENDED_PICTOGRAPHIC', 'Emoji, and code points reserved for future emoji',
+            [(start, end) for start, end, prop in emoji if prop == 'Extended_Pictographic'],
+        ),
+        table(
+            'GRAPHEME_BREAK', 'The Grapheme_Cluster_Break property, anything else is `Other`',
+            grapheme_break, 'GraphemeCat',
+        ),
+    ]
+    sys.stdout.write('\n'.join([header] + tables))
+
+if __name__ == '__main__':
+    main()
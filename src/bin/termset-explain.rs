@@ -0,0 +1,117 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::os::unix::io::FromRawFd;
+use std::process::{self, Command, Stdio};
+
+use nix::pty::{openpty, Winsize};
+use termset::core::{window_size, Output, OutputDecoder};
+
+/// How wide the column with the raw bytes is
+const RAW_WIDTH: usize = 28;
+
+const USAGE: &str = "\
+usage: termset-explain [--] [command [args...]]
+
+Runs `command` with its stdout on a pseudo-terminal (or reads stdin if there's no command) and
+prints what every piece of its output does, one per line.";
+
+/// Print one line per piece of output a program writes, saying what it does. Handy for
+/// working out why a program's output messed up the terminal:
+///
+/// ```sh
+/// termset-explain ls --color=always
+/// some-program | termset-explain
+/// ```
+fn main() {
+    let mut args: Vec<_> = std::env::args_os().skip(1).collect();
+    match args.first().and_then(|arg| arg.to_str()) {
+        Some("-h" | "--help") => {
+            println!("{}", USAGE);
+            return;
+        }
+        Some("--") => {
+            args.remove(0);
+        }
+        _ => (),
+    }
+
+    let result = match args.split_first() {
+        None => explain(io::stdin().lock()).map(|()| 0),
+        Some((command, args)) => run(Command::new(command).args(args)),
+    };
+    match result {
+        Ok(code) => process::exit(code),
+        Err(e) => {
+            eprintln!("termset-explain: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Run `command` on a pty the size of ours, explaining its output. Returns its exit code.
+fn run(command: &mut Command) -> io::Result<i32> {
+    let winsize = window_size().ok().map(|size| Winsize {
+        ws_row: size.rows,
+        ws_col: size.cols,
+        ws_xpixel: size.x_pixels,
+        ws_ypixel: size.y_pixels,
+    });
+    let pty = openpty(winsize.as_ref(), None)?;
+    // `Stdio` owns the slave now, and closes it when `command` is done with it
+    let mut child = command
+        .stdout(unsafe { Stdio::from_raw_fd(pty.slave) })
+        .spawn()?;
+    command.stdout(Stdio::inherit());
+    let master = unsafe { File::from_raw_fd(pty.master) };
+    explain(master)?;
+    let status = child.wait()?;
+    Ok(status.code().unwrap_or(1))
+}
+
+fn explain(mut input: impl Read) -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut pending = Vec::new();
+    let mut buf = [0; 4096];
+    loop {
+        let len = match input.read(&mut buf) {
+            Ok(len) => len,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            // what reading a pty gives once everything on the other end has closed it
+            Err(e) if e.raw_os_error() == Some(libc::EIO) => 0,
+            Err(e) => return Err(e),
+        };
+        let done = len == 0;
+        pending.extend_from_slice(&buf[..len]);
+
+        let mut decoder = OutputDecoder::new(&pending);
+        let mut start = 0;
+        while let Some(output) = decoder.next() {
+            if matches!(output, Output::Incomplete(_)) && !done {
+                // wait for the rest of it
+                break;
+            }
+            write_line(&mut out, &pending[start..decoder.position()], &output)?;
+            start = decoder.position();
+        }
+        pending.drain(..start);
+        out.flush()?;
+        if done {
+            return Ok(());
+        }
+    }
+}
+
+fn write_line<W: Write>(out: &mut W, raw: &[u8], output: &Output) -> io::Result<()> {
+    let mut raw = raw.escape_ascii().to_string();
+    if raw.len() > RAW_WIDTH {
+        raw.truncate(RAW_WIDTH - 3);
+        raw.push_str("...");
+    }
+    writeln!(
+        out,
+        "{:<width$} {}",
+        raw,
+        output.describe(),
+        width = RAW_WIDTH
+    )
+}
@@ -0,0 +1,273 @@
+use std::mem;
+
+use super::{display_width, grapheme_width, graphemes, Output, OutputDecoder, StdoutEscSeq, Style};
+
+/// `s` without any escape sequences, i.e. just the text that ends up on the screen (and
+/// control characters like `\n` and `\t`)
+pub fn ansi_strip(s: &str) -> String {
+    let mut stripped = String::with_capacity(s.len());
+    for output in OutputDecoder::new(s.as_bytes()) {
+        match output {
+            Output::Text(text) => stripped.push_str(text),
+            Output::Control(byte) => stripped.push(byte as char),
+            _ => (),
+        }
+    }
+    stripped
+}
+
+/// The number of columns `s` takes up, like `display_width` but skipping escape sequences
+pub fn ansi_width(s: &str) -> usize {
+    OutputDecoder::new(s.as_bytes())
+        .map(|output| match output {
+            Output::Text(text) => display_width(text),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Cut `s` down to `width` columns, ending with `ellipsis` if anything had to go (it's left
+/// out if it doesn't fit either). Styles and hyperlinks that are still on where it's cut are
+/// turned off at the end, so they don't leak into whatever comes next.
+///
+/// ```rs
+/// let cell = ansi_truncate("\x1b[31mtoo long for this\x1b[0m", 8, "…");
+/// assert_eq!(cell, "\x1b[31mtoo lon…\x1b[0m");
+/// ```
+pub fn ansi_truncate(s: &str, width: usize, ellipsis: &str) -> String {
+    if ansi_width(s) <= width {
+        return s.to_string();
+    }
+    let (ellipsis, width) = match width.checked_sub(ansi_width(ellipsis)) {
+        Some(width) => (ellipsis, width),
+        None => ("", width),
+    };
+
+    let mut line = StyledLine::new();
+    'outer: for (raw, output) in outputs(s) {
+        match output {
+            Output::Text(text) => {
+                for grapheme in graphemes(text) {
+                    let grapheme_width = grapheme_width(grapheme);
+                    if line.width + grapheme_width > width {
+                        break 'outer;
+                    }
+                    line.push_text(grapheme, grapheme_width);
+                }
+            }
+            Output::Incomplete(_) => (),
+            _ => line.push_seq(raw, &output),
+        }
+    }
+    line.line.push_str(ellipsis);
+    line.close()
+}
+
+/// Word-wrap `s` to `width` columns, breaking at spaces (and in the middle of words that
+/// don't fit on a line of their own) as well as at every `\n`. Each line turns off any style
+/// or hyperlink that's still on at the end of it, and the next line turns them back on, so
+/// the lines can be drawn anywhere on their own.
+pub fn ansi_wrap(s: &str, width: usize) -> Vec<String> {
+    let mut wrapper = Wrapper {
+        width,
+        lines: Vec::new(),
+        line: StyledLine::new(),
+        spaces: 0,
+        word: Vec::new(),
+        word_width: 0,
+    };
+    for (raw, output) in outputs(s) {
+        match output {
+            Output::Text(text) => {
+                for grapheme in graphemes(text) {
+                    if grapheme == " " {
+                        wrapper.end_word();
+                        wrapper.spaces += 1;
+                    } else {
+                        let grapheme_width = grapheme_width(grapheme);
+                        wrapper.word.push(Atom::Grapheme(grapheme, grapheme_width));
+                        wrapper.word_width += grapheme_width;
+                    }
+                }
+            }
+            Output::Control(b'\n') => {
+                wrapper.end_word();
+                wrapper.end_line();
+            }
+            Output::Incomplete(_) => (),
+            // sequences go with the word they're in front of (or at the end of)
+            output => wrapper.word.push(Atom::Seq(raw, output)),
+        }
+    }
+    wrapper.end_word();
+    wrapper.end_line();
+    wrapper.lines
+}
+
+/// The decoded pieces of `s`, each with the part of `s` it came from
+fn outputs(s: &str) -> impl Iterator<Item = (&str, Output<'_>)> {
+    let mut decoder = OutputDecoder::new(s.as_bytes());
+    std::iter::from_fn(move || {
+        let start = decoder.position();
+        let output = decoder.next()?;
+        // sequences start with `ESC` and end with an ASCII byte (or the end of `s`), so these
+        // are always char boundaries
+        Some((&s[start..decoder.position()], output))
+    })
+}
+
+/// A line being built up, along with the style and hyperlink that are on at the end of it
+struct StyledLine<'a> {
+    line: String,
+    width: usize,
+    style: Style,
+    /// The `OSC 8` sequence that started the hyperlink we're in
+    hyperlink: Option<&'a str>,
+}
+
+impl<'a> StyledLine<'a> {
+    fn new() -> Self {
+        Self {
+            line: String::new(),
+            width: 0,
+            style: Style::new(),
+            hyperlink: None,
+        }
+    }
+
+    fn push_text(&mut self, text: &str, width: usize) {
+        self.line.push_str(text);
+        self.width += width;
+    }
+
+    fn push_seq(&mut self, raw: &'a str, output: &Output) {
+        self.line.push_str(raw);
+        match output {
+            Output::Sgr(changes) => {
+                self.style = changes
+                    .iter()
+                    .fold(self.style, |style, sgr| sgr.apply(style));
+            }
+            Output::Seq(StdoutEscSeq::BeginHyperlink(..)) => self.hyperlink = Some(raw),
+            Output::Seq(StdoutEscSeq::EndHyperlink) => self.hyperlink = None,
+            _ => (),
+        }
+    }
+
+    /// Hand back the line, with the style and hyperlink turned off at the end
+    fn close(&mut self) -> String {
+        let mut line = mem::take(&mut self.line);
+        if self.style != Style::new() {
+            line.push_str(&StdoutEscSeq::ResetStyle.to_string());
+        }
+        if self.hyperlink.is_some() {
+            line.push_str(&StdoutEscSeq::EndHyperlink.to_string());
+        }
+        self.width = 0;
+        line
+    }
+
+    /// Start the (empty) line with whatever style and hyperlink the last one ended with
+    fn reopen(&mut self) {
+        if self.style != Style::new() {
+            self.line
+                .push_str(&StdoutEscSeq::SetStyle(self.style).to_string());
+        }
+        if let Some(hyperlink) = self.hyperlink {
+            self.line.push_str(hyperlink);
+        }
+    }
+}
+
+enum Atom<'a> {
+    Grapheme(&'a str, usize),
+    Seq(&'a str, Output<'a>),
+}
+
+struct Wrapper<'a> {
+    width: usize,
+    lines: Vec<String>,
+    line: StyledLine<'a>,
+    /// The number of spaces since the last word, which are left out if the line breaks here
+    spaces: usize,
+    word: Vec<Atom<'a>>,
+    word_width: usize,
+}
+
+impl<'a> Wrapper<'a> {
+    /// Put the word we've got so far on the line, or the next one if it doesn't fit
+    fn end_word(&mut self) {
+        if self.word.is_empty() {
+            return;
+        }
+        if self.line.width + self.spaces + self.word_width <= self.width {
+            self.line.push_text(&" ".repeat(self.spaces), self.spaces);
+        } else if self.line.width > 0 {
+            self.break_line();
+        }
+        self.spaces = 0;
+        for atom in mem::take(&mut self.word) {
+            match atom {
+                Atom::Grapheme(grapheme, width) => {
+                    // words too long for a line of their own get split up
+                    if self.line.width + width > self.width && self.line.width > 0 {
+                        self.break_line();
+                    }
+                    self.line.push_text(grapheme, width);
+                }
+                Atom::Seq(raw, output) => self.line.push_seq(raw, &output),
+            }
+        }
+        self.word_width = 0;
+    }
+
+    /// Finish the line, leaving out any spaces at the end
+    fn end_line(&mut self) {
+        self.spaces = 0;
+        self.break_line();
+    }
+
+    fn break_line(&mut self) {
+        self.lines.push(self.line.close());
+        self.line.reopen();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_closes_styles() {
+        let cell = ansi_truncate("\x1b[31mtoo long for this\x1b[0m", 8, "…");
+        assert_eq!(cell, "\x1b[31mtoo lon…\x1b[0m");
+    }
+
+    #[test]
+    fn truncate_leaves_short_strings_alone() {
+        assert_eq!(
+            ansi_truncate("\x1b[1mfits\x1b[0m", 4, "…"),
+            "\x1b[1mfits\x1b[0m"
+        );
+        assert_eq!(ansi_truncate("", 0, "…"), "");
+    }
+
+    #[test]
+    fn truncate_without_room_for_the_ellipsis() {
+        assert_eq!(ansi_truncate("abc", 0, "…"), "");
+        assert_eq!(ansi_truncate("abc", 2, "..."), "ab");
+    }
+
+    #[test]
+    fn truncate_doesnt_split_wide_characters() {
+        assert_eq!(ansi_truncate("日本語", 4, "…"), "日…");
+        assert_eq!(ansi_truncate("日本語", 5, ""), "日本");
+    }
+
+    #[test]
+    fn width_and_strip_skip_escape_sequences() {
+        let s = "\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\ \x1b[1m日本\x1b[0m";
+        assert_eq!(ansi_width(s), 9);
+        assert_eq!(ansi_strip(s), "link 日本");
+    }
+}
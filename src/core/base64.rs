@@ -0,0 +1,58 @@
+// Just enough (standard, padded) base64 for the escape sequences that need it
+
+use std::io;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Write the base64 encoding of `bytes` to `w`, a block at a time through a buffer on the
+/// stack
+pub(crate) fn encode_to<W: io::Write + ?Sized>(bytes: &[u8], w: &mut W) -> io::Result<()> {
+    let mut block = [0; 256];
+    for chunks in bytes.chunks(block.len() / 4 * 3) {
+        let mut len = 0;
+        for chunk in chunks.chunks(3) {
+            let b = [
+                chunk[0],
+                chunk.get(1).copied().unwrap_or(0),
+                chunk.get(2).copied().unwrap_or(0),
+            ];
+            let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+            for i in 0..4 {
+                block[len] = if i <= chunk.len() {
+                    ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize]
+                } else {
+                    b'='
+                };
+                len += 1;
+            }
+        }
+        w.write_all(&block[..len])?;
+    }
+    Ok(())
+}
+
+/// Decode base64, ignoring whitespace and padding. Returns `None` if there's anything else in
+/// there that isn't base64.
+pub(crate) fn decode(encoded: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(encoded.len() / 4 * 3);
+    let mut n = 0u32;
+    let mut bits = 0;
+    for &byte in encoded {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' | b' ' | b'\n' | b'\r' | b'\t' => continue,
+            _ => return None,
+        };
+        n = n << 6 | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((n >> bits) as u8);
+        }
+    }
+    Some(out)
+}
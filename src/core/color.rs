@@ -0,0 +1,393 @@
+use std::env;
+use std::os::unix::io::RawFd;
+
+use once_cell::sync::Lazy;
+
+use super::{Color, Style, TermInfo};
+
+/// How many colors a terminal can show, from least to most
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorDepth {
+    /// Don't send any colors at all (attributes like bold are still fine)
+    NoColor,
+    /// Only the 8 basic colors, e.g. the Linux console
+    Ansi8,
+    /// The 8 basic colors and their bright versions
+    Ansi16,
+    /// The xterm 256 color palette
+    Ansi256,
+    /// 24-bit RGB
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Work out the color depth of the terminal on stdout from the environment and `terminfo`.
+    ///
+    /// `NO_COLOR` turns colors off, and so does stdout not being a terminal unless
+    /// `CLICOLOR_FORCE` is set. After that, `COLORTERM=truecolor` (or `24bit`), then the
+    /// terminfo `Tc`/`RGB`/`colors` capabilities and finally the name in `$TERM` are used.
+    pub fn detect(terminfo: Option<&TermInfo>) -> Self {
+        Self::detect_for(libc::STDOUT_FILENO, terminfo)
+    }
+
+    /// `detect`, for the terminal on `fd` instead of stdout
+    pub fn detect_for(fd: RawFd, terminfo: Option<&TermInfo>) -> Self {
+        let is_tty = unsafe { libc::isatty(fd) } == 1;
+        Self::detect_from(|name| env::var(name).ok(), terminfo, is_tty)
+    }
+
+    fn detect_from(
+        var: impl Fn(&str) -> Option<String>,
+        terminfo: Option<&TermInfo>,
+        is_tty: bool,
+    ) -> Self {
+        let set = |name| var(name).is_some_and(|value| !value.is_empty());
+        if set("NO_COLOR") {
+            return Self::NoColor;
+        }
+        let forced = set("CLICOLOR_FORCE") && var("CLICOLOR_FORCE").as_deref() != Some("0");
+        if !forced && (!is_tty || var("CLICOLOR").as_deref() == Some("0")) {
+            return Self::NoColor;
+        }
+
+        // forcing colors on doesn't mean we know any more about the terminal
+        match Self::detect_terminal(&var, terminfo) {
+            Self::NoColor if forced => Self::Ansi16,
+            detected => detected,
+        }
+    }
+
+    fn detect_terminal(var: &impl Fn(&str) -> Option<String>, terminfo: Option<&TermInfo>) -> Self {
+        if let Some("truecolor" | "24bit") = var("COLORTERM").as_deref() {
+            return Self::TrueColor;
+        }
+        if let Some(terminfo) = terminfo {
+            let rgb = terminfo.flag("RGB")
+                || terminfo.number("RGB").is_some()
+                || terminfo.string("RGB").is_some();
+            if terminfo.flag("Tc") || rgb {
+                return Self::TrueColor;
+            }
+            match terminfo.number("colors") {
+                Some(colors) if colors >= 0x100_0000 => return Self::TrueColor,
+                Some(colors) if colors >= 256 => return Self::Ansi256,
+                Some(colors) if colors >= 16 => return Self::Ansi16,
+                Some(colors) if colors >= 8 => return Self::Ansi8,
+                Some(_) => return Self::NoColor,
+                None => (),
+            }
+        }
+
+        let term = var("TERM").unwrap_or_default();
+        match term.as_str() {
+            "" | "dumb" => Self::NoColor,
+            "linux" => Self::Ansi8,
+            term if term.ends_with("-direct") || term.contains("truecolor") => Self::TrueColor,
+            term if term.contains("256") => Self::Ansi256,
+            _ => Self::Ansi16,
+        }
+    }
+}
+
+/// Whether the terminal has a light or a dark background, so you can pick colors that show up
+/// on it. See `TsStdout::detect_color_scheme`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum ColorScheme {
+    Light,
+    /// What most terminals are, so it's what we guess when we can't tell
+    #[default]
+    Dark,
+}
+
+impl ColorScheme {
+    /// The scheme for a terminal with this background color: light if it's lighter than a
+    /// middle gray
+    pub fn from_background(rgb: (u8, u8, u8)) -> Self {
+        if Lab::from_rgb(rgb).0 > 0.6 {
+            Self::Light
+        } else {
+            Self::Dark
+        }
+    }
+
+    /// Guess from `COLORFGBG` (e.g. `15;0`, the palette indexes of the foreground and
+    /// background), which rxvt, Konsole and a few others set. `None` if it isn't set.
+    pub fn from_env() -> Option<Self> {
+        Self::from_env_with(|name| env::var(name).ok())
+    }
+
+    fn from_env_with(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let background: u8 = var("COLORFGBG")?.rsplit(';').next()?.parse().ok()?;
+        // white, and the bright colors apart from bright black
+        match background {
+            7 | 9..=15 => Some(Self::Light),
+            _ => Some(Self::Dark),
+        }
+    }
+}
+
+/// The default xterm values for the 16 named colors. Every theme changes these, but they're a
+/// decent guess for picking the closest one.
+const NAMED_RGB: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00),
+    (0xcd, 0x00, 0x00),
+    (0x00, 0xcd, 0x00),
+    (0xcd, 0xcd, 0x00),
+    (0x00, 0x00, 0xee),
+    (0xcd, 0x00, 0xcd),
+    (0x00, 0xcd, 0xcd),
+    (0xe5, 0xe5, 0xe5),
+    (0x7f, 0x7f, 0x7f),
+    (0xff, 0x00, 0x00),
+    (0x00, 0xff, 0x00),
+    (0xff, 0xff, 0x00),
+    (0x5c, 0x5c, 0xff),
+    (0xff, 0x00, 0xff),
+    (0x00, 0xff, 0xff),
+    (0xff, 0xff, 0xff),
+];
+
+/// The levels used by each axis of the 6x6x6 color cube (indexes 16 to 231)
+const CUBE_LEVELS: [u8; 6] = [0x00, 0x5f, 0x87, 0xaf, 0xd7, 0xff];
+
+/// A color in the OKLab space, where euclidean distance is roughly how different two colors
+/// look
+#[derive(Debug, Clone, Copy)]
+struct Lab(f32, f32, f32);
+
+impl Lab {
+    fn from_rgb((r, g, b): (u8, u8, u8)) -> Self {
+        fn linear(c: u8) -> f32 {
+            let c = c as f32 / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        }
+        let (r, g, b) = (linear(r), linear(g), linear(b));
+        let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+        let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+        let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+        Lab(
+            0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+        )
+    }
+
+    fn distance(&self, other: &Lab) -> f32 {
+        (self.0 - other.0).powi(2) + (self.1 - other.1).powi(2) + (self.2 - other.2).powi(2)
+    }
+}
+
+/// The xterm 256 color palette in OKLab, indexed by palette index
+static PALETTE_LAB: Lazy<Vec<Lab>> = Lazy::new(|| {
+    (0..=255)
+        .map(|i| Lab::from_rgb(Color::Indexed(i).to_rgb()))
+        .collect()
+});
+
+/// The palette index in `candidates` that looks the most like `rgb`
+fn nearest(rgb: (u8, u8, u8), candidates: std::ops::Range<usize>) -> u8 {
+    let lab = Lab::from_rgb(rgb);
+    candidates
+        .min_by(|&a, &b| {
+            let (a, b) = (lab.distance(&PALETTE_LAB[a]), lab.distance(&PALETTE_LAB[b]));
+            a.total_cmp(&b)
+        })
+        .unwrap() as u8
+}
+
+impl Color {
+    /// What this color (probably) looks like. Named colors use the xterm defaults, since we
+    /// can't know the user's theme.
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
+        match (self.named_index(), *self) {
+            (Some(i), _) => NAMED_RGB[i as usize],
+            (None, Color::Indexed(i)) if i < 16 => NAMED_RGB[i as usize],
+            (None, Color::Indexed(i)) if i < 232 => {
+                let i = i - 16;
+                (
+                    CUBE_LEVELS[(i / 36) as usize],
+                    CUBE_LEVELS[(i / 6 % 6) as usize],
+                    CUBE_LEVELS[(i % 6) as usize],
+                )
+            }
+            (None, Color::Indexed(i)) => {
+                let level = 8 + 10 * (i - 232);
+                (level, level, level)
+            }
+            (None, Color::Rgb(r, g, b)) => (r, g, b),
+            (None, _) => unreachable!(),
+        }
+    }
+
+    /// The closest color a terminal with `depth` can show, `None` for `NoColor`.
+    ///
+    /// RGB colors only map onto the cube and grays of the 256 color palette (16 to 255), since
+    /// the first 16 depend on the theme.
+    pub fn downsample(&self, depth: ColorDepth) -> Option<Color> {
+        let named = |i: u8| Color::NAMED[i as usize];
+        match (depth, self.named_index(), *self) {
+            (ColorDepth::NoColor, _, _) => None,
+            (ColorDepth::TrueColor, _, color) => Some(color),
+            (ColorDepth::Ansi256 | ColorDepth::Ansi16, Some(_), color) => Some(color),
+            (ColorDepth::Ansi8, Some(i), _) => Some(named(i % 8)),
+            (ColorDepth::Ansi256, None, Color::Indexed(i)) => Some(Color::Indexed(i)),
+            (ColorDepth::Ansi256, None, color) => {
+                Some(Color::Indexed(nearest(color.to_rgb(), 16..256)))
+            }
+            (ColorDepth::Ansi16, None, Color::Indexed(i)) if i < 16 => Some(named(i)),
+            (ColorDepth::Ansi16, None, color) => Some(named(nearest(color.to_rgb(), 0..16))),
+            (ColorDepth::Ansi8, None, Color::Indexed(i)) if i < 16 => Some(named(i % 8)),
+            (ColorDepth::Ansi8, None, color) => Some(named(nearest(color.to_rgb(), 0..8))),
+        }
+    }
+}
+
+impl Style {
+    /// This style with every color replaced by the closest one a terminal with `depth` can
+    /// show (or removed, for `NoColor`)
+    pub fn downsample(&self, depth: ColorDepth) -> Style {
+        Style {
+            fg: self.fg.and_then(|color| color.downsample(depth)),
+            bg: self.bg.and_then(|color| color.downsample(depth)),
+            underline_color: self
+                .underline_color
+                .and_then(|color| color.downsample(depth)),
+            ..*self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `detect_from` with the environment set to `vars`
+    fn detect(vars: &[(&str, &str)], is_tty: bool) -> ColorDepth {
+        let var = |name: &str| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        };
+        ColorDepth::detect_from(var, None, is_tty)
+    }
+
+    #[test]
+    fn no_color_wins() {
+        let vars = [
+            ("NO_COLOR", "1"),
+            ("CLICOLOR_FORCE", "1"),
+            ("COLORTERM", "truecolor"),
+        ];
+        assert_eq!(detect(&vars, true), ColorDepth::NoColor);
+        // empty means unset
+        let vars = [("NO_COLOR", ""), ("TERM", "xterm")];
+        assert_eq!(detect(&vars, true), ColorDepth::Ansi16);
+    }
+
+    #[test]
+    fn clicolor() {
+        let term = ("TERM", "xterm-256color");
+        assert_eq!(detect(&[term], false), ColorDepth::NoColor);
+        let force = ("CLICOLOR_FORCE", "1");
+        assert_eq!(detect(&[force, term], false), ColorDepth::Ansi256);
+        // forced on, but we don't know anything about the terminal
+        assert_eq!(detect(&[force], false), ColorDepth::Ansi16);
+        assert_eq!(
+            detect(&[("CLICOLOR_FORCE", "0"), term], false),
+            ColorDepth::NoColor
+        );
+
+        let off = ("CLICOLOR", "0");
+        assert_eq!(detect(&[off, term], true), ColorDepth::NoColor);
+        assert_eq!(detect(&[off, force, term], true), ColorDepth::Ansi256);
+        assert_eq!(
+            detect(&[("CLICOLOR", "1"), term], true),
+            ColorDepth::Ansi256
+        );
+    }
+
+    #[test]
+    fn colorterm_beats_term() {
+        for colorterm in ["truecolor", "24bit"] {
+            let vars = [("COLORTERM", colorterm), ("TERM", "linux")];
+            assert_eq!(detect(&vars, true), ColorDepth::TrueColor);
+        }
+        let vars = [("COLORTERM", "yes"), ("TERM", "linux")];
+        assert_eq!(detect(&vars, true), ColorDepth::Ansi8);
+    }
+
+    #[test]
+    fn term_fallbacks() {
+        assert_eq!(detect(&[], true), ColorDepth::NoColor);
+        for (term, depth) in [
+            ("", ColorDepth::NoColor),
+            ("dumb", ColorDepth::NoColor),
+            ("linux", ColorDepth::Ansi8),
+            ("xterm-direct", ColorDepth::TrueColor),
+            ("foot-truecolor", ColorDepth::TrueColor),
+            ("xterm-256color", ColorDepth::Ansi256),
+            ("screen.xterm-256color", ColorDepth::Ansi256),
+            ("xterm", ColorDepth::Ansi16),
+            ("vt100", ColorDepth::Ansi16),
+        ] {
+            assert_eq!(detect(&[("TERM", term)], true), depth, "{term:?}");
+        }
+    }
+
+    #[test]
+    fn terminfo_beats_term() {
+        let bytes = include_bytes!("../../tests/terminfo/xterm-256color");
+        let terminfo = TermInfo::from_bytes(bytes).unwrap();
+        let var = |name: &str| (name == "TERM").then(|| "dumb".to_string());
+        let depth = ColorDepth::detect_from(var, Some(&terminfo), true);
+        assert_eq!(depth, ColorDepth::Ansi256);
+    }
+
+    #[test]
+    fn downsample() {
+        let red = Color::Rgb(255, 0, 0);
+        assert_eq!(red.downsample(ColorDepth::TrueColor), Some(red));
+        assert_eq!(
+            red.downsample(ColorDepth::Ansi256),
+            Some(Color::Indexed(196))
+        );
+        assert_eq!(red.downsample(ColorDepth::Ansi16), Some(Color::BrightRed));
+        assert_eq!(red.downsample(ColorDepth::Ansi8), Some(Color::Red));
+        assert_eq!(red.downsample(ColorDepth::NoColor), None);
+
+        // RGB never lands on the themed colors, even when one is an exact match
+        let white = Color::Rgb(255, 255, 255);
+        assert_eq!(
+            white.downsample(ColorDepth::Ansi256),
+            Some(Color::Indexed(231))
+        );
+        let gray = Color::Rgb(0x80, 0x80, 0x80);
+        assert_eq!(
+            gray.downsample(ColorDepth::Ansi256),
+            Some(Color::Indexed(244))
+        );
+
+        assert_eq!(
+            Color::Indexed(9).downsample(ColorDepth::Ansi16),
+            Some(Color::BrightRed)
+        );
+        assert_eq!(
+            Color::Indexed(9).downsample(ColorDepth::Ansi8),
+            Some(Color::Red)
+        );
+        assert_eq!(
+            Color::BrightRed.downsample(ColorDepth::Ansi8),
+            Some(Color::Red)
+        );
+        let cube_red = Color::Indexed(196);
+        assert_eq!(cube_red.downsample(ColorDepth::Ansi256), Some(cube_red));
+        assert_eq!(
+            cube_red.downsample(ColorDepth::Ansi16),
+            Some(Color::BrightRed)
+        );
+    }
+}
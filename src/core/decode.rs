@@ -0,0 +1,826 @@
+use std::str;
+
+use super::{
+    base64, parse_rgb_spec, Attributes, ClipboardSelection, Color, CursorStyle, StdoutEscSeq,
+    Style, TerminalColor, TitleKind, UnderlineStyle, BEL_ASCII, ESC_ASCII,
+};
+
+/// One thing in a stream of output, see `OutputDecoder`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Output<'a> {
+    /// Text to print, without any control characters
+    Text(&'a str),
+    /// A C0 control character, like `\r`, `\n`, `\t` or `\x08`. `BEL` is `Seq(Bell)`.
+    Control(u8),
+    /// A sequence `StdoutEscSeq` can write, read the way the terminal reads it, so `CSI 0 A`
+    /// is `MoveUp(1)`. Text in OSC sequences (titles, URIs, ...) is borrowed as is, i.e. URIs
+    /// are still percent-encoded.
+    Seq(StdoutEscSeq<'a>),
+    /// An SGR sequence (`CSI ... m`), as the changes it makes in order
+    Sgr(Vec<Sgr>),
+    /// An OSC string we don't know, with its body (between `ESC ]` and the terminator)
+    Osc(&'a [u8]),
+    /// A DCS string we don't know (sixel images, tmux passthrough, ...), with its body
+    Dcs(&'a [u8]),
+    /// An APC string (kitty graphics commands), with its body
+    Apc(&'a [u8]),
+    /// Any other escape sequence, or bytes that aren't valid UTF-8, exactly as they were
+    Unknown(&'a [u8]),
+    /// The start of something that got cut off by the end of the input, exactly as it was.
+    /// When decoding a stream as it comes in, hang on to these and try again with more.
+    Incomplete(&'a [u8]),
+}
+
+/// One change an SGR sequence makes to the style
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Sgr {
+    /// Back to the default style (`0`, or no parameters at all)
+    Reset,
+    On(Attributes),
+    /// Turn attributes off. `22` turns off bold and dim together.
+    Off(Attributes),
+    /// `None` turns the underline off
+    Underline(Option<UnderlineStyle>),
+    /// `None` is back to the default color
+    Foreground(Option<Color>),
+    Background(Option<Color>),
+    UnderlineColor(Option<Color>),
+    /// A parameter we don't know, like `6` (rapid blink) or `10` (fonts). For extended colors
+    /// we can't make sense of, this is the `38`/`48`/`58` and the rest of the sequence is
+    /// skipped.
+    Unknown(u32),
+}
+
+impl Sgr {
+    /// `style` with this change made to it
+    pub fn apply(&self, style: Style) -> Style {
+        let mut style = style;
+        match *self {
+            Self::Reset => style = Style::new(),
+            Self::On(attributes) => style.attributes.insert(attributes),
+            Self::Off(attributes) => style.attributes.remove(attributes),
+            Self::Underline(underline) => style.underline = underline,
+            Self::Foreground(color) => style.fg = color,
+            Self::Background(color) => style.bg = color,
+            Self::UnderlineColor(color) => style.underline_color = color,
+            Self::Unknown(_) => (),
+        }
+        style
+    }
+}
+
+/// Turns bytes written to a terminal back into text and sequences, the opposite of
+/// `StdoutEscSeq::as_bytes`. Nothing is lost: every byte ends up in exactly one `Output`, and
+/// `position` says where the next one starts.
+///
+/// ```rs
+/// let mut style = Style::new();
+/// for output in OutputDecoder::new(&bytes) {
+///     match output {
+///         Output::Text(text) => println!("{:?} in {:?}", text, style),
+///         Output::Sgr(changes) => style = changes.iter().fold(style, |s, c| c.apply(s)),
+///         _ => (),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct OutputDecoder<'a> {
+    bytes: &'a [u8],
+    index: usize,
+}
+
+impl<'a> OutputDecoder<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, index: 0 }
+    }
+
+    /// How many bytes have been decoded so far
+    pub fn position(&self) -> usize {
+        self.index
+    }
+}
+
+impl<'a> Iterator for OutputDecoder<'a> {
+    type Item = Output<'a>;
+
+    fn next(&mut self) -> Option<Output<'a>> {
+        let rest = &self.bytes[self.index..];
+        let (len, output) = match *rest.first()? {
+            ESC_ASCII => decode_esc(rest),
+            BEL_ASCII => (1, Output::Seq(StdoutEscSeq::Bell)),
+            byte @ (0..=0x1f | 0x7f) => (1, Output::Control(byte)),
+            _ => decode_text(rest),
+        };
+        self.index += len;
+        Some(output)
+    }
+}
+
+fn decode_text(rest: &[u8]) -> (usize, Output<'_>) {
+    let end = rest
+        .iter()
+        .position(|&b| b < 0x20 || b == 0x7f)
+        .unwrap_or(rest.len());
+    match str::from_utf8(&rest[..end]) {
+        Ok(text) => (end, Output::Text(text)),
+        Err(e) if e.valid_up_to() > 0 => {
+            let len = e.valid_up_to();
+            // checked by `from_utf8`
+            (len, Output::Text(str::from_utf8(&rest[..len]).unwrap()))
+        }
+        Err(e) => match e.error_len() {
+            Some(len) => (len, Output::Unknown(&rest[..len])),
+            None if end == rest.len() => (end, Output::Incomplete(rest)),
+            // cut off by a control character, not the end of the input
+            None => (end, Output::Unknown(&rest[..end])),
+        },
+    }
+}
+
+fn decode_esc(rest: &[u8]) -> (usize, Output<'_>) {
+    let Some(&kind) = rest.get(1) else {
+        return (1, Output::Incomplete(rest));
+    };
+    match kind {
+        b'[' => decode_csi(rest),
+        b']' | b'P' | b'_' | b'^' | b'X' => decode_string(rest, kind),
+        b'7' => (2, Output::Seq(StdoutEscSeq::SaveCursorPosition)),
+        b'8' => (2, Output::Seq(StdoutEscSeq::RestorCursorPosition)),
+        b'H' => (2, Output::Seq(StdoutEscSeq::SetTabStop)),
+        // intermediates and then a final byte, like `ESC ( B`
+        0x20..=0x2f => {
+            let Some(end) = rest[1..].iter().position(|b| !(0x20..=0x2f).contains(b)) else {
+                return (rest.len(), Output::Incomplete(rest));
+            };
+            let end = end + 1;
+            if (0x30..=0x7e).contains(&rest[end]) {
+                (end + 1, Output::Unknown(&rest[..end + 1]))
+            } else {
+                (end, Output::Unknown(&rest[..end]))
+            }
+        }
+        0x30..=0x7e => (2, Output::Unknown(&rest[..2])),
+        // the terminal drops the `ESC` and carries on with whatever's after it
+        _ => (1, Output::Unknown(&rest[..1])),
+    }
+}
+
+fn decode_csi(rest: &[u8]) -> (usize, Output<'_>) {
+    let params_end = 2 + rest[2..]
+        .iter()
+        .take_while(|b| (0x30..=0x3f).contains(*b))
+        .count();
+    let end = params_end
+        + rest[params_end..]
+            .iter()
+            .take_while(|b| (0x20..=0x2f).contains(*b))
+            .count();
+    let Some(&last) = rest.get(end) else {
+        return (rest.len(), Output::Incomplete(rest));
+    };
+    if !(0x40..=0x7e).contains(&last) {
+        return (end, Output::Unknown(&rest[..end]));
+    }
+    let seq = &rest[..end + 1];
+    let params = &rest[2..params_end];
+    let intermediates = &rest[params_end..end];
+    let output = match (params, intermediates, last) {
+        (_, b"", b'm') => sgr(params).map(Output::Sgr),
+        _ => csi_seq(params, intermediates, last).map(Output::Seq),
+    };
+    (end + 1, output.unwrap_or(Output::Unknown(seq)))
+}
+
+/// Numbers separated by `;`, `None` for the ones that are left out. Anything else (like
+/// sub-parameters with `:`) gives `None`.
+fn numbers(params: &[u8]) -> Option<Vec<Option<u32>>> {
+    if params.is_empty() {
+        return Some(Vec::new());
+    }
+    params.split(|&b| b == b';').map(number).collect()
+}
+
+fn number(param: &[u8]) -> Option<Option<u32>> {
+    if param.is_empty() {
+        return Some(None);
+    }
+    if !param.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    str::from_utf8(param).ok()?.parse().ok().map(Some)
+}
+
+fn csi_seq(params: &[u8], intermediates: &[u8], last: u8) -> Option<StdoutEscSeq<'static>> {
+    use StdoutEscSeq::*;
+
+    if let Some(mode) = params.strip_prefix(b"?") {
+        let mode = number(mode)??;
+        return match (intermediates, last, mode) {
+            (b"", b'h' | b'l', _) => private_mode(mode, last == b'h'),
+            (b"$", b'p', _) => Some(RequestPrivateMode(mode)),
+            _ => None,
+        };
+    }
+    let params = numbers(params)?;
+    let param = |i: usize| params.get(i).copied().flatten();
+    // counts and positions of `0` mean `1`, like when they're left out
+    let count = |i: usize| param(i).unwrap_or(1).max(1);
+    let max_params = match (intermediates, last) {
+        (b"", b'H' | b'f' | b'r' | b't') => 2,
+        _ => 1,
+    };
+    if params.len() > max_params {
+        return None;
+    }
+    let seq = match (intermediates, last) {
+        (b"", b'A') => MoveUp(count(0)),
+        (b"", b'B') => MoveDown(count(0)),
+        (b"", b'C') => MoveRight(count(0)),
+        (b"", b'D') => MoveLeft(count(0)),
+        (b"", b'E') => MoveToNextLine(count(0)),
+        (b"", b'F') => MoveToPreviousLine(count(0)),
+        (b"", b'G') => MoveToColumn(count(0) - 1),
+        (b"", b'd') => MoveToRow(count(0) - 1),
+        (b"", b'H' | b'f') => MoveTo(count(0) - 1, count(1) - 1),
+        (b"", b'S') => ScrollUp(count(0)),
+        (b"", b'T') => ScrollDown(count(0)),
+        (b"", b'L') => InsertLines(count(0)),
+        (b"", b'M') => DeleteLines(count(0)),
+        (b"", b'@') => InsertChars(count(0)),
+        (b"", b'P') => DeleteChars(count(0)),
+        (b"", b'X') => EraseChars(count(0)),
+        (b"", b'I') => TabForward(count(0)),
+        (b"", b'Z') => TabBackward(count(0)),
+        (b"", b'g') => match param(0).unwrap_or(0) {
+            0 => ClearTabStop,
+            3 => ClearAllTabStops,
+            _ => return None,
+        },
+        (b"", b'r') => match (param(0), param(1)) {
+            (None, None) => ResetScrollRegion,
+            (_, Some(bottom)) => SetScrollRegion(count(0) - 1, bottom.max(1) - 1),
+            // the bottom is the last row, whatever that is
+            (Some(_), None) => return None,
+        },
+        (b"", b'J') => match param(0).unwrap_or(0) {
+            0 => EraseToEndOfScreen,
+            1 => EraseToStartOfScreen,
+            2 => EraseEntireScreen,
+            3 => EraseScrollback,
+            _ => return None,
+        },
+        (b"", b'K') => match param(0).unwrap_or(0) {
+            0 => EraseToEndOfLine,
+            1 => EraseToStartOfLine,
+            2 => EraseEntireLine,
+            _ => return None,
+        },
+        (b"", b's') if params.is_empty() => ScoSaveCursorPosition,
+        (b"", b'u') if params.is_empty() => ScoRestoreCursorPosition,
+        (b"", b't') => {
+            let kind = match param(1).unwrap_or(0) {
+                0 => TitleKind::Both,
+                1 => TitleKind::IconName,
+                2 => TitleKind::WindowTitle,
+                _ => return None,
+            };
+            match param(0) {
+                Some(22) => PushTitle(kind),
+                Some(23) => PopTitle(kind),
+                _ => return None,
+            }
+        }
+        (b"", b'n') if param(0) == Some(6) => RequestCursorPosition,
+        (b" ", b'q') => SetCursorStyle(match param(0).unwrap_or(0) {
+            0 => CursorStyle::Default,
+            1 => CursorStyle::BlinkingBlock,
+            2 => CursorStyle::SteadyBlock,
+            3 => CursorStyle::BlinkingUnderline,
+            4 => CursorStyle::SteadyUnderline,
+            5 => CursorStyle::BlinkingBar,
+            6 => CursorStyle::SteadyBar,
+            _ => return None,
+        }),
+        _ => return None,
+    };
+    Some(seq)
+}
+
+fn private_mode(mode: u32, set: bool) -> Option<StdoutEscSeq<'static>> {
+    use StdoutEscSeq::*;
+
+    let (on, off) = match mode {
+        25 => (ShowCursor, HideCursor),
+        47 => (SaveScreen, RestoreScreen),
+        1042 => (EnableUrgentBell, DisableUrgentBell),
+        1047 => (SwitchToAlternateScreen, SwitchToMainScreen),
+        1049 => (EnterAlternateScreen, LeaveAlternateScreen),
+        2026 => (BeginSynchronizedUpdate, EndSynchronizedUpdate),
+        _ => return None,
+    };
+    Some(if set { on } else { off })
+}
+
+/// The changes in an SGR sequence, with `params` being everything between `CSI` and `m`
+fn sgr(params: &[u8]) -> Option<Vec<Sgr>> {
+    if params
+        .first()
+        .is_some_and(|b| !b.is_ascii_digit() && *b != b';' && *b != b':')
+    {
+        // private SGR-like sequences, e.g. `CSI > 4 ; 1 m` (modifyOtherKeys)
+        return None;
+    }
+    if params.is_empty() {
+        return Some(vec![Sgr::Reset]);
+    }
+    let mut groups = params.split(|&b| b == b';');
+    let mut changes = Vec::new();
+    while let Some(group) = groups.next() {
+        let mut subparams = group.split(|&b| b == b':');
+        let code = number(subparams.next().unwrap_or_default())?.unwrap_or(0);
+        let change = match code {
+            0 => Sgr::Reset,
+            1 => Sgr::On(Attributes::BOLD),
+            2 => Sgr::On(Attributes::DIM),
+            3 => Sgr::On(Attributes::ITALIC),
+            4 => match subparams.next().map(number) {
+                None => Sgr::Underline(Some(UnderlineStyle::Single)),
+                Some(style) => match style?.unwrap_or(1) {
+                    0 => Sgr::Underline(None),
+                    1 => Sgr::Underline(Some(UnderlineStyle::Single)),
+                    2 => Sgr::Underline(Some(UnderlineStyle::Double)),
+                    3 => Sgr::Underline(Some(UnderlineStyle::Curly)),
+                    4 => Sgr::Underline(Some(UnderlineStyle::Dotted)),
+                    5 => Sgr::Underline(Some(UnderlineStyle::Dashed)),
+                    _ => Sgr::Unknown(code),
+                },
+            },
+            5 => Sgr::On(Attributes::BLINK),
+            7 => Sgr::On(Attributes::REVERSE),
+            8 => Sgr::On(Attributes::HIDDEN),
+            9 => Sgr::On(Attributes::STRIKETHROUGH),
+            21 => Sgr::Underline(Some(UnderlineStyle::Double)),
+            22 => Sgr::Off(Attributes::BOLD | Attributes::DIM),
+            23 => Sgr::Off(Attributes::ITALIC),
+            24 => Sgr::Underline(None),
+            25 => Sgr::Off(Attributes::BLINK),
+            27 => Sgr::Off(Attributes::REVERSE),
+            28 => Sgr::Off(Attributes::HIDDEN),
+            29 => Sgr::Off(Attributes::STRIKETHROUGH),
+            53 => Sgr::On(Attributes::OVERLINE),
+            55 => Sgr::Off(Attributes::OVERLINE),
+            30..=37 => Sgr::Foreground(Some(Color::NAMED[code as usize - 30])),
+            40..=47 => Sgr::Background(Some(Color::NAMED[code as usize - 40])),
+            90..=97 => Sgr::Foreground(Some(Color::NAMED[code as usize - 90 + 8])),
+            100..=107 => Sgr::Background(Some(Color::NAMED[code as usize - 100 + 8])),
+            39 => Sgr::Foreground(None),
+            49 => Sgr::Background(None),
+            59 => Sgr::UnderlineColor(None),
+            38 | 48 | 58 => {
+                let subparams: Vec<_> = subparams.collect();
+                let color = if subparams.is_empty() {
+                    // `38;5;n` or `38;2;r;g;b`, taking the parameters that follow
+                    extended_color(|| groups.next())
+                } else {
+                    // `38:5:n`, `38:2:r:g:b` or `38:2:colorspace:r:g:b`
+                    let mut subparams = match subparams.first() {
+                        Some(&&[b'2']) if subparams.len() == 5 => {
+                            [&subparams[..1], &subparams[2..]].concat().into_iter()
+                        }
+                        _ => subparams.into_iter(),
+                    };
+                    extended_color(|| subparams.next())
+                };
+                match (color, code) {
+                    (Some(color), 38) => Sgr::Foreground(Some(color)),
+                    (Some(color), 48) => Sgr::Background(Some(color)),
+                    (Some(color), _) => Sgr::UnderlineColor(Some(color)),
+                    (None, _) => {
+                        changes.push(Sgr::Unknown(code));
+                        break;
+                    }
+                }
+            }
+            _ => Sgr::Unknown(code),
+        };
+        changes.push(change);
+    }
+    Some(changes)
+}
+
+/// The color after a `38`/`48`/`58`, with `next` giving the parameters after it
+fn extended_color<'a>(mut next: impl FnMut() -> Option<&'a [u8]>) -> Option<Color> {
+    let mut next_u8 = || -> Option<u8> { u8::try_from(number(next()?)??).ok() };
+    match next_u8()? {
+        5 => Some(Color::Indexed(next_u8()?)),
+        2 => Some(Color::Rgb(next_u8()?, next_u8()?, next_u8()?)),
+        _ => None,
+    }
+}
+
+/// OSC, DCS, APC, PM and SOS strings, which go on until `ST` (or `BEL`, for OSC)
+fn decode_string(rest: &[u8], kind: u8) -> (usize, Output<'_>) {
+    let mut i = 2;
+    let (body_end, end) = loop {
+        match rest.get(i) {
+            None => return (rest.len(), Output::Incomplete(rest)),
+            Some(&BEL_ASCII) if kind == b']' => break (i, i + 1),
+            Some(&ESC_ASCII) => match rest.get(i + 1) {
+                Some(b'\\') => break (i, i + 2),
+                None => return (rest.len(), Output::Incomplete(rest)),
+                // any other escape sequence cancels the string
+                Some(_) => return (i, Output::Unknown(&rest[..i])),
+            },
+            Some(_) => i += 1,
+        }
+    };
+    let body = &rest[2..body_end];
+    let output = match kind {
+        b']' => osc_seq(body).map_or(Output::Osc(body), Output::Seq),
+        b'P' => match body {
+            b"=1s" => Output::Seq(StdoutEscSeq::BeginSynchronizedUpdateLegacy),
+            b"=2s" => Output::Seq(StdoutEscSeq::EndSynchronizedUpdateLegacy),
+            _ => Output::Dcs(body),
+        },
+        b'_' => Output::Apc(body),
+        _ => Output::Unknown(&rest[..end]),
+    };
+    (end, output)
+}
+
+fn osc_seq(body: &[u8]) -> Option<StdoutEscSeq<'_>> {
+    let body = str::from_utf8(body).ok()?;
+    let reset = match body {
+        "110" => Some(TerminalColor::Foreground),
+        "111" => Some(TerminalColor::Background),
+        "112" => Some(TerminalColor::Cursor),
+        _ => None,
+    };
+    if let Some(color) = reset {
+        return Some(StdoutEscSeq::ResetColor(color));
+    }
+    let (command, rest) = body.split_once(';')?;
+    let seq = match command {
+        "0" => StdoutEscSeq::SetTitleAndIconName(rest),
+        "1" => StdoutEscSeq::SetIconName(rest),
+        "2" => StdoutEscSeq::SetTitle(rest),
+        "8" => match rest.split_once(';')? {
+            ("", "") => StdoutEscSeq::EndHyperlink,
+            ("", uri) => StdoutEscSeq::BeginHyperlink(None, uri),
+            (params, uri) => {
+                let id = params.strip_prefix("id=").filter(|id| !id.contains(':'))?;
+                StdoutEscSeq::BeginHyperlink(Some(id), uri)
+            }
+        },
+        // ConEmu uses `9;<number>;...` for its own commands (progress bars etc.)
+        "9" if !rest
+            .split_once(';')
+            .is_some_and(|(n, _)| n.bytes().all(|b| b.is_ascii_digit())) =>
+        {
+            StdoutEscSeq::Notify("", rest)
+        }
+        "52" => match rest.as_bytes() {
+            [selection, b';', b'?'] => {
+                StdoutEscSeq::RequestClipboard(ClipboardSelection::from_byte(*selection)?)
+            }
+            _ => return None,
+        },
+        "4" => {
+            let (index, spec) = rest.split_once(';')?;
+            color_seq(TerminalColor::Palette(index.parse().ok()?), spec)?
+        }
+        "10" => color_seq(TerminalColor::Foreground, rest)?,
+        "11" => color_seq(TerminalColor::Background, rest)?,
+        "12" => color_seq(TerminalColor::Cursor, rest)?,
+        "104" => StdoutEscSeq::ResetColor(TerminalColor::Palette(rest.parse().ok()?)),
+        "777" => {
+            let (title, body) = rest.strip_prefix("notify;")?.split_once(';')?;
+            StdoutEscSeq::NotifyOsc777(title, body)
+        }
+        _ => return None,
+    };
+    Some(seq)
+}
+
+/// Asking about or setting `color`, depending on whether `spec` is `?`
+fn color_seq(color: TerminalColor, spec: &str) -> Option<StdoutEscSeq<'static>> {
+    if spec == "?" {
+        return Some(StdoutEscSeq::RequestColor(color));
+    }
+    let (r, g, b) = parse_rgb_spec(spec.as_bytes())?;
+    Some(StdoutEscSeq::SetColor(color, Color::Rgb(r, g, b)))
+}
+
+impl Output<'_> {
+    /// What this does, in English, e.g. "move the cursor to row 1, column 1"
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Text(text) => format!("text {:?}", text),
+            Self::Control(byte) => describe_control(*byte).to_string(),
+            Self::Seq(seq) => describe_seq(seq),
+            Self::Sgr(changes) => {
+                let changes: Vec<_> = changes.iter().map(describe_sgr).collect();
+                format!("style: {}", changes.join(", "))
+            }
+            Self::Osc(body) => describe_osc(body),
+            Self::Dcs(body) => describe_dcs(body),
+            Self::Apc(body) => match body.strip_prefix(b"G") {
+                Some(command) => {
+                    let keys = command.split(|&b| b == b';').next().unwrap_or_default();
+                    format!("kitty graphics command ({})", String::from_utf8_lossy(keys))
+                }
+                None => "unknown APC string".to_string(),
+            },
+            Self::Unknown(bytes) => describe_unknown(bytes),
+            Self::Incomplete(_) => "cut off at the end of the output".to_string(),
+        }
+    }
+}
+
+fn describe_control(byte: u8) -> &'static str {
+    match byte {
+        0x00 => "NUL (ignored)",
+        0x08 => "backspace: move the cursor left one column",
+        0x09 => "tab: move the cursor to the next tab stop",
+        0x0a => "line feed: move the cursor down a line, scrolling at the bottom",
+        0x0b => "vertical tab: same as a line feed",
+        0x0c => "form feed: same as a line feed",
+        0x0d => "carriage return: move the cursor to the start of the line",
+        0x0e => "SO: switch to the G1 character set",
+        0x0f => "SI: switch back to the G0 character set",
+        0x18 | 0x1a => "cancel the current escape sequence",
+        0x7f => "DEL (ignored)",
+        _ => "control character (ignored by most terminals)",
+    }
+}
+
+fn plural(n: u32, thing: &str) -> String {
+    if n == 1 {
+        format!("1 {}", thing)
+    } else {
+        format!("{} {}s", n, thing)
+    }
+}
+
+fn describe_seq(seq: &StdoutEscSeq) -> String {
+    use StdoutEscSeq::*;
+
+    match *seq {
+        MoveUp(n) => format!("move the cursor up {}", plural(n, "line")),
+        MoveDown(n) => format!("move the cursor down {}", plural(n, "line")),
+        MoveRight(n) => format!("move the cursor right {}", plural(n, "column")),
+        MoveLeft(n) => format!("move the cursor left {}", plural(n, "column")),
+        MoveTo(row, col) => format!("move the cursor to row {}, column {}", row + 1, col + 1),
+        MoveToColumn(col) => format!("move the cursor to column {}", col + 1),
+        MoveToRow(row) => format!("move the cursor to row {}", row + 1),
+        MoveToNextLine(n) => format!("move the cursor down {}, to the start", plural(n, "line")),
+        MoveToPreviousLine(n) => format!("move the cursor up {}, to the start", plural(n, "line")),
+        HideCursor => "hide the cursor".to_string(),
+        ShowCursor => "show the cursor".to_string(),
+        SetCursorStyle(style) => format!("set the cursor style to {:?}", style),
+        EnterAlternateScreen => "save the cursor and switch to a clear alternate screen".into(),
+        LeaveAlternateScreen => "switch back to the main screen and restore the cursor".into(),
+        SwitchToAlternateScreen => "switch to the alternate screen".to_string(),
+        SwitchToMainScreen => "clear the alternate screen and switch back".to_string(),
+        SaveScreen => "switch to the alternate screen (old style)".to_string(),
+        RestoreScreen => "switch back to the main screen (old style)".to_string(),
+        SetScrollRegion(top, bottom) => format!(
+            "only scroll rows {} to {}, and move the cursor to the top left",
+            top + 1,
+            bottom + 1
+        ),
+        ResetScrollRegion => "scroll the whole screen, and move the cursor to the top left".into(),
+        ScrollUp(n) => format!("scroll up {}", plural(n, "line")),
+        ScrollDown(n) => format!("scroll down {}", plural(n, "line")),
+        InsertLines(n) => format!("insert {} at the cursor", plural(n, "blank line")),
+        DeleteLines(n) => format!("delete {} at the cursor", plural(n, "line")),
+        InsertChars(n) => format!("insert {} at the cursor", plural(n, "blank character")),
+        DeleteChars(n) => format!("delete {} at the cursor", plural(n, "character")),
+        EraseChars(n) => format!("blank out {} from the cursor", plural(n, "character")),
+        SetTabStop => "set a tab stop in the cursor's column (HTS)".to_string(),
+        ClearTabStop => "clear the tab stop in the cursor's column (TBC)".to_string(),
+        ClearAllTabStops => "clear all tab stops (TBC)".to_string(),
+        TabForward(n) => format!("move the cursor forward {} (CHT)", plural(n, "tab stop")),
+        TabBackward(n) => format!("move the cursor back {} (CBT)", plural(n, "tab stop")),
+        SaveCursorPosition => "save the cursor position and attributes".to_string(),
+        RestorCursorPosition => "restore the saved cursor position and attributes".to_string(),
+        ScoSaveCursorPosition => "save the cursor position (SCO)".to_string(),
+        ScoRestoreCursorPosition => "restore the saved cursor position (SCO)".to_string(),
+        SetForeground(color) => format!("style: foreground {}", describe_color(color)),
+        SetBackground(color) => format!("style: background {}", describe_color(color)),
+        SetUnderlineColor(color) => format!("style: underline color {}", describe_color(color)),
+        SetStyle(style) => format!("style: {:?}", style),
+        ResetStyle => "style: reset".to_string(),
+        EraseEntireScreen => "erase the whole screen".to_string(),
+        EraseToEndOfScreen => "erase from the cursor to the end of the screen".to_string(),
+        EraseToStartOfScreen => "erase from the start of the screen to the cursor".to_string(),
+        EraseScrollback => "erase the scrollback".to_string(),
+        EraseToEndOfLine => "erase from the cursor to the end of the line".to_string(),
+        EraseToStartOfLine => "erase from the start of the line to the cursor".to_string(),
+        EraseEntireLine => "erase the whole line".to_string(),
+        SetTitle(title) => format!("set the window title to {:?}", title),
+        SetIconName(name) => format!("set the icon name to {:?}", name),
+        SetTitleAndIconName(title) => format!("set the title and icon name to {:?}", title),
+        PushTitle(kind) => format!("save the title on the title stack ({:?})", kind),
+        PopTitle(kind) => format!("restore the title from the title stack ({:?})", kind),
+        Bell => "ring the bell".to_string(),
+        EnableUrgentBell => "make the bell set the window's urgency hint".to_string(),
+        DisableUrgentBell => "stop the bell setting the window's urgency hint".to_string(),
+        Notify(title, body) => format!("notification {:?} {:?} (OSC 9)", title, body),
+        NotifyOsc777(title, body) => format!("notification {:?} {:?} (OSC 777)", title, body),
+        NotifyKitty(id, title, body, _) => {
+            format!("notification {} {:?} {:?} (OSC 99)", id, title, body)
+        }
+        Kitty(_) => "kitty graphics command".to_string(),
+        BeginHyperlink(Some(id), uri) => format!("start a hyperlink to {} (id {})", uri, id),
+        BeginHyperlink(None, uri) => format!("start a hyperlink to {}", uri),
+        EndHyperlink => "end the hyperlink".to_string(),
+        SetClipboard(selection, text) => format!("copy {:?} to the {:?}", text, selection),
+        BeginSynchronizedUpdate => "hold off drawing until the update ends".to_string(),
+        EndSynchronizedUpdate => "draw everything since the update began".to_string(),
+        BeginSynchronizedUpdateLegacy => "hold off drawing until the update ends (DCS)".into(),
+        EndSynchronizedUpdateLegacy => "draw everything since the update began (DCS)".into(),
+        RequestCursorPosition => "ask where the cursor is".to_string(),
+        RequestPrivateMode(mode) => format!("ask about mode {}", describe_mode(mode)),
+        RequestClipboard(selection) => format!("ask for the contents of the {:?}", selection),
+        RequestColor(color) => format!("ask what the {} is", describe_terminal_color(color)),
+        SetColor(color, value) => format!(
+            "set the {} to {}",
+            describe_terminal_color(color),
+            describe_color(value)
+        ),
+        ResetColor(color) => format!("reset the {}", describe_terminal_color(color)),
+    }
+}
+
+fn describe_terminal_color(color: TerminalColor) -> String {
+    match color {
+        TerminalColor::Foreground => "default foreground color".to_string(),
+        TerminalColor::Background => "default background color".to_string(),
+        TerminalColor::Cursor => "cursor color".to_string(),
+        TerminalColor::Palette(i) => format!("palette color {}", i),
+    }
+}
+
+fn describe_color(color: Color) -> String {
+    match color {
+        Color::Indexed(i) => format!("color {}", i),
+        Color::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        named => format!("{:?}", named).to_lowercase(),
+    }
+}
+
+fn describe_attributes(attributes: Attributes) -> String {
+    let names = [
+        (Attributes::BOLD, "bold"),
+        (Attributes::DIM, "dim"),
+        (Attributes::ITALIC, "italic"),
+        (Attributes::BLINK, "blink"),
+        (Attributes::REVERSE, "reverse"),
+        (Attributes::HIDDEN, "hidden"),
+        (Attributes::STRIKETHROUGH, "strikethrough"),
+        (Attributes::OVERLINE, "overline"),
+    ];
+    let names: Vec<_> = names
+        .iter()
+        .filter(|(attribute, _)| attributes.contains(*attribute))
+        .map(|(_, name)| *name)
+        .collect();
+    names.join(" and ")
+}
+
+fn describe_sgr(change: &Sgr) -> String {
+    let or_default = |color: Option<Color>| color.map_or("default".to_string(), describe_color);
+    match *change {
+        Sgr::Reset => "reset".to_string(),
+        Sgr::On(attributes) => describe_attributes(attributes),
+        Sgr::Off(attributes) => format!("not {}", describe_attributes(attributes)),
+        Sgr::Underline(Some(style)) => format!("{:?} underline", style).to_lowercase(),
+        Sgr::Underline(None) => "no underline".to_string(),
+        Sgr::Foreground(color) => format!("foreground {}", or_default(color)),
+        Sgr::Background(color) => format!("background {}", or_default(color)),
+        Sgr::UnderlineColor(color) => format!("underline color {}", or_default(color)),
+        Sgr::Unknown(code) => format!("unknown ({})", code),
+    }
+}
+
+fn describe_mode(mode: u32) -> String {
+    let name = match mode {
+        1 => "application cursor keys",
+        3 => "132 columns",
+        5 => "reverse video",
+        6 => "origin mode",
+        7 => "auto-wrap",
+        9 => "X10 mouse reporting",
+        12 => "cursor blinking",
+        25 => "cursor visible",
+        47 | 1047 => "alternate screen",
+        1000 => "mouse click reporting",
+        1002 => "mouse drag reporting",
+        1003 => "mouse motion reporting",
+        1004 => "focus reporting",
+        1005 => "UTF-8 mouse coordinates",
+        1006 => "SGR mouse coordinates",
+        1015 => "urxvt mouse coordinates",
+        1042 => "urgent bell",
+        1049 => "alternate screen and saved cursor",
+        2004 => "bracketed paste",
+        2026 => "synchronized output",
+        2027 => "grapheme clustering",
+        _ => return format!("?{}", mode),
+    };
+    format!("?{} ({})", mode, name)
+}
+
+fn describe_unknown(bytes: &[u8]) -> String {
+    let Some(rest) = bytes.strip_prefix(&[ESC_ASCII]) else {
+        return "invalid UTF-8".to_string();
+    };
+    match rest {
+        [] => "lone ESC".to_string(),
+        [b'c'] => "reset the terminal completely (RIS)".to_string(),
+        [b'D'] => "move the cursor down a line, scrolling at the bottom (IND)".to_string(),
+        [b'E'] => "move to the start of the next line (NEL)".to_string(),
+        [b'M'] => "move the cursor up a line, scrolling at the top (RI)".to_string(),
+        [b'='] => "application keypad mode".to_string(),
+        [b'>'] => "normal keypad mode".to_string(),
+        [b'(' | b')' | b'*' | b'+', charset] => {
+            format!("select character set {}", *charset as char)
+        }
+        [b'[', csi @ ..] => {
+            let Some((&last, params)) = csi.split_last() else {
+                return "unknown CSI sequence".to_string();
+            };
+            match (params.strip_prefix(b"?"), last) {
+                (Some(modes), b'h' | b'l') => {
+                    let modes: Vec<_> = modes
+                        .split(|&b| b == b';')
+                        .map(|mode| match number(mode) {
+                            Some(Some(mode)) => describe_mode(mode),
+                            _ => String::from_utf8_lossy(mode).into_owned(),
+                        })
+                        .collect();
+                    let action = if last == b'h' { "turn on" } else { "turn off" };
+                    format!("{} {}", action, modes.join(", "))
+                }
+                (None, b'm') => "private or malformed SGR".to_string(),
+                (None, b'c') => "ask what kind of terminal this is (DA)".to_string(),
+                (None, b'h' | b'l') if params == b"4" => "insert mode".to_string(),
+                (None, b'u') if params.starts_with(b">") || params.starts_with(b"<") => {
+                    "kitty keyboard protocol".to_string()
+                }
+                _ => "unknown CSI sequence".to_string(),
+            }
+        }
+        [b']', ..] | [b'P', ..] | [b'_', ..] => "string cancelled before the end".to_string(),
+        [b'^', ..] => "privacy message (ignored)".to_string(),
+        [b'X', ..] => "start of string (ignored)".to_string(),
+        _ => "unknown escape sequence".to_string(),
+    }
+}
+
+fn describe_osc(body: &[u8]) -> String {
+    let (command, rest) = match body.iter().position(|&b| b == b';') {
+        Some(i) => (&body[..i], &body[i + 1..]),
+        None => (body, &[][..]),
+    };
+    let command = String::from_utf8_lossy(command);
+    let what = match &*command {
+        "4" => "set or ask for palette colors".to_string(),
+        "7" => format!("current directory {}", String::from_utf8_lossy(rest)),
+        "10" => "set or ask for the foreground color".to_string(),
+        "11" => "set or ask for the background color".to_string(),
+        "12" => "set or ask for the cursor color".to_string(),
+        "52" => {
+            let data = rest.splitn(2, |&b| b == b';').nth(1).unwrap_or_default();
+            match base64::decode(data) {
+                Some(text) => format!("copy {:?} to the clipboard", String::from_utf8_lossy(&text)),
+                None => "copy to the clipboard".to_string(),
+            }
+        }
+        "99" => "kitty notification".to_string(),
+        "104" => "reset palette colors".to_string(),
+        "110" => "reset the foreground color".to_string(),
+        "111" => "reset the background color".to_string(),
+        "112" => "reset the cursor color".to_string(),
+        "133" => "shell integration mark".to_string(),
+        "633" => "VS Code shell integration".to_string(),
+        "1337" => "iTerm2 command".to_string(),
+        _ => "unknown OSC string".to_string(),
+    };
+    format!("{} (OSC {})", what, command)
+}
+
+fn describe_dcs(body: &[u8]) -> String {
+    let params = body
+        .iter()
+        .take_while(|b| b.is_ascii_digit() || **b == b';')
+        .count();
+    match &body[params..] {
+        [b'q', ..] => "sixel image".to_string(),
+        [b'$', b'q', ..] => "ask for a setting (DECRQSS)".to_string(),
+        [b'+', b'q', ..] => "ask for terminfo capabilities (XTGETTCAP)".to_string(),
+        rest if rest.starts_with(b"tmux;") => "passed through tmux".to_string(),
+        _ => "unknown DCS string".to_string(),
+    }
+}
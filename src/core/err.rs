@@ -1,3 +1,5 @@
+use std::io;
+
 /// Indicates an issue when trying to create a Termset instance.
 /// You can safely ignore the error variant for most applications on a result with
 /// this return type, as it is effectively guaranteed not to happen.
@@ -11,3 +13,22 @@ pub enum TermsetCreationError {
     NotATerminal,
 }
 
+/// Indicates why `LineEditor::read_line` didn't give us a line
+#[derive(Debug)]
+pub enum ReadLineError {
+    /// The user pressed Ctrl-C
+    Interrupted,
+    /// The user pressed Ctrl-D on an empty line (or stdin was closed)
+    Eof,
+    /// Reading from stdin or writing to stdout failed
+    Io(io::Error),
+}
+
+impl From<io::Error> for ReadLineError {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::UnexpectedEof => Self::Eof,
+            _ => Self::Io(e),
+        }
+    }
+}
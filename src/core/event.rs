@@ -0,0 +1,393 @@
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::UnixStream;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use libc::{c_int, SIGCONT, SIGTSTP, SIGWINCH};
+use signal_hook::SigId;
+
+use super::{window_size, Token, TokenReader, WinSize};
+
+/// The shortest interval `EventLoop::add_interval` allows, so an interval can't fire over and
+/// over without ever letting the loop `poll`
+const MIN_INTERVAL: Duration = Duration::from_millis(1);
+
+/// How long a cut off escape sequence gets for the rest of it to turn up before we give up
+/// and call it the Escape key (or Alt + something). Terminals send a whole sequence at once,
+/// so this only has to cover a slow link.
+const ESC_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Identifies a timer registered with `EventLoop::add_timer` or `EventLoop::add_interval`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimerId(u64);
+
+/// Everything the `EventLoop` can wake up for
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub enum Event<'a> {
+    /// A token was read from stdin
+    Token(Token<'a>),
+    /// We got `SIGWINCH`, this carries the new size of the terminal
+    Resize(WinSize),
+    /// We got `SIGTSTP` (usually Ctrl-Z). Since we catch it, the process is *not* stopped --
+    /// restore the terminal and call `suspend_process` if you want the default behaviour
+    Suspend,
+    /// We got `SIGCONT`, e.g. after being resumed with `fg`. You probably want to re-apply your
+    /// `Termset` and redraw everything
+    Continue,
+    /// A timer fired
+    Timer(TimerId),
+    /// Some `Waker` was woken (any number of wakes since the last `Wake` are collapsed into one)
+    Wake,
+}
+
+/// Stop the process with `SIGSTOP`, this is what `SIGTSTP` would normally do if we weren't
+/// catching it. Returns once the process has been continued.
+pub fn suspend_process() {
+    unsafe {
+        libc::raise(libc::SIGSTOP);
+    }
+}
+
+/// A handle that can wake up an `EventLoop` from any thread. Cheap to clone.
+#[derive(Clone)]
+pub struct Waker {
+    stream: Arc<UnixStream>,
+}
+
+impl Waker {
+    /// Make the `EventLoop` return `Event::Wake` as soon as possible
+    pub fn wake(&self) -> io::Result<()> {
+        match (&*self.stream).write(&[0]) {
+            Ok(_) => Ok(()),
+            // the pipe is full, so the loop is going to wake up anyway
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+struct Timer {
+    id: TimerId,
+    deadline: Instant,
+    interval: Option<Duration>,
+}
+
+/// A self-pipe registered for a single signal
+struct SignalPipe {
+    signal: c_int,
+    id: SigId,
+    read: UnixStream,
+}
+
+impl SignalPipe {
+    fn new(signal: c_int) -> io::Result<Self> {
+        let (read, write) = UnixStream::pair()?;
+        read.set_nonblocking(true)?;
+        write.set_nonblocking(true)?;
+        let id = signal_hook::low_level::pipe::register(signal, write)?;
+        Ok(Self { signal, id, read })
+    }
+}
+
+impl Drop for SignalPipe {
+    fn drop(&mut self) {
+        signal_hook::low_level::unregister(self.id);
+    }
+}
+
+/// Read everything out of a non-blocking stream, returns `true` if there was anything in it
+fn drain(mut stream: &UnixStream) -> bool {
+    let mut buf = [0; 64];
+    let mut drained = false;
+    loop {
+        match stream.read(&mut buf) {
+            Ok(0) => return drained,
+            Ok(_) => drained = true,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(_) => return drained,
+        }
+    }
+}
+
+/// A small reactor built on `poll(2)`. It multiplexes tokens from stdin (through its own
+/// `TokenReader`), `SIGWINCH`/`SIGTSTP`/`SIGCONT`, timers and `Waker`s into a single stream of
+/// `Event`s, so you don't have to block on stdin to find out the terminal was resized.
+///
+/// Only create one of these at a time -- they all read from stdin.
+///
+/// ```rs
+/// let mut event_loop = tsc::EventLoop::new().unwrap();
+/// let tick = event_loop.add_interval(Duration::from_millis(500));
+/// loop {
+///     match event_loop.next().unwrap() {
+///         tsc::Event::Token(tsc::Token::Char("q")) => break,
+///         tsc::Event::Resize(size) => redraw(size),
+///         tsc::Event::Timer(id) if id == tick => blink_cursor(),
+///         _ => (),
+///     }
+/// }
+/// ```
+pub struct EventLoop {
+    reader: TokenReader,
+    signals: Vec<SignalPipe>,
+    waker_read: UnixStream,
+    waker: Waker,
+    timers: Vec<Timer>,
+    next_timer_id: u64,
+    /// the timer for `ESC_TIMEOUT`, while we're waiting on a cut off escape sequence
+    esc_timer: Option<TimerId>,
+    pending: VecDeque<Event<'static>>,
+}
+
+impl EventLoop {
+    pub fn new() -> io::Result<Self> {
+        let signals = [SIGWINCH, SIGTSTP, SIGCONT]
+            .into_iter()
+            .map(SignalPipe::new)
+            .collect::<io::Result<Vec<_>>>()?;
+        let (waker_read, waker_write) = UnixStream::pair()?;
+        waker_read.set_nonblocking(true)?;
+        waker_write.set_nonblocking(true)?;
+
+        Ok(Self {
+            reader: TokenReader::new(),
+            signals,
+            waker_read,
+            waker: Waker {
+                stream: Arc::new(waker_write),
+            },
+            timers: Vec::new(),
+            next_timer_id: 0,
+            esc_timer: None,
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// Get a `Waker` for this loop, which you can send to other threads
+    pub fn waker(&self) -> Waker {
+        self.waker.clone()
+    }
+
+    fn insert_timer(&mut self, after: Duration, interval: Option<Duration>) -> TimerId {
+        let id = TimerId(self.next_timer_id);
+        self.next_timer_id += 1;
+        self.timers.push(Timer {
+            id,
+            deadline: Instant::now() + after,
+            interval,
+        });
+        id
+    }
+
+    /// Fire `Event::Timer` once, after `after` has elapsed
+    pub fn add_timer(&mut self, after: Duration) -> TimerId {
+        self.insert_timer(after, None)
+    }
+
+    /// Fire `Event::Timer` every `every` (at least 1ms), until it is cancelled
+    pub fn add_interval(&mut self, every: Duration) -> TimerId {
+        let every = every.max(MIN_INTERVAL);
+        self.insert_timer(every, Some(every))
+    }
+
+    /// Stop a timer from firing, returns `false` if it had already fired (or never existed)
+    pub fn cancel_timer(&mut self, id: TimerId) -> bool {
+        let len = self.timers.len();
+        self.timers.retain(|timer| timer.id != id);
+        self.timers.len() != len
+    }
+
+    /// Pop the first timer that has expired, rescheduling it if it's an interval
+    fn expired_timer(&mut self, now: Instant) -> Option<TimerId> {
+        let index = self
+            .timers
+            .iter()
+            .enumerate()
+            .filter(|(_, timer)| timer.deadline <= now)
+            .min_by_key(|(_, timer)| timer.deadline)
+            .map(|(index, _)| index)?;
+        let timer = &mut self.timers[index];
+        let id = timer.id;
+        match timer.interval {
+            Some(interval) => {
+                timer.deadline += interval;
+                // if we've fallen behind, skip the ticks we missed instead of firing them all
+                // back to back
+                if timer.deadline <= now {
+                    timer.deadline = now + interval;
+                }
+            }
+            None => {
+                self.timers.swap_remove(index);
+            }
+        }
+        Some(id)
+    }
+
+    /// What a timer going off means: it's one of the user's, or the one waiting on a cut off
+    /// escape sequence, `None` if that sequence has since turned into a token by itself
+    fn timer_event(&mut self, id: TimerId) -> Option<Event<'static>> {
+        if self.esc_timer != Some(id) {
+            return Some(Event::Timer(id));
+        }
+        self.esc_timer = None;
+        self.reader.flush_esc().map(Event::Token)
+    }
+
+    /// Start the clock on a cut off escape sequence, if there is one
+    fn wait_for_esc(&mut self) {
+        if self.esc_timer.is_none() && self.reader.has_cut_off_esc() {
+            self.esc_timer = Some(self.add_timer(ESC_TIMEOUT));
+        }
+    }
+
+    /// Wait on all our file descriptors for at most `timeout` (forever if `None`), queueing
+    /// up any events and filling the reader's buffer
+    fn poll(&mut self, timeout: Option<Duration>) -> io::Result<()> {
+        let mut fds = vec![libc::pollfd {
+            // with no room to read into, there's no point waking up for stdin (the buffer is
+            // only full when it has tokens to take out, though)
+            fd: if self.reader.is_full() {
+                -1
+            } else {
+                libc::STDIN_FILENO
+            },
+            events: libc::POLLIN,
+            revents: 0,
+        }];
+        fds.extend(
+            self.signals
+                .iter()
+                .map(|pipe| pipe.read.as_raw_fd())
+                .chain([self.waker_read.as_raw_fd()])
+                .map(|fd| libc::pollfd {
+                    fd,
+                    events: libc::POLLIN,
+                    revents: 0,
+                }),
+        );
+
+        let timeout = match timeout {
+            // round up, so we don't spin on a deadline that's less than 1ms away
+            Some(timeout) => timeout
+                .as_nanos()
+                .div_ceil(1_000_000)
+                .min(c_int::MAX as u128) as c_int,
+            None => -1,
+        };
+
+        let ready = unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, timeout) };
+        if ready == -1 {
+            let e = io::Error::last_os_error();
+            return match e.kind() {
+                // a signal arrived, the self-pipe will have it
+                io::ErrorKind::Interrupted => Ok(()),
+                _ => Err(e),
+            };
+        }
+
+        // one of them was closed behind our back, and `poll` would keep returning at once
+        if fds.iter().any(|fd| fd.revents & libc::POLLNVAL != 0) {
+            return Err(io::Error::from_raw_os_error(libc::EBADF));
+        }
+
+        for (pipe, fd) in self.signals.iter().zip(&fds[1..]) {
+            if fd.revents & libc::POLLIN != 0 && drain(&pipe.read) {
+                self.pending.push_back(match pipe.signal {
+                    SIGWINCH => Event::Resize(window_size()?),
+                    SIGTSTP => Event::Suspend,
+                    _ => Event::Continue,
+                });
+            }
+        }
+
+        if fds[fds.len() - 1].revents & libc::POLLIN != 0 && drain(&self.waker_read) {
+            self.pending.push_back(Event::Wake);
+        }
+
+        if fds[0].revents & (libc::POLLIN | libc::POLLHUP | libc::POLLERR) != 0 {
+            match self.reader.read_available() {
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(_) => {
+                    // the clock starts again from the latest bytes
+                    if let Some(id) = self.esc_timer.take() {
+                        self.cancel_timer(id);
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => (),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Wait for the next event for at most `timeout`, returning `None` if nothing happened in
+    /// that time. A timeout of zero just checks without blocking.
+    pub fn next_timeout(&mut self, timeout: Duration) -> io::Result<Option<Event<'_>>> {
+        let deadline = Instant::now() + timeout;
+        let mut polled = false;
+        loop {
+            let now = Instant::now();
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(Some(event));
+            }
+            if let Some(id) = self.expired_timer(now) {
+                match self.timer_event(id) {
+                    Some(event) => return Ok(Some(event)),
+                    None => continue,
+                }
+            }
+            if self.reader.has_token() {
+                return Ok(self.reader.try_next().map(Event::Token));
+            }
+            self.wait_for_esc();
+            // always poll at least once, so a zero timeout still picks up new input
+            if polled && now >= deadline {
+                return Ok(None);
+            }
+
+            let wait = self
+                .timers
+                .iter()
+                .map(|timer| timer.deadline)
+                .chain([deadline])
+                .min()
+                .unwrap()
+                .saturating_duration_since(now);
+            self.poll(Some(wait))?;
+            polled = true;
+        }
+    }
+
+    /// Block until the next event
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> io::Result<Event<'_>> {
+        loop {
+            let now = Instant::now();
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(event);
+            }
+            if let Some(id) = self.expired_timer(now) {
+                match self.timer_event(id) {
+                    Some(event) => return Ok(event),
+                    None => continue,
+                }
+            }
+            if self.reader.has_token() {
+                return Ok(Event::Token(self.reader.try_next().unwrap()));
+            }
+            self.wait_for_esc();
+
+            let wait = self
+                .timers
+                .iter()
+                .map(|timer| timer.deadline.saturating_duration_since(now))
+                .min();
+            self.poll(wait)?;
+        }
+    }
+}
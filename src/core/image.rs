@@ -0,0 +1,120 @@
+/// An image in memory, as 8-bit RGBA pixels row by row from the top left. This is what the
+/// graphics protocols (`SixelEncoder`, ...) draw.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    /// `None` if `pixels` isn't `width * height` RGBA pixels
+    pub fn from_rgba(width: u32, height: u32, pixels: Vec<u8>) -> Option<Self> {
+        (pixels.len() == width as usize * height as usize * 4).then_some(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// `None` if `pixels` isn't `width * height` RGB pixels. Everything comes out opaque.
+    pub fn from_rgb(width: u32, height: u32, pixels: &[u8]) -> Option<Self> {
+        if pixels.len() != width as usize * height as usize * 3 {
+            return None;
+        }
+        let pixels = pixels
+            .chunks_exact(3)
+            .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 0xff])
+            .collect();
+        Some(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The RGBA bytes
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// The RGBA pixel at `(x, y)`, which has to be inside the image
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+
+    /// Scale to `width` x `height`. Each new pixel is the average of the ones it covers, so
+    /// shrinking things like plots doesn't lose thin lines, and growing is nearest neighbour.
+    pub fn resize(&self, width: u32, height: u32) -> Image {
+        let width = width.max(1);
+        let height = height.max(1);
+        if (width, height) == (self.width, self.height) || self.pixels.is_empty() {
+            return self.clone();
+        }
+        // the source pixels `[start, end)` that end up in new pixel `i` along one axis
+        let span = |i: u32, new: u32, old: u32| {
+            let start = (i as u64 * old as u64 / new as u64) as u32;
+            let end = ((i as u64 + 1) * old as u64 / new as u64) as u32;
+            start..end.max(start + 1)
+        };
+
+        let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+        for y in 0..height {
+            let ys = span(y, height, self.height);
+            for x in 0..width {
+                let xs = span(x, width, self.width);
+                // colors are weighted by alpha, so transparent pixels don't darken the edges
+                let mut sum = [0u64; 4];
+                for sy in ys.clone() {
+                    for sx in xs.clone() {
+                        let [r, g, b, a] = self.pixel(sx, sy);
+                        sum[0] += r as u64 * a as u64;
+                        sum[1] += g as u64 * a as u64;
+                        sum[2] += b as u64 * a as u64;
+                        sum[3] += a as u64;
+                    }
+                }
+                let count = ys.len() as u64 * xs.len() as u64;
+                let channel = |c: u64| (c + sum[3] / 2).checked_div(sum[3]).unwrap_or(0) as u8;
+                pixels.extend([
+                    channel(sum[0]),
+                    channel(sum[1]),
+                    channel(sum[2]),
+                    ((sum[3] + count / 2) / count) as u8,
+                ]);
+            }
+        }
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Scale to the biggest size that fits in `rows` x `cols` character cells, keeping the
+    /// aspect ratio. `cell_size` is `(width, height)` in pixels, see `WinSize::cell_size`.
+    pub fn fit_cells(&self, rows: u32, cols: u32, cell_size: (u32, u32)) -> Image {
+        let max_width = cols.saturating_mul(cell_size.0) as f64;
+        let max_height = rows.saturating_mul(cell_size.1) as f64;
+        let scale =
+            (max_width / self.width.max(1) as f64).min(max_height / self.height.max(1) as f64);
+        self.resize(
+            (self.width as f64 * scale).round() as u32,
+            (self.height as f64 * scale).round() as u32,
+        )
+    }
+}
@@ -0,0 +1,240 @@
+use std::io;
+
+use super::{base64, ST};
+
+/// What's in the data sent with `KittyCommand::Transmit`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum KittyFormat {
+    /// 3 bytes per pixel (`f=24`)
+    Rgb,
+    /// 4 bytes per pixel (`f=32`)
+    Rgba,
+    /// A PNG file, which knows its own size (`f=100`)
+    Png,
+}
+
+impl KittyFormat {
+    /// The `f=` parameter for this
+    pub fn as_param(&self) -> u32 {
+        match *self {
+            Self::Rgb => 24,
+            Self::Rgba => 32,
+            Self::Png => 100,
+        }
+    }
+}
+
+/// Where and how to show an image that's been sent with `KittyCommand::Transmit`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KittyPlacement {
+    pub image_id: u32,
+    /// Placing an image again with the same (non-zero) placement id moves it instead of
+    /// showing it twice. `0` lets the terminal pick a new one every time.
+    pub placement_id: u32,
+    /// Where it goes relative to text and other images: negative is under the text (and under
+    /// cell backgrounds too below `-1_073_741_824`)
+    pub z_index: i32,
+    /// Scale the image to this many rows, `0` for its own size
+    pub rows: u32,
+    /// Scale the image to this many columns, `0` for its own size
+    pub cols: u32,
+    /// Leave the cursor where it is, instead of after the image
+    pub keep_cursor: bool,
+    /// Don't draw anything, just make the image available to Unicode placeholders (`U=1`),
+    /// see `TsStdout::draw_kitty_placeholders`
+    pub virtual_placement: bool,
+}
+
+impl KittyPlacement {
+    /// Image `image_id` at its own size, over the text
+    pub fn new(image_id: u32) -> Self {
+        Self {
+            image_id,
+            placement_id: 0,
+            z_index: 0,
+            rows: 0,
+            cols: 0,
+            keep_cursor: false,
+            virtual_placement: false,
+        }
+    }
+}
+
+/// A command for kitty's graphics protocol (`ESC _ G ... ESC \`), which kitty, WezTerm,
+/// Ghostty and Konsole support. Terminals that don't just ignore these.
+///
+/// The terminal answers commands with an image id with `Token::GraphicsResponse`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum KittyCommand<'a> {
+    /// Send image data for the terminal to keep as `id`, without showing it yet (`a=t`).
+    /// `width` and `height` are ignored for PNGs. The data is sent as base64 in as many
+    /// chunks as it takes.
+    Transmit {
+        id: u32,
+        format: KittyFormat,
+        width: u32,
+        height: u32,
+        data: &'a [u8],
+    },
+    /// Show an image at the cursor (`a=p`)
+    Place(KittyPlacement),
+    /// Take placement `placement_id` of image `id` off the screen, or every placement if
+    /// `None`, and forget the image data too if `free` (`a=d`)
+    Delete {
+        id: u32,
+        placement_id: Option<u32>,
+        free: bool,
+    },
+    /// Take every image off the screen, and forget their data too if `free`
+    DeleteAll { free: bool },
+}
+
+/// The most base64 we send in one chunk
+const CHUNK_SIZE: usize = 4096;
+
+/// Inside tmux, everything has to be wrapped up for tmux to pass it through to the real
+/// terminal (with `ESC` doubled), and `allow-passthrough` has to be on
+const TMUX_PREFIX: &[u8] = b"\x1bPtmux;\x1b";
+
+impl KittyCommand<'_> {
+    /// Write the command to `w`. With `tmux_passthrough`, each chunk is wrapped up so tmux
+    /// sends it on to the terminal it's running in.
+    pub fn write_to<W: io::Write + ?Sized>(
+        &self,
+        w: &mut W,
+        tmux_passthrough: bool,
+    ) -> io::Result<()> {
+        let start = |w: &mut W| -> io::Result<()> {
+            if tmux_passthrough {
+                w.write_all(TMUX_PREFIX)?;
+            }
+            w.write_all(b"\x1b_G")
+        };
+        let end = |w: &mut W| -> io::Result<()> {
+            if tmux_passthrough {
+                w.write_all(b"\x1b")?;
+                w.write_all(&ST)?;
+            }
+            w.write_all(&ST)
+        };
+
+        start(w)?;
+        match *self {
+            Self::Transmit {
+                id,
+                format,
+                width,
+                height,
+                data,
+            } => {
+                write!(w, "a=t,i={},f={},", id, format.as_param())?;
+                if format != KittyFormat::Png {
+                    write!(w, "s={},v={},", width, height)?;
+                }
+                let mut chunks = data.chunks(CHUNK_SIZE / 4 * 3).peekable();
+                loop {
+                    let chunk = chunks.next().unwrap_or_default();
+                    let more = chunks.peek().is_some();
+                    write!(w, "m={};", more as u32)?;
+                    base64::encode_to(chunk, w)?;
+                    end(w)?;
+                    if !more {
+                        return Ok(());
+                    }
+                    // the chunks after the first only say whether there's more
+                    start(w)?;
+                }
+            }
+            Self::Place(placement) => {
+                write!(w, "a=p,i={}", placement.image_id)?;
+                if placement.placement_id != 0 {
+                    write!(w, ",p={}", placement.placement_id)?;
+                }
+                if placement.z_index != 0 {
+                    write!(w, ",z={}", placement.z_index)?;
+                }
+                if placement.rows != 0 {
+                    write!(w, ",r={}", placement.rows)?;
+                }
+                if placement.cols != 0 {
+                    write!(w, ",c={}", placement.cols)?;
+                }
+                if placement.keep_cursor {
+                    w.write_all(b",C=1")?;
+                }
+                if placement.virtual_placement {
+                    w.write_all(b",U=1")?;
+                }
+            }
+            Self::Delete {
+                id,
+                placement_id,
+                free,
+            } => {
+                // capital letters free the data too
+                write!(w, "a=d,d={},i={}", if free { 'I' } else { 'i' }, id)?;
+                if let Some(placement_id) = placement_id {
+                    write!(w, ",p={}", placement_id)?;
+                }
+            }
+            Self::DeleteAll { free } => {
+                write!(w, "a=d,d={}", if free { 'A' } else { 'a' })?;
+            }
+        }
+        end(w)
+    }
+}
+
+/// The character Unicode placeholders are made of
+pub const KITTY_PLACEHOLDER: char = '\u{10EEEE}';
+
+/// The combining characters that say which row and column of the image a placeholder is (and
+/// the top byte of the image id): every combining class 230 mark from Unicode 6.0 that isn't
+/// part of a canonical decomposition, in code point order. This has to match kitty's
+/// `rowcolumn-diacritics.txt` exactly.
+#[rustfmt::skip]
+pub(crate) const ROW_COLUMN_DIACRITICS: [char; 297] = [
+    '\u{0305}', '\u{030D}', '\u{030E}', '\u{0310}', '\u{0312}', '\u{033D}', '\u{033E}',
+    '\u{033F}', '\u{0346}', '\u{034A}', '\u{034B}', '\u{034C}', '\u{0350}', '\u{0351}',
+    '\u{0352}', '\u{0357}', '\u{035B}', '\u{0363}', '\u{0364}', '\u{0365}', '\u{0366}',
+    '\u{0367}', '\u{0368}', '\u{0369}', '\u{036A}', '\u{036B}', '\u{036C}', '\u{036D}',
+    '\u{036E}', '\u{036F}', '\u{0483}', '\u{0484}', '\u{0485}', '\u{0486}', '\u{0487}',
+    '\u{0592}', '\u{0593}', '\u{0594}', '\u{0595}', '\u{0597}', '\u{0598}', '\u{0599}',
+    '\u{059C}', '\u{059D}', '\u{059E}', '\u{059F}', '\u{05A0}', '\u{05A1}', '\u{05A8}',
+    '\u{05A9}', '\u{05AB}', '\u{05AC}', '\u{05AF}', '\u{05C4}', '\u{0610}', '\u{0611}',
+    '\u{0612}', '\u{0613}', '\u{0614}', '\u{0615}', '\u{0616}', '\u{0617}', '\u{0657}',
+    '\u{0658}', '\u{0659}', '\u{065A}', '\u{065B}', '\u{065D}', '\u{065E}', '\u{06D6}',
+    '\u{06D7}', '\u{06D8}', '\u{06D9}', '\u{06DA}', '\u{06DB}', '\u{06DC}', '\u{06DF}',
+    '\u{06E0}', '\u{06E1}', '\u{06E2}', '\u{06E4}', '\u{06E7}', '\u{06E8}', '\u{06EB}',
+    '\u{06EC}', '\u{0730}', '\u{0732}', '\u{0733}', '\u{0735}', '\u{0736}', '\u{073A}',
+    '\u{073D}', '\u{073F}', '\u{0740}', '\u{0741}', '\u{0743}', '\u{0745}', '\u{0747}',
+    '\u{0749}', '\u{074A}', '\u{07EB}', '\u{07EC}', '\u{07ED}', '\u{07EE}', '\u{07EF}',
+    '\u{07F0}', '\u{07F1}', '\u{07F3}', '\u{0816}', '\u{0817}', '\u{0818}', '\u{0819}',
+    '\u{081B}', '\u{081C}', '\u{081D}', '\u{081E}', '\u{081F}', '\u{0820}', '\u{0821}',
+    '\u{0822}', '\u{0823}', '\u{0825}', '\u{0826}', '\u{0827}', '\u{0829}', '\u{082A}',
+    '\u{082B}', '\u{082C}', '\u{082D}', '\u{0951}', '\u{0953}', '\u{0954}', '\u{0F82}',
+    '\u{0F83}', '\u{0F86}', '\u{0F87}', '\u{135D}', '\u{135E}', '\u{135F}', '\u{17DD}',
+    '\u{193A}', '\u{1A17}', '\u{1A75}', '\u{1A76}', '\u{1A77}', '\u{1A78}', '\u{1A79}',
+    '\u{1A7A}', '\u{1A7B}', '\u{1A7C}', '\u{1B6B}', '\u{1B6D}', '\u{1B6E}', '\u{1B6F}',
+    '\u{1B70}', '\u{1B71}', '\u{1B72}', '\u{1B73}', '\u{1CD0}', '\u{1CD1}', '\u{1CD2}',
+    '\u{1CDA}', '\u{1CDB}', '\u{1CE0}', '\u{1DC0}', '\u{1DC1}', '\u{1DC3}', '\u{1DC4}',
+    '\u{1DC5}', '\u{1DC6}', '\u{1DC7}', '\u{1DC8}', '\u{1DC9}', '\u{1DCB}', '\u{1DCC}',
+    '\u{1DD1}', '\u{1DD2}', '\u{1DD3}', '\u{1DD4}', '\u{1DD5}', '\u{1DD6}', '\u{1DD7}',
+    '\u{1DD8}', '\u{1DD9}', '\u{1DDA}', '\u{1DDB}', '\u{1DDC}', '\u{1DDD}', '\u{1DDE}',
+    '\u{1DDF}', '\u{1DE0}', '\u{1DE1}', '\u{1DE2}', '\u{1DE3}', '\u{1DE4}', '\u{1DE5}',
+    '\u{1DE6}', '\u{1DFE}', '\u{20D0}', '\u{20D1}', '\u{20D4}', '\u{20D5}', '\u{20D6}',
+    '\u{20D7}', '\u{20DB}', '\u{20DC}', '\u{20E1}', '\u{20E7}', '\u{20E9}', '\u{20F0}',
+    '\u{2CEF}', '\u{2CF0}', '\u{2CF1}', '\u{2DE0}', '\u{2DE1}', '\u{2DE2}', '\u{2DE3}',
+    '\u{2DE4}', '\u{2DE5}', '\u{2DE6}', '\u{2DE7}', '\u{2DE8}', '\u{2DE9}', '\u{2DEA}',
+    '\u{2DEB}', '\u{2DEC}', '\u{2DED}', '\u{2DEE}', '\u{2DEF}', '\u{2DF0}', '\u{2DF1}',
+    '\u{2DF2}', '\u{2DF3}', '\u{2DF4}', '\u{2DF5}', '\u{2DF6}', '\u{2DF7}', '\u{2DF8}',
+    '\u{2DF9}', '\u{2DFA}', '\u{2DFB}', '\u{2DFC}', '\u{2DFD}', '\u{2DFE}', '\u{2DFF}',
+    '\u{A66F}', '\u{A67C}', '\u{A67D}', '\u{A6F0}', '\u{A6F1}', '\u{A8E0}', '\u{A8E1}',
+    '\u{A8E2}', '\u{A8E3}', '\u{A8E4}', '\u{A8E5}', '\u{A8E6}', '\u{A8E7}', '\u{A8E8}',
+    '\u{A8E9}', '\u{A8EA}', '\u{A8EB}', '\u{A8EC}', '\u{A8ED}', '\u{A8EE}', '\u{A8EF}',
+    '\u{A8F0}', '\u{A8F1}', '\u{AAB0}', '\u{AAB2}', '\u{AAB3}', '\u{AAB7}', '\u{AAB8}',
+    '\u{AABE}', '\u{AABF}', '\u{AAC1}', '\u{FE20}', '\u{FE21}', '\u{FE22}', '\u{FE23}',
+    '\u{FE24}', '\u{FE25}', '\u{FE26}', '\u{10A0F}', '\u{10A38}', '\u{1D185}', '\u{1D186}',
+    '\u{1D187}', '\u{1D188}', '\u{1D189}', '\u{1D1AA}', '\u{1D1AB}', '\u{1D1AC}', '\u{1D1AD}',
+    '\u{1D242}', '\u{1D243}', '\u{1D244}',
+];
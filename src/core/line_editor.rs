@@ -0,0 +1,913 @@
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use super::{
+    display_width, grapheme_width, graphemes, next_grapheme_boundary, prev_grapheme_boundary,
+    window_size, ReadLineError, StdinEscSeq, StdoutEscSeq, Termset, Token, TokenReader, TsStdout,
+    ECHO, ICANON, ISIG,
+};
+
+/// How many kills the kill ring remembers
+pub const KILL_RING_SIZE: usize = 32;
+
+/// How many lines a `History` keeps by default
+pub const DEFAULT_HISTORY_SIZE: usize = 1000;
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Start of the word before `index`, like readline's `backward-word`
+fn prev_word(s: &str, index: usize) -> usize {
+    s[..index]
+        .trim_end_matches(|c| !is_word_char(c))
+        .trim_end_matches(is_word_char)
+        .len()
+}
+
+/// End of the word after `index`, like readline's `forward-word`
+fn next_word(s: &str, index: usize) -> usize {
+    let rest = s[index..]
+        .trim_start_matches(|c| !is_word_char(c))
+        .trim_start_matches(is_word_char);
+    s.len() - rest.len()
+}
+
+/// Start of the whitespace-delimited word before `index`, like Ctrl-W in a shell
+fn prev_big_word(s: &str, index: usize) -> usize {
+    s[..index]
+        .trim_end()
+        .trim_end_matches(|c: char| !c.is_whitespace())
+        .len()
+}
+
+/// Work out where we end up after writing `s` from `(row, col)` on a terminal that is `cols`
+/// wide. A returned `col == cols` means we're in the pending-wrap state at the end of `row`.
+fn layout(cols: usize, (mut row, mut col): (usize, usize), s: &str) -> (usize, usize) {
+    for grapheme in graphemes(s) {
+        let w = grapheme_width(grapheme);
+        // wide characters that don't fit go on the next row, leaving a gap
+        if col + w > cols {
+            row += 1;
+            col = 0;
+        }
+        col += w;
+    }
+    (row, col)
+}
+
+/// The longest prefix that all of `candidates` share
+fn common_prefix(candidates: &[String]) -> &str {
+    let first = &candidates[0];
+    let mut len = first.len();
+    for candidate in &candidates[1..] {
+        len = first[..len]
+            .char_indices()
+            .zip(candidate.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(len.min(candidate.len()), |((i, _), _)| i);
+    }
+    &first[..len]
+}
+
+/// A list of previously entered lines, optionally backed by a file (one line per entry)
+pub struct History {
+    entries: Vec<String>,
+    max_len: usize,
+    path: Option<PathBuf>,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl History {
+    /// An in-memory history
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            max_len: DEFAULT_HISTORY_SIZE,
+            path: None,
+        }
+    }
+
+    /// Load history from `path`, new lines are appended to it as they are pushed. The file
+    /// doesn't have to exist yet.
+    pub fn with_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let entries = match fs::read_to_string(&path) {
+            Ok(contents) => contents.lines().map(String::from).collect(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        let mut history = Self {
+            entries,
+            max_len: DEFAULT_HISTORY_SIZE,
+            path: Some(path),
+        };
+        history.truncate();
+        Ok(history)
+    }
+
+    /// Set the maximum number of entries we keep, dropping the oldest ones if needed
+    pub fn set_max_len(&mut self, max_len: usize) {
+        self.max_len = max_len;
+        self.truncate();
+    }
+
+    fn truncate(&mut self) {
+        if self.entries.len() > self.max_len {
+            self.entries.drain(..self.entries.len() - self.max_len);
+        }
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Add a line to the history (and the history file). Empty lines and repeats of the last
+    /// line are ignored.
+    pub fn push(&mut self, line: &str) -> io::Result<()> {
+        if line.trim().is_empty() || self.entries.last().map(String::as_str) == Some(line) {
+            return Ok(());
+        }
+        self.entries.push(line.to_string());
+        self.truncate();
+
+        if let Some(path) = &self.path {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    /// Rewrite the history file with only the entries we're keeping. `push` only ever appends,
+    /// so call this every now and then to stop the file growing forever.
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(path) = &self.path {
+            let mut contents = self.entries.join("\n");
+            contents.push('\n');
+            fs::write(path, contents)?;
+        }
+        Ok(())
+    }
+}
+
+/// Called with the line and (byte) cursor position when Tab is pressed
+pub type Completer = Box<dyn FnMut(&str, usize) -> Completion>;
+
+/// What a completion callback gives back: replace `line[start..cursor]` with one of
+/// `candidates`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Completion {
+    pub start: usize,
+    pub candidates: Vec<String>,
+}
+
+/// The kinds of command we need to remember between keypresses, so that kills stick together,
+/// typing is undone a word at a time, and Alt-y knows what to replace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Other,
+    Insert,
+    Kill,
+    Yank { start: usize, end: usize },
+}
+
+struct Search {
+    query: String,
+    /// the history entry we're currently showing, if anything matched
+    found: Option<usize>,
+    /// what we had before we started searching, so Ctrl-G can put it back
+    original: (String, usize),
+}
+
+/// A readline-style line editor, see `read_line`.
+///
+/// Supports the usual emacs-ish bindings: arrows, Home/End, Ctrl-A/E/B/F, Alt-B/F and
+/// Ctrl-Left/Right for word movement, Ctrl-K/U/W and Alt-D/Alt-Backspace to kill, Ctrl-Y and
+/// Alt-Y to yank, Ctrl-_ to undo, Up/Down and Ctrl-P/N for history, Ctrl-R to search the
+/// history and Tab to complete.
+///
+/// ```rs
+/// let mut editor = tsc::LineEditor::new("> ");
+/// editor.set_history(tsc::History::with_file(".history")?);
+/// editor.set_completer(|line, cursor| complete_command(line, cursor));
+/// loop {
+///     match editor.read_line(&mut stdout, &mut reader) {
+///         Ok(line) => run(&line),
+///         Err(_) => break,
+///     }
+/// }
+/// ```
+pub struct LineEditor {
+    prompt: String,
+    history: History,
+    kill_ring: VecDeque<String>,
+    completer: Option<Completer>,
+
+    line: String,
+    /// byte index into `line`
+    cursor: usize,
+    undo: Vec<(String, usize)>,
+    last_command: Command,
+    /// index into the history of what we're showing, `entries().len()` is the new line
+    history_index: usize,
+    /// the new line, while we're looking at history
+    saved_line: String,
+    search: Option<Search>,
+
+    cols: usize,
+    /// how many rows below the start of the prompt the terminal cursor is
+    cursor_row: usize,
+    /// how many rows below the start of the prompt the end of the line is
+    end_row: usize,
+    /// whether the line exactly filled its last row, so `end_row` is an empty row
+    wrapped_end: bool,
+}
+
+impl LineEditor {
+    /// `prompt` should be plain text, escape sequences will throw the cursor math off
+    pub fn new(prompt: &str) -> Self {
+        Self {
+            prompt: prompt.to_string(),
+            history: History::new(),
+            kill_ring: VecDeque::new(),
+            completer: None,
+            line: String::new(),
+            cursor: 0,
+            undo: Vec::new(),
+            last_command: Command::Other,
+            history_index: 0,
+            saved_line: String::new(),
+            search: None,
+            cols: 80,
+            cursor_row: 0,
+            end_row: 0,
+            wrapped_end: false,
+        }
+    }
+
+    pub fn set_prompt(&mut self, prompt: &str) {
+        self.prompt = prompt.to_string();
+    }
+
+    pub fn set_history(&mut self, history: History) {
+        self.history = history;
+    }
+
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    pub fn history_mut(&mut self) -> &mut History {
+        &mut self.history
+    }
+
+    /// Set the function that is called when Tab is pressed. It gets the line and the (byte)
+    /// cursor position. If there is one candidate it is inserted, if there are several we
+    /// insert as much as they have in common, and list them if that doesn't get us anywhere.
+    pub fn set_completer<F>(&mut self, completer: F)
+    where
+        F: FnMut(&str, usize) -> Completion + 'static,
+    {
+        self.completer = Some(Box::new(completer));
+    }
+
+    /// Tell the editor how wide the terminal is, e.g. after an `Event::Resize`. This is
+    /// queried automatically when a line is started.
+    pub fn set_cols(&mut self, cols: usize) {
+        self.cols = cols.max(1);
+    }
+
+    /// The line as it currently is
+    pub fn line(&self) -> &str {
+        &self.line
+    }
+
+    /// Put the terminal in raw mode and read a line from `reader`, the terminal is put back
+    /// how it was when we return. If you're already using an `EventLoop`, use `start` and
+    /// `feed` instead.
+    pub fn read_line<W: io::Write>(
+        &mut self,
+        stdout: &mut TsStdout<W>,
+        reader: &mut TokenReader,
+    ) -> Result<String, ReadLineError> {
+        let mut termset = Termset::new().map_err(|_| io::Error::last_os_error())?;
+        termset.disable_lflag(ECHO | ICANON | ISIG);
+        termset.update(None);
+
+        self.start(stdout);
+        stdout.flush();
+        loop {
+            while !reader.has_token() {
+                match reader.read_available() {
+                    // stdin was closed (a terminal that's gone away gives `EIO` instead), and
+                    // it's never going to give us anything else
+                    Ok(0) => return self.closed(stdout),
+                    Err(e) if e.raw_os_error() == Some(libc::EIO) => return self.closed(stdout),
+                    Ok(_) => (),
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            let token = reader.try_next().unwrap();
+            let result = self.feed(token, stdout);
+            stdout.flush();
+            if let Some(result) = result {
+                return result;
+            }
+        }
+    }
+
+    /// `read_line` ran out of input
+    fn closed<W: io::Write>(&mut self, stdout: &mut TsStdout<W>) -> Result<String, ReadLineError> {
+        self.finish(stdout);
+        stdout.flush();
+        Err(ReadLineError::Eof)
+    }
+
+    /// Start a new line and draw the prompt, the terminal should already be in raw mode
+    pub fn start<W: io::Write>(&mut self, stdout: &mut TsStdout<W>) {
+        if let Ok(size) = window_size() {
+            self.set_cols(size.cols as usize);
+        }
+        self.line.clear();
+        self.cursor = 0;
+        self.undo.clear();
+        self.last_command = Command::Other;
+        self.history_index = self.history.entries().len();
+        self.saved_line.clear();
+        self.search = None;
+        self.cursor_row = 0;
+        self.end_row = 0;
+        self.refresh(stdout);
+    }
+
+    /// Handle a single token, returns `Some` once the line is finished
+    pub fn feed<W: io::Write>(
+        &mut self,
+        token: Token,
+        stdout: &mut TsStdout<W>,
+    ) -> Option<Result<String, ReadLineError>> {
+        if self.search.is_some() {
+            match self.feed_search(&token) {
+                SearchOutcome::Handled => {
+                    self.refresh(stdout);
+                    return None;
+                }
+                SearchOutcome::Exited => (),
+            }
+        }
+
+        let mut command = Command::Other;
+        match token {
+            Token::Char(c) => match c {
+                "\r" | "\n" => {
+                    self.finish(stdout);
+                    return Some(Ok(self.accept()));
+                }
+                "\u{3}" => {
+                    self.finish(stdout);
+                    return Some(Err(ReadLineError::Interrupted));
+                }
+                "\u{4}" if self.line.is_empty() => {
+                    self.finish(stdout);
+                    return Some(Err(ReadLineError::Eof));
+                }
+                "\u{4}" => self.delete(),
+                "\u{1}" => self.cursor = 0,
+                "\u{5}" => self.cursor = self.line.len(),
+                "\u{2}" => self.cursor = prev_grapheme_boundary(&self.line, self.cursor),
+                "\u{6}" => self.cursor = next_grapheme_boundary(&self.line, self.cursor),
+                "\u{7f}" | "\u{8}" => self.backspace(),
+                "\t" => self.complete(stdout),
+                "\u{b}" => command = self.kill(self.cursor..self.line.len(), true),
+                "\u{15}" => command = self.kill(0..self.cursor, false),
+                "\u{17}" => {
+                    let start = prev_big_word(&self.line, self.cursor);
+                    command = self.kill(start..self.cursor, false);
+                }
+                "\u{19}" => command = self.yank(),
+                "\u{1f}" => self.undo(),
+                "\u{10}" => self.history_prev(),
+                "\u{e}" => self.history_next(),
+                "\u{12}" => self.start_search(),
+                c if !c.chars().any(char::is_control) => {
+                    self.insert(c);
+                    command = Command::Insert;
+                }
+                _ => (),
+            },
+            Token::Esc(seq) => match seq {
+                StdinEscSeq::MoveLeft(_) => {
+                    self.cursor = prev_grapheme_boundary(&self.line, self.cursor)
+                }
+                StdinEscSeq::MoveRight(_) => {
+                    self.cursor = next_grapheme_boundary(&self.line, self.cursor)
+                }
+                StdinEscSeq::MoveUp(_) => self.history_prev(),
+                StdinEscSeq::MoveDown(_) => self.history_next(),
+                StdinEscSeq::Home => self.cursor = 0,
+                StdinEscSeq::End => self.cursor = self.line.len(),
+                StdinEscSeq::Delete => self.delete(),
+                StdinEscSeq::WordLeft | StdinEscSeq::Alt('b') => {
+                    self.cursor = prev_word(&self.line, self.cursor)
+                }
+                StdinEscSeq::WordRight | StdinEscSeq::Alt('f') => {
+                    self.cursor = next_word(&self.line, self.cursor)
+                }
+                StdinEscSeq::Alt('d') => {
+                    let end = next_word(&self.line, self.cursor);
+                    command = self.kill(self.cursor..end, true);
+                }
+                StdinEscSeq::Alt('\u{7f}' | '\u{8}') => {
+                    let start = prev_word(&self.line, self.cursor);
+                    command = self.kill(start..self.cursor, false);
+                }
+                StdinEscSeq::Alt('y') => command = self.yank_pop(),
+                _ => (),
+            },
+            // replies to queries, which aren't for us
+            _ => (),
+        }
+        self.last_command = command;
+        self.refresh(stdout);
+        None
+    }
+
+    /// Remember the line so it can be undone. Runs of typing are undone in one go.
+    fn checkpoint(&mut self, command: Command) {
+        if command == Command::Insert && self.last_command == Command::Insert {
+            return;
+        }
+        let state = (self.line.clone(), self.cursor);
+        if self.undo.last() != Some(&state) {
+            self.undo.push(state);
+        }
+    }
+
+    fn undo(&mut self) {
+        if let Some((line, cursor)) = self.undo.pop() {
+            self.line = line;
+            self.cursor = cursor;
+        }
+    }
+
+    fn insert(&mut self, s: &str) {
+        self.checkpoint(Command::Insert);
+        self.line.insert_str(self.cursor, s);
+        self.cursor += s.len();
+    }
+
+    fn replace(&mut self, start: usize, end: usize, s: &str) {
+        self.line.replace_range(start..end, s);
+        self.cursor = start + s.len();
+    }
+
+    fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.checkpoint(Command::Other);
+            let start = prev_grapheme_boundary(&self.line, self.cursor);
+            self.replace(start, self.cursor, "");
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.cursor < self.line.len() {
+            self.checkpoint(Command::Other);
+            let end = next_grapheme_boundary(&self.line, self.cursor);
+            self.line.replace_range(self.cursor..end, "");
+        }
+    }
+
+    /// Cut `range` into the kill ring. Consecutive kills are joined into one entry, in the
+    /// order they were on the line.
+    fn kill(&mut self, range: std::ops::Range<usize>, forward: bool) -> Command {
+        if range.is_empty() {
+            return self.last_command;
+        }
+        self.checkpoint(Command::Kill);
+        let text = &self.line[range.clone()];
+        match self.kill_ring.front_mut() {
+            Some(top) if self.last_command == Command::Kill && forward => top.push_str(text),
+            Some(top) if self.last_command == Command::Kill => top.insert_str(0, text),
+            _ => {
+                self.kill_ring.push_front(text.to_string());
+                self.kill_ring.truncate(KILL_RING_SIZE);
+            }
+        }
+        self.replace(range.start, range.end, "");
+        Command::Kill
+    }
+
+    fn yank(&mut self) -> Command {
+        let Some(text) = self.kill_ring.front().cloned() else {
+            return Command::Other;
+        };
+        self.checkpoint(Command::Other);
+        let start = self.cursor;
+        self.insert(&text);
+        Command::Yank {
+            start,
+            end: self.cursor,
+        }
+    }
+
+    /// Replace what we just yanked with the next thing in the kill ring
+    fn yank_pop(&mut self) -> Command {
+        let Command::Yank { start, end } = self.last_command else {
+            return Command::Other;
+        };
+        self.kill_ring.rotate_left(1);
+        let text = self.kill_ring.front().cloned().unwrap_or_default();
+        self.replace(start, end, &text);
+        Command::Yank {
+            start,
+            end: self.cursor,
+        }
+    }
+
+    fn show_history(&mut self, index: usize) {
+        if self.history_index == self.history.entries().len() {
+            self.saved_line = self.line.clone();
+        }
+        self.history_index = index;
+        self.line = match self.history.entries().get(index) {
+            Some(entry) => entry.clone(),
+            None => self.saved_line.clone(),
+        };
+        self.cursor = self.line.len();
+        self.undo.clear();
+    }
+
+    fn history_prev(&mut self) {
+        if self.history_index > 0 {
+            self.show_history(self.history_index - 1);
+        }
+    }
+
+    fn history_next(&mut self) {
+        if self.history_index < self.history.entries().len() {
+            self.show_history(self.history_index + 1);
+        }
+    }
+
+    fn start_search(&mut self) {
+        self.search = Some(Search {
+            query: String::new(),
+            found: None,
+            original: (self.line.clone(), self.cursor),
+        });
+    }
+
+    /// Look for the query in history entries older than `before`, showing the newest match
+    fn search_from(&mut self, before: usize) {
+        let Some(search) = &mut self.search else {
+            return;
+        };
+        let entries = self.history.entries();
+        let found = entries[..before.min(entries.len())]
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(i, entry)| entry.find(&search.query).map(|at| (i, at)));
+        match found {
+            Some((i, at)) => {
+                search.found = Some(i);
+                self.line = entries[i].clone();
+                self.cursor = at;
+            }
+            None if search.query.is_empty() => {
+                search.found = None;
+                (self.line, self.cursor) = search.original.clone();
+            }
+            // keep showing the last match, like readline does
+            None => search.found = None,
+        }
+    }
+
+    fn feed_search(&mut self, token: &Token) -> SearchOutcome {
+        let search = self.search.as_mut().unwrap();
+        match *token {
+            Token::Char("\u{12}") => {
+                let before = search.found.unwrap_or(self.history_index);
+                self.search_from(before);
+            }
+            Token::Char("\u{7}" | "\u{3}") => {
+                let search = self.search.take().unwrap();
+                (self.line, self.cursor) = search.original;
+            }
+            Token::Char("\u{7f}" | "\u{8}") => {
+                search.query.pop();
+                self.search_from(self.history_index);
+            }
+            Token::Char(c) if !c.chars().any(char::is_control) => {
+                search.query.push_str(c);
+                self.search_from(self.history_index);
+            }
+            _ => {
+                // anything else accepts what we found and then does whatever it normally does
+                let search = self.search.take().unwrap();
+                if let Some(found) = search.found {
+                    self.history_index = found;
+                }
+                self.undo.clear();
+                return SearchOutcome::Exited;
+            }
+        }
+        SearchOutcome::Handled
+    }
+
+    fn complete<W: io::Write>(&mut self, stdout: &mut TsStdout<W>) {
+        let Some(completer) = self.completer.as_mut() else {
+            return;
+        };
+        let Completion { start, candidates } = completer(&self.line, self.cursor);
+        let start = start.min(self.cursor);
+        if candidates.is_empty() || !self.line.is_char_boundary(start) {
+            stdout.write_str("\u{7}");
+            return;
+        }
+
+        let prefix = common_prefix(&candidates);
+        if candidates.len() == 1 || prefix.len() > self.cursor - start {
+            let prefix = prefix.to_string();
+            self.checkpoint(Command::Other);
+            self.replace(start, self.cursor, &prefix);
+            return;
+        }
+
+        // nothing we can insert, so list them underneath and redraw the prompt after
+        self.move_to_end(stdout);
+        let col_width = candidates
+            .iter()
+            .map(|c| display_width(c))
+            .max()
+            .unwrap_or(0)
+            + 2;
+        let per_row = (self.cols / col_width).max(1);
+        for row in candidates.chunks(per_row) {
+            stdout.write_str("\r\n");
+            for candidate in row {
+                stdout
+                    .write_str(candidate)
+                    .write_str(&" ".repeat(col_width - display_width(candidate)));
+            }
+        }
+        stdout.write_str("\r\n");
+        self.cursor_row = 0;
+        self.end_row = 0;
+    }
+
+    fn prompt(&self) -> String {
+        match &self.search {
+            Some(search) if search.found.is_none() && !search.query.is_empty() => {
+                format!("(failed reverse-i-search)`{}': ", search.query)
+            }
+            Some(search) => format!("(reverse-i-search)`{}': ", search.query),
+            None => self.prompt.clone(),
+        }
+    }
+
+    /// Redraw the prompt and line, and put the cursor in the right place
+    fn refresh<W: io::Write>(&mut self, stdout: &mut TsStdout<W>) {
+        let prompt = self.prompt();
+        if self.cursor_row > 0 {
+            stdout.exec([&StdoutEscSeq::MoveUp(self.cursor_row as u32)]);
+        }
+        stdout
+            .write_str("\r")
+            .exec([&StdoutEscSeq::EraseToEndOfScreen])
+            .write_str(&prompt)
+            .write_str(&self.line);
+
+        let (mut end_row, end_col) =
+            layout(self.cols, layout(self.cols, (0, 0), &prompt), &self.line);
+        self.wrapped_end = end_col == self.cols;
+        if self.wrapped_end {
+            // get out of the pending-wrap state, so we know which row we're on
+            stdout.write_str("\r\n");
+            end_row += 1;
+        }
+
+        let (mut row, mut col) = layout(
+            self.cols,
+            layout(self.cols, (0, 0), &prompt),
+            &self.line[..self.cursor],
+        );
+        if col == self.cols {
+            row += 1;
+            col = 0;
+        }
+        if end_row > row {
+            stdout.exec([&StdoutEscSeq::MoveUp((end_row - row) as u32)]);
+        }
+        stdout.write_str("\r");
+        if col > 0 {
+            stdout.exec([&StdoutEscSeq::MoveRight(col as u32)]);
+        }
+
+        self.cursor_row = row;
+        self.end_row = end_row;
+    }
+
+    fn move_to_end<W: io::Write>(&mut self, stdout: &mut TsStdout<W>) {
+        if self.end_row > self.cursor_row {
+            stdout.exec([&StdoutEscSeq::MoveDown(
+                (self.end_row - self.cursor_row) as u32,
+            )]);
+        }
+        self.cursor_row = self.end_row;
+    }
+
+    /// Leave the line as it is on the screen and move onto the next one
+    fn finish<W: io::Write>(&mut self, stdout: &mut TsStdout<W>) {
+        self.search = None;
+        self.cursor = self.line.len();
+        self.refresh(stdout);
+        // `refresh` already moved us onto a fresh row if the line filled the last one exactly
+        if !self.wrapped_end {
+            stdout.write_str("\r\n");
+        }
+    }
+
+    fn accept(&mut self) -> String {
+        let line = std::mem::take(&mut self.line);
+        // a history file we can't write to shouldn't stop the user from entering lines
+        let _ = self.history.push(&line);
+        line
+    }
+}
+
+enum SearchOutcome {
+    Handled,
+    Exited,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn editor() -> (LineEditor, TsStdout<Vec<u8>>) {
+        let mut editor = LineEditor::new("> ");
+        let mut stdout = TsStdout::with_writer(Vec::new());
+        editor.start(&mut stdout);
+        (editor, stdout)
+    }
+
+    /// Feed `keys` one at a time, and what the last one returned
+    fn feed<'a>(
+        editor: &mut LineEditor,
+        stdout: &mut TsStdout<Vec<u8>>,
+        keys: impl IntoIterator<Item = Token<'a>>,
+    ) -> Option<Result<String, ReadLineError>> {
+        keys.into_iter()
+            .map(|key| editor.feed(key, stdout))
+            .last()
+            .flatten()
+    }
+
+    /// `s` typed a key at a time
+    fn typed(s: &str) -> Vec<Token<'_>> {
+        graphemes(s).map(Token::Char).collect()
+    }
+
+    fn alt(c: char) -> Token<'static> {
+        Token::Esc(StdinEscSeq::Alt(c))
+    }
+
+    #[test]
+    fn word_motions() {
+        let s = "foo_bar  baz.qux";
+        assert_eq!(prev_word(s, s.len()), 13);
+        assert_eq!(prev_word(s, 13), 9);
+        assert_eq!(prev_word(s, 9), 0);
+        assert_eq!(prev_word(s, 0), 0);
+        assert_eq!(next_word(s, 0), 7);
+        assert_eq!(next_word(s, 7), 12);
+        assert_eq!(next_word(s, 12), s.len());
+        assert_eq!(next_word(s, s.len()), s.len());
+        assert_eq!(prev_big_word(s, s.len()), 9);
+        assert_eq!(prev_big_word(s, 9), 0);
+        assert_eq!(prev_word("héllo wörld", 13), 7);
+    }
+
+    #[test]
+    fn layout_wraps() {
+        assert_eq!(layout(10, (0, 0), "abc"), (0, 3));
+        // filling the row exactly leaves us waiting to wrap
+        assert_eq!(layout(10, (0, 0), "abcdefghij"), (0, 10));
+        assert_eq!(layout(10, (0, 0), "abcdefghijk"), (1, 1));
+        // a wide character that doesn't fit goes on the next row
+        assert_eq!(layout(10, (0, 0), "abcdefghi日"), (1, 2));
+        assert_eq!(layout(10, (0, 8), "日本"), (1, 2));
+        assert_eq!(layout(4, (2, 0), "e\u{301}e\u{301}"), (2, 2));
+    }
+
+    #[test]
+    fn common_prefixes() {
+        let strings = |s: &[&str]| s.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(common_prefix(&strings(&["foobar", "foobaz", "foo"])), "foo");
+        assert_eq!(common_prefix(&strings(&["héllo", "hélp"])), "hél");
+        assert_eq!(common_prefix(&strings(&["abc"])), "abc");
+        assert_eq!(common_prefix(&strings(&["x", "y"])), "");
+    }
+
+    #[test]
+    fn complete_inserts_the_common_prefix() {
+        let (mut editor, mut stdout) = editor();
+        editor.set_completer(|line, cursor| {
+            let start = line[..cursor].rfind(' ').map_or(0, |i| i + 1);
+            let candidates = ["foobar", "foobaz", "quux"]
+                .into_iter()
+                .filter(|c| c.starts_with(&line[start..cursor]))
+                .map(String::from)
+                .collect();
+            Completion { start, candidates }
+        });
+        feed(&mut editor, &mut stdout, typed("x f\t"));
+        assert_eq!(editor.line(), "x fooba");
+        feed(&mut editor, &mut stdout, typed("r\u{17}q\t"));
+        assert_eq!(editor.line(), "x quux");
+    }
+
+    #[test]
+    fn kills_join_and_yank_pop_cycles() {
+        let (mut editor, mut stdout) = editor();
+        feed(&mut editor, &mut stdout, typed("one two three"));
+        // two Ctrl-Ws in a row make one kill
+        feed(&mut editor, &mut stdout, typed("\u{17}\u{17}"));
+        assert_eq!(editor.line(), "one ");
+        // Ctrl-A then Ctrl-K is a new one
+        feed(&mut editor, &mut stdout, typed("\u{1}\u{b}"));
+        assert_eq!(editor.line(), "");
+
+        feed(&mut editor, &mut stdout, typed("\u{19}"));
+        assert_eq!(editor.line(), "one ");
+        feed(&mut editor, &mut stdout, [alt('y')]);
+        assert_eq!(editor.line(), "two three");
+        feed(&mut editor, &mut stdout, [alt('y')]);
+        assert_eq!(editor.line(), "one ");
+
+        // Alt-Y only does anything straight after a yank
+        feed(&mut editor, &mut stdout, typed("!"));
+        feed(&mut editor, &mut stdout, [alt('y')]);
+        assert_eq!(editor.line(), "one !");
+    }
+
+    #[test]
+    fn undo_takes_typing_back_in_one_go() {
+        let (mut editor, mut stdout) = editor();
+        feed(&mut editor, &mut stdout, typed("hello world"));
+        feed(&mut editor, &mut stdout, [alt('\u{7f}')]);
+        assert_eq!(editor.line(), "hello ");
+        feed(&mut editor, &mut stdout, typed("\u{7f}"));
+        assert_eq!(editor.line(), "hello");
+
+        feed(&mut editor, &mut stdout, typed("\u{1f}"));
+        assert_eq!(editor.line(), "hello ");
+        feed(&mut editor, &mut stdout, typed("\u{1f}"));
+        assert_eq!(editor.line(), "hello world");
+        feed(&mut editor, &mut stdout, typed("\u{1f}"));
+        assert_eq!(editor.line(), "");
+        // nothing left to undo
+        feed(&mut editor, &mut stdout, typed("\u{1f}"));
+        assert_eq!(editor.line(), "");
+    }
+
+    #[test]
+    fn history_search() {
+        let (mut editor, mut stdout) = editor();
+        for line in ["cargo build", "git status", "cargo test"] {
+            editor.history_mut().push(line).unwrap();
+        }
+        editor.start(&mut stdout);
+
+        feed(&mut editor, &mut stdout, typed("\u{12}cargo"));
+        assert_eq!(editor.line(), "cargo test");
+        // again for an older match
+        feed(&mut editor, &mut stdout, typed("\u{12}"));
+        assert_eq!(editor.line(), "cargo build");
+        // and nothing older than that, so we stay put
+        feed(&mut editor, &mut stdout, typed("\u{12}"));
+        assert_eq!(editor.line(), "cargo build");
+        let result = feed(&mut editor, &mut stdout, typed("\r"));
+        assert!(matches!(result, Some(Ok(line)) if line == "cargo build"));
+
+        // Ctrl-G puts back what we had
+        editor.start(&mut stdout);
+        feed(&mut editor, &mut stdout, typed("gi\u{12}st\u{7}"));
+        assert_eq!(editor.line(), "gi");
+
+        // Up goes back through them, Down comes back to the new line
+        feed(&mut editor, &mut stdout, typed("\u{10}\u{10}"));
+        assert_eq!(editor.line(), "cargo test");
+        feed(&mut editor, &mut stdout, typed("\u{e}\u{e}"));
+        assert_eq!(editor.line(), "gi");
+    }
+}
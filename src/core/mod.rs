@@ -2,7 +2,40 @@ mod termset;
 mod token;
 mod err;
 mod stdout;
+mod event;
+mod base64;
+mod style;
+mod color;
+mod terminfo;
+mod line_editor;
+mod screen;
+mod unicode;
+mod unicode_tables;
+mod notify;
+mod image;
+mod sixel;
+mod kitty;
+mod decode;
+mod ansi;
+mod transcript;
+mod tab_stops;
 
 pub use termset::*;
 pub use token::*;
-pub use stdout::*;
\ No newline at end of file
+pub use stdout::*;
+pub use event::*;
+pub use line_editor::*;
+pub use screen::*;
+pub use unicode::*;
+pub use style::*;
+pub use color::*;
+pub use notify::*;
+pub use image::*;
+pub use sixel::*;
+pub use kitty::*;
+pub use decode::*;
+pub use ansi::*;
+pub use transcript::*;
+pub use tab_stops::*;
+pub use terminfo::*;
+pub use err::*;
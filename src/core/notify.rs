@@ -0,0 +1,75 @@
+use std::env;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// How we can get the terminal to pop up a desktop notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NotificationProtocol {
+    /// OSC 9, iTerm2, ConEmu, WezTerm, Ghostty. There's only room for one line of text.
+    Osc9,
+    /// OSC 777 `notify`, urxvt (with the `notify` extension), foot, WezTerm, Ghostty
+    Osc777,
+    /// OSC 99, kitty's protocol, which has urgency levels and no length limit
+    Kitty,
+    /// No notifications, just ring the bell. Lots of terminals flash the tab or set the
+    /// window's urgency hint for this (see `StdoutEscSeq::EnableUrgentBell`).
+    Bell,
+}
+
+impl NotificationProtocol {
+    /// Work out what the terminal on stdout supports from the environment: `$TERM`,
+    /// `$TERM_PROGRAM` and the variables some terminals set for their children. Anything we
+    /// don't recognise gets `Bell`, and so does tmux, which doesn't pass notifications on.
+    pub fn detect() -> Self {
+        Self::detect_from(|name| env::var(name).ok())
+    }
+
+    fn detect_from(var: impl Fn(&str) -> Option<String>) -> Self {
+        let set = |name| var(name).is_some_and(|value| !value.is_empty());
+        let term = var("TERM").unwrap_or_default();
+        if set("TMUX") || term.starts_with("tmux") || term.starts_with("screen") {
+            return Self::Bell;
+        }
+        if term == "xterm-kitty" || set("KITTY_WINDOW_ID") {
+            return Self::Kitty;
+        }
+        if term.starts_with("foot") || term.starts_with("rxvt-unicode") {
+            return Self::Osc777;
+        }
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+        if matches!(program.as_str(), "iTerm.app" | "WezTerm" | "ghostty")
+            || var("ConEmuANSI").as_deref() == Some("ON")
+        {
+            return Self::Osc9;
+        }
+        Self::Bell
+    }
+}
+
+/// How much a notification should interrupt the user, for the protocols that support it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Urgency {
+    Low,
+    #[default]
+    Normal,
+    Critical,
+}
+
+impl Urgency {
+    /// The OSC 99 `u=` parameter for this
+    pub fn as_param(&self) -> u32 {
+        match *self {
+            Self::Low => 0,
+            Self::Normal => 1,
+            Self::Critical => 2,
+        }
+    }
+}
+
+/// Where the next OSC 99 notification id comes from
+static NEXT_NOTIFICATION_ID: AtomicU32 = AtomicU32::new(0);
+
+/// A fresh id for an OSC 99 notification, so chunks from different notifications don't get
+/// mixed up
+pub(crate) fn next_notification_id() -> u32 {
+    NEXT_NOTIFICATION_ID.fetch_add(1, Ordering::Relaxed)
+}
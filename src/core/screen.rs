@@ -0,0 +1,550 @@
+use std::io;
+
+use super::{
+    decimal, grapheme_width, graphemes, window_size, StdoutEscSeq, Style, TabStops, TsStdout,
+};
+
+/// The most bytes of UTF-8 a `Cell` can hold, longer symbols are cut short. This fits all but
+/// the very longest emoji ZWJ sequences.
+pub const CELL_SYMBOL_CAPACITY: usize = 31;
+
+/// One character cell on the screen: what's in it and how it looks. A wide character takes up
+/// two cells, the second of which has an empty symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    symbol: [u8; CELL_SYMBOL_CAPACITY],
+    len: u8,
+    pub style: Style,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self::BLANK
+    }
+}
+
+impl Cell {
+    /// A space in the default style, which is what an erased cell looks like
+    pub const BLANK: Cell = Cell {
+        symbol: {
+            let mut symbol = [0; CELL_SYMBOL_CAPACITY];
+            symbol[0] = b' ';
+            symbol
+        },
+        len: 1,
+        style: Style::new(),
+    };
+
+    /// `symbol` should be one grapheme (see `graphemes`)
+    pub fn new(symbol: &str, style: Style) -> Self {
+        let mut cell = Self {
+            style,
+            ..Self::BLANK
+        };
+        cell.set_symbol(symbol);
+        cell
+    }
+
+    pub fn symbol(&self) -> &str {
+        std::str::from_utf8(&self.symbol[..self.len as usize]).unwrap_or_default()
+    }
+
+    pub fn set_symbol(&mut self, symbol: &str) {
+        let mut len = symbol.len().min(CELL_SYMBOL_CAPACITY);
+        while !symbol.is_char_boundary(len) {
+            len -= 1;
+        }
+        self.symbol[..len].copy_from_slice(&symbol.as_bytes()[..len]);
+        self.len = len as u8;
+    }
+
+    /// How many columns the symbol takes up: `2` for wide characters, `0` for the cell after
+    /// one, and `1` otherwise
+    pub fn width(&self) -> usize {
+        match self.len {
+            0 => 0,
+            _ => grapheme_width(self.symbol()).clamp(1, 2),
+        }
+    }
+
+    /// The other half of a wide character
+    fn continuation(style: Style) -> Self {
+        Self {
+            len: 0,
+            style,
+            ..Self::BLANK
+        }
+    }
+}
+
+/// How many digits `n` is in decimal
+fn digits(n: u32) -> usize {
+    decimal(n, &mut [0; 10]).len()
+}
+
+/// A grid of cells you draw into, and then `present` to the terminal all at once. Only the
+/// cells that changed since the last `present` are sent.
+///
+/// Positions are `(row, col)` from `(0, 0)` at the top left, and anything drawn outside the
+/// screen is clipped.
+///
+/// A screen can also take up just a few rows at the cursor instead of the whole terminal (see
+/// `inline`), for progress bars and pickers that shouldn't hide what's already there.
+///
+/// ```rs
+/// let mut screen = Screen::new()?;
+/// screen.draw_text(0, 0, "hello", Style::new().bold());
+/// screen.present(&mut stdout);
+/// ```
+pub struct Screen {
+    rows: u16,
+    cols: u16,
+    /// what we think is on the terminal
+    front: Vec<Cell>,
+    /// what we want on the terminal after the next `present`
+    back: Vec<Cell>,
+    /// `front` can't be trusted, so the next `present` clears the screen and starts over
+    invalidated: bool,
+    /// where to leave the cursor after `present`, `None` to hide it
+    cursor: Option<(u16, u16)>,
+    /// whether we last showed the cursor (or hid it), `None` if we haven't touched it yet
+    cursor_shown: Option<bool>,
+    /// we're drawn in a few rows at the cursor, see `inline`
+    inline: bool,
+    /// for inline screens, where the terminal's cursor is (counting from our top left), `None`
+    /// until `present` makes room for us. The column can be `cols`, if the last column was
+    /// just written to and the cursor is waiting to wrap.
+    inline_cursor: Option<(u16, u16)>,
+    /// where `\t` goes in `draw_text`
+    tab_stops: TabStops,
+}
+
+impl Screen {
+    /// A screen the size of the terminal
+    pub fn new() -> io::Result<Self> {
+        let size = window_size()?;
+        Ok(Self::with_size(size.rows, size.cols))
+    }
+
+    pub fn with_size(rows: u16, cols: u16) -> Self {
+        let cells = rows as usize * cols as usize;
+        Self {
+            rows,
+            cols,
+            front: vec![Cell::BLANK; cells],
+            back: vec![Cell::BLANK; cells],
+            invalidated: true,
+            cursor: None,
+            cursor_shown: None,
+            inline: false,
+            inline_cursor: None,
+            tab_stops: TabStops::new(cols as u32),
+        }
+    }
+
+    /// A screen `rows` high and as wide as the terminal, drawn at the cursor instead of over
+    /// the whole terminal. The first `present` makes room for it, scrolling what's already
+    /// there up if it has to, and after that it only ever draws in those rows. Use
+    /// `print_above` to write things above it, and `leave_inline` when you're done.
+    ///
+    /// It starts on the cursor's line if the `TsStdout` knows that's at the start of a line
+    /// (see `TsStdout::sync_cursor_position`), otherwise on the next one.
+    ///
+    /// ```rs
+    /// let mut screen = Screen::inline(3)?;
+    /// for i in 0..=100 {
+    ///     screen.clear();
+    ///     screen.draw_text(1, 0, &format!("{}%", i), Style::new().bold());
+    ///     screen.present(&mut stdout);
+    /// }
+    /// screen.leave_inline(&mut stdout);
+    /// ```
+    pub fn inline(rows: u16) -> io::Result<Self> {
+        let size = window_size()?;
+        Ok(Self::inline_with_size(rows.min(size.rows), size.cols))
+    }
+
+    pub fn inline_with_size(rows: u16, cols: u16) -> Self {
+        Self {
+            inline: true,
+            ..Self::with_size(rows.max(1), cols)
+        }
+    }
+
+    /// `(rows, cols)`
+    pub fn size(&self) -> (u16, u16) {
+        (self.rows, self.cols)
+    }
+
+    /// Change the size (e.g. on `Event::Resize`). This clears everything, and the next
+    /// `present` redraws the whole screen.
+    pub fn resize(&mut self, rows: u16, cols: u16) {
+        let mut tab_stops = self.tab_stops.clone();
+        tab_stops.resize(cols as u32);
+        *self = Self {
+            cursor: self.cursor,
+            cursor_shown: self.cursor_shown,
+            inline: self.inline,
+            inline_cursor: self.inline_cursor,
+            tab_stops,
+            ..Self::with_size(rows, cols)
+        };
+    }
+
+    /// Forget what's on the terminal, so the next `present` redraws everything. Use this if
+    /// something else has written to it.
+    pub fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+
+    fn index(&self, row: u16, col: u16) -> Option<usize> {
+        (row < self.rows && col < self.cols)
+            .then(|| row as usize * self.cols as usize + col as usize)
+    }
+
+    pub fn cell(&self, row: u16, col: u16) -> Option<&Cell> {
+        self.index(row, col).map(|i| &self.back[i])
+    }
+
+    pub fn cell_mut(&mut self, row: u16, col: u16) -> Option<&mut Cell> {
+        self.index(row, col).map(|i| &mut self.back[i])
+    }
+
+    /// Set a cell. A wide character takes the cell after it too, and is replaced with a blank
+    /// if it's in the last column.
+    pub fn set_cell(&mut self, row: u16, col: u16, cell: Cell) {
+        let Some(i) = self.index(row, col) else {
+            return;
+        };
+        self.orphan(i);
+        if cell.width() < 2 {
+            self.back[i] = cell;
+        } else if col + 1 < self.cols {
+            self.orphan(i + 1);
+            self.back[i] = cell;
+            self.back[i + 1] = Cell::continuation(cell.style);
+        } else {
+            self.back[i] = Cell {
+                style: cell.style,
+                ..Cell::BLANK
+            };
+        }
+    }
+
+    /// We're about to overwrite cell `i`, so if it's half of a wide character, blank out the
+    /// other half
+    fn orphan(&mut self, i: usize) {
+        let col = i % self.cols as usize;
+        let other = match self.back[i].width() {
+            0 if col > 0 => i - 1,
+            2 if col + 1 < self.cols as usize => i + 1,
+            _ => return,
+        };
+        self.back[other] = Cell {
+            style: self.back[other].style,
+            ..Cell::BLANK
+        };
+    }
+
+    /// Draw `text` on one row starting at `col`, a grapheme at a time (so wide characters take
+    /// up two cells). A `\t` moves on to the next tab stop (see `set_tab_stops`) like it would
+    /// on a terminal, leaving the cells it skips alone. Other control characters are skipped,
+    /// and the text is cut off at the edge of the screen. Returns the column after the text.
+    pub fn draw_text(&mut self, row: u16, col: u16, text: &str, style: Style) -> u16 {
+        let mut col = col;
+        for grapheme in graphemes(text) {
+            if grapheme == "\t" {
+                if col >= self.cols {
+                    break;
+                }
+                col = self.tab_stops.next(col as u32, 1) as u16;
+                continue;
+            }
+            let width = grapheme_width(grapheme) as u16;
+            if width == 0 {
+                continue;
+            }
+            if col + width > self.cols {
+                break;
+            }
+            self.set_cell(row, col, Cell::new(grapheme, style));
+            col += width;
+        }
+        col
+    }
+
+    /// Set every cell in the `height` x `width` rectangle with its top left at `(row, col)`
+    pub fn fill(&mut self, row: u16, col: u16, height: u16, width: u16, cell: Cell) {
+        let rows = row..row.saturating_add(height).min(self.rows);
+        let end = col.saturating_add(width).min(self.cols);
+        let step = cell.width().max(1);
+        for row in rows {
+            for col in (col..end).step_by(step) {
+                self.set_cell(row, col, cell);
+            }
+        }
+    }
+
+    /// Blank out everything
+    pub fn clear(&mut self) {
+        self.back.fill(Cell::BLANK);
+    }
+
+    /// Where `\t` goes in `draw_text`, every 8 columns to start with. These are only for
+    /// laying out text on this screen, use `TsStdout::set_tab_stops` to change the terminal's.
+    pub fn set_tab_stops(&mut self, tab_stops: TabStops) {
+        self.tab_stops = tab_stops;
+        self.tab_stops.resize(self.cols as u32);
+    }
+
+    pub fn tab_stops(&self) -> &TabStops {
+        &self.tab_stops
+    }
+
+    /// Where the cursor should be after `present`, `None` hides it
+    pub fn set_cursor(&mut self, cursor: Option<(u16, u16)>) {
+        self.cursor = cursor;
+    }
+
+    /// Send whatever changed since the last `present` to the terminal as one frame (see
+    /// `TsStdout::frame`). The style `stdout` is left in is whatever the last cell drawn
+    /// needed.
+    pub fn present<W: io::Write>(&mut self, stdout: &mut TsStdout<W>) {
+        let mut frame = stdout.frame();
+        self.draw(&mut frame);
+    }
+
+    /// Print `text` above an inline screen (see `inline`), e.g. log lines, and draw the screen
+    /// again below it. `text` goes on lines of its own, and each `\n` in it starts a new one.
+    /// This does nothing for full-screen screens.
+    pub fn print_above<W: io::Write>(&mut self, stdout: &mut TsStdout<W>, text: &str) {
+        if !self.inline {
+            return;
+        }
+        let mut frame = stdout.frame();
+        let stdout = &mut *frame;
+        match self.inline_cursor {
+            Some(at) => {
+                self.move_cursor(stdout, Some(at), (0, 0));
+                stdout
+                    .reset_style()
+                    .exec([&StdoutEscSeq::EraseToEndOfScreen]);
+            }
+            None => self.start_line(stdout),
+        }
+
+        let text = text.strip_suffix('\n').unwrap_or(text);
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                stdout.write_str("\r\n");
+            }
+            stdout.write_str(line);
+        }
+        if text.contains('\x1b') {
+            // whatever style `text` left on isn't one `stdout` knows about
+            stdout.exec([&StdoutEscSeq::ResetStyle]);
+        }
+        stdout.write_str("\r\n");
+        self.inline_cursor = Some(self.make_room(stdout));
+        self.draw(stdout);
+    }
+
+    /// Finish with an inline screen: what's on it is left there (and ends up in the
+    /// scrollback once it scrolls off), and the cursor goes to the start of the line below
+    /// it. The next `present` starts over, wherever the cursor is by then.
+    pub fn leave_inline<W: io::Write>(&mut self, stdout: &mut TsStdout<W>) {
+        let Some(at) = self.inline_cursor.take() else {
+            return;
+        };
+        self.move_cursor(stdout, Some(at), (self.rows - 1, 0));
+        stdout.reset_style().write_str("\r\n");
+        if self.cursor_shown == Some(false) {
+            stdout.exec([&StdoutEscSeq::ShowCursor]);
+        }
+        self.cursor_shown = None;
+        self.invalidated = true;
+        stdout.flush();
+    }
+
+    /// Go to the start of a line for an inline screen to begin on
+    fn start_line<W: io::Write>(&self, stdout: &mut TsStdout<W>) {
+        if stdout.cursor_position().is_some_and(|(_, col)| col == 0) {
+            stdout.write_str("\r");
+        } else {
+            stdout.write_str("\r\n");
+        }
+    }
+
+    /// Make sure there are `rows` lines from the start of the cursor's line to the bottom of
+    /// the terminal, and clear them. Returns where the cursor ends up: our top left.
+    fn make_room<W: io::Write>(&mut self, stdout: &mut TsStdout<W>) -> (u16, u16) {
+        // `\n` scrolls when it's on the last line and `MoveDown` doesn't
+        stdout.reset_style();
+        for _ in 1..self.rows {
+            stdout.write_str("\n");
+        }
+        if self.rows > 1 {
+            stdout.exec([&StdoutEscSeq::MoveUp(self.rows as u32 - 1)]);
+        }
+        stdout.exec([&StdoutEscSeq::EraseToEndOfScreen]);
+        self.front.fill(Cell::BLANK);
+        self.invalidated = false;
+        (0, 0)
+    }
+
+    fn draw<W: io::Write>(&mut self, stdout: &mut TsStdout<W>) {
+        // where the terminal's cursor is, if we know
+        let mut at = None;
+        if self.inline {
+            at = Some(match self.inline_cursor {
+                Some(at) if !self.invalidated => at,
+                Some(at) => {
+                    self.move_cursor(stdout, Some(at), (0, 0));
+                    self.make_room(stdout)
+                }
+                None => {
+                    self.start_line(stdout);
+                    self.make_room(stdout)
+                }
+            });
+        } else if self.invalidated {
+            stdout
+                .reset_style()
+                .exec([&StdoutEscSeq::EraseEntireScreen]);
+            self.front.fill(Cell::BLANK);
+            self.invalidated = false;
+        }
+
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let i = row as usize * self.cols as usize + col as usize;
+                // the second half of a wide character gets drawn along with the first
+                if self.back[i] == self.front[i] || self.back[i].width() == 0 {
+                    continue;
+                }
+                self.move_cursor(stdout, at, (row, col));
+                stdout
+                    .set_style(&self.back[i].style)
+                    .write_str(self.back[i].symbol());
+                // after the last column the cursor is waiting to wrap, which terminals don't
+                // agree on the details of, so we just say we don't know. Inline screens can't
+                // just use `MoveTo` afterwards though, so they get `move_cursor` to sort it out.
+                let next = col + self.back[i].width() as u16;
+                at = (next < self.cols || self.inline).then_some((row, next));
+            }
+        }
+
+        match self.cursor {
+            Some(cursor) => {
+                self.move_cursor(stdout, at, cursor);
+                at = Some(cursor);
+                if self.cursor_shown != Some(true) {
+                    stdout.exec([&StdoutEscSeq::ShowCursor]);
+                }
+            }
+            None if self.cursor_shown != Some(false) => {
+                stdout.exec([&StdoutEscSeq::HideCursor]);
+            }
+            None => (),
+        }
+        self.cursor_shown = Some(self.cursor.is_some());
+        if self.inline {
+            self.inline_cursor = at;
+        }
+
+        self.front.copy_from_slice(&self.back);
+    }
+
+    /// Move the cursor from `at` (if we know where it is) to `to`, picking whatever takes the
+    /// fewest bytes
+    fn move_cursor<W: io::Write>(
+        &self,
+        stdout: &mut TsStdout<W>,
+        at: Option<(u16, u16)>,
+        to: (u16, u16),
+    ) {
+        let (row, col) = to;
+        let Some((at_row, at_col)) = at else {
+            stdout.exec([&StdoutEscSeq::MoveTo(row as u32, col as u32)]);
+            return;
+        };
+        if at_col >= self.cols {
+            // waiting to wrap (only inline screens keep track of this), which `\r` gets us
+            // out of
+            stdout.write_str("\r");
+            return self.move_cursor(stdout, Some((at_row, 0)), to);
+        }
+        if at == Some(to) {
+            return;
+        }
+
+        if at_row == row && at_col < col {
+            // going right a little way, it might be shorter to just draw what's in between
+            // again (if it doesn't need a different style)
+            let n = (col - at_col) as u32;
+            let gap = &self.back[self.index(row, at_col).unwrap()..self.index(row, col).unwrap()];
+            let redraw: usize = gap.iter().map(|cell| cell.symbol().len()).sum();
+            let style = *stdout.style();
+            let simple = gap.iter().all(|cell| {
+                cell.width() == 1 && cell.style.downsample(stdout.color_depth()) == style
+            });
+            // or the terminal's tab stops might take us straight there, if we know where they
+            // are
+            let tabs = stdout
+                .known_tab_stops()
+                .and_then(|tab_stops| tabs_to(tab_stops, at_col as u32, col as u32));
+            if simple && redraw <= 3 + digits(n) && tabs.is_none_or(|tabs| redraw <= tabs) {
+                for cell in gap {
+                    stdout.write_str(cell.symbol());
+                }
+            } else if let Some(tabs) =
+                tabs.filter(|&tabs| tabs <= 3 + digits(n).min(digits(col as u32 + 1)))
+            {
+                stdout.write_str(&"\t".repeat(tabs));
+            } else if digits(n) <= digits(col as u32 + 1) {
+                stdout.exec([&StdoutEscSeq::MoveRight(n)]);
+            } else {
+                stdout.exec([&StdoutEscSeq::MoveToColumn(col as u32)]);
+            }
+        } else if at_row == row && col == 0 {
+            stdout.write_str("\r");
+        } else if at_row == row {
+            let n = (at_col - col) as u32;
+            if digits(n) <= digits(col as u32 + 1) {
+                stdout.exec([&StdoutEscSeq::MoveLeft(n)]);
+            } else {
+                stdout.exec([&StdoutEscSeq::MoveToColumn(col as u32)]);
+            }
+        } else if at_row + 1 == row && col == 0 {
+            // `\r` first, in case `OPOST` is off and `\n` doesn't go back to the start
+            stdout.write_str("\r\n");
+        } else if self.inline {
+            // we don't know where on the terminal an inline screen is, so we can only move
+            // relative to where we are
+            if row < at_row {
+                stdout.exec([&StdoutEscSeq::MoveUp((at_row - row) as u32)]);
+            } else {
+                stdout.exec([&StdoutEscSeq::MoveDown((row - at_row) as u32)]);
+            }
+            self.move_cursor(stdout, Some((row, at_col)), to);
+        } else {
+            stdout.exec([&StdoutEscSeq::MoveTo(row as u32, col as u32)]);
+        }
+    }
+}
+
+/// How many `\t`s it takes to get from `from` to `to`, if there's a tab stop right on `to`
+fn tabs_to(tab_stops: &TabStops, from: u32, to: u32) -> Option<usize> {
+    let mut col = from;
+    let mut tabs = 0;
+    while col < to {
+        let next = tab_stops.next(col, 1);
+        if next == col {
+            // stuck at the last column
+            return None;
+        }
+        col = next;
+        tabs += 1;
+    }
+    (col == to).then_some(tabs)
+}
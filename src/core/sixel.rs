@@ -0,0 +1,352 @@
+use std::collections::HashMap;
+use std::io;
+
+use super::{decimal, window_size, Image};
+
+/// What we assume a cell is `(width, height)` in pixels when the terminal doesn't say
+pub const DEFAULT_CELL_SIZE: (u32, u32) = (10, 20);
+
+/// Pixels less opaque than this are left out (so whatever's behind them shows through), sixel
+/// can't do anything in between
+const ALPHA_THRESHOLD: u8 = 0x80;
+
+/// Turns an `Image` into sixel graphics (a `DCS q` string), which xterm (with
+/// `-ti vt340`), foot, mlterm, WezTerm and others can show. Images are brought down to a
+/// palette of at most 256 colors first.
+///
+/// ```rs
+/// let encoder = SixelEncoder::new().fit_cells(10, 40);
+/// stdout.draw_sixel(&encoder, &image).flush();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SixelEncoder {
+    max_colors: usize,
+    dither: bool,
+    /// `(rows, cols)` to scale the image to fit
+    cells: Option<(u32, u32)>,
+}
+
+impl Default for SixelEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SixelEncoder {
+    /// Up to 256 colors with dithering, at the image's own size
+    pub fn new() -> Self {
+        Self {
+            max_colors: 256,
+            dither: true,
+            cells: None,
+        }
+    }
+
+    /// Use at most `max_colors` colors (1 to 256). Fewer colors is less to send. Old terminals
+    /// only have 16 color registers.
+    pub fn max_colors(mut self, max_colors: usize) -> Self {
+        self.max_colors = max_colors.clamp(1, 256);
+        self
+    }
+
+    /// Whether to spread the error from picking the nearest palette color onto the pixels
+    /// around it (Floyd-Steinberg), which makes gradients look much better but flat colors a
+    /// bit noisier. Images with few enough colors aren't dithered either way.
+    pub fn dither(mut self, dither: bool) -> Self {
+        self.dither = dither;
+        self
+    }
+
+    /// Scale the image to the biggest size that fits in `rows` x `cols` cells, keeping the
+    /// aspect ratio. The cell size comes from the terminal (`WinSize::cell_size`), or
+    /// `DEFAULT_CELL_SIZE` if it doesn't report one.
+    pub fn fit_cells(mut self, rows: u32, cols: u32) -> Self {
+        self.cells = Some((rows, cols));
+        self
+    }
+
+    /// Write `image` as a sixel string to `w`
+    pub fn write_to<W: io::Write + ?Sized>(&self, image: &Image, w: &mut W) -> io::Result<()> {
+        let scaled;
+        let image = match self.cells {
+            Some((rows, cols)) => {
+                let cell_size = window_size()
+                    .ok()
+                    .and_then(|size| size.cell_size())
+                    .map_or(DEFAULT_CELL_SIZE, |(w, h)| (w as u32, h as u32));
+                scaled = image.fit_cells(rows, cols, cell_size);
+                &scaled
+            }
+            None => image,
+        };
+        let (palette, indices) = quantize(image, self.max_colors, self.dither);
+        write_sixel(image.width(), image.height(), &palette, &indices, w)
+    }
+
+    /// The sixel string for `image`
+    pub fn encode(&self, image: &Image) -> Vec<u8> {
+        let mut buf = Vec::new();
+        // writing to a `Vec` can't fail
+        let _ = self.write_to(image, &mut buf);
+        buf
+    }
+}
+
+type Rgb = [u8; 3];
+
+/// Pick a palette of at most `max_colors` for `image`, and which palette entry each pixel
+/// uses (`None` for transparent ones)
+fn quantize(image: &Image, max_colors: usize, dither: bool) -> (Vec<Rgb>, Vec<Option<u8>>) {
+    let mut histogram = HashMap::<Rgb, u32>::new();
+    for pixel in image.pixels().chunks_exact(4) {
+        if pixel[3] >= ALPHA_THRESHOLD {
+            *histogram.entry([pixel[0], pixel[1], pixel[2]]).or_default() += 1;
+        }
+    }
+    let mut colors: Vec<(Rgb, u32)> = histogram.into_iter().collect();
+    // so the same image always gets the same palette
+    colors.sort_unstable();
+
+    if colors.len() <= max_colors {
+        // every color fits as it is
+        let palette: Vec<Rgb> = colors.iter().map(|&(rgb, _)| rgb).collect();
+        let index: HashMap<Rgb, u8> = palette
+            .iter()
+            .enumerate()
+            .map(|(i, &rgb)| (rgb, i as u8))
+            .collect();
+        let indices = image
+            .pixels()
+            .chunks_exact(4)
+            .map(|p| (p[3] >= ALPHA_THRESHOLD).then(|| index[&[p[0], p[1], p[2]]]))
+            .collect();
+        return (palette, indices);
+    }
+
+    let palette = median_cut(colors, max_colors);
+    let mut nearest = Nearest::new(&palette);
+    let indices = if dither {
+        floyd_steinberg(image, &palette, &mut nearest)
+    } else {
+        image
+            .pixels()
+            .chunks_exact(4)
+            .map(|p| (p[3] >= ALPHA_THRESHOLD).then(|| nearest.get([p[0], p[1], p[2]])))
+            .collect()
+    };
+    (palette, indices)
+}
+
+/// Split the colors into `max_colors` boxes, always splitting the box with the widest range
+/// of a channel at the (pixel count weighted) median of that channel, and use the average
+/// color of each box
+fn median_cut(colors: Vec<(Rgb, u32)>, max_colors: usize) -> Vec<Rgb> {
+    /// the channel with the widest range in `colors`, and how wide
+    fn widest(colors: &[(Rgb, u32)]) -> (usize, u8) {
+        (0..3)
+            .map(|c| {
+                let (min, max) = colors.iter().fold((u8::MAX, 0), |(min, max), (rgb, _)| {
+                    (min.min(rgb[c]), max.max(rgb[c]))
+                });
+                (c, max - min)
+            })
+            .max_by_key(|&(_, range)| range)
+            .unwrap_or((0, 0))
+    }
+
+    let mut boxes = vec![colors];
+    while boxes.len() < max_colors {
+        let Some((i, (channel, range))) = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, colors)| colors.len() > 1)
+            .map(|(i, colors)| (i, widest(colors)))
+            .max_by_key(|&(_, (_, range))| range)
+        else {
+            break;
+        };
+        if range == 0 {
+            break;
+        }
+        let mut colors = boxes.swap_remove(i);
+        colors.sort_unstable_by_key(|&(rgb, _)| rgb[channel]);
+        let total: u64 = colors.iter().map(|&(_, count)| count as u64).sum();
+        let mut seen = 0;
+        let split = colors
+            .iter()
+            .position(|&(_, count)| {
+                seen += count as u64;
+                seen * 2 >= total
+            })
+            .unwrap_or(0)
+            // both halves need at least one color
+            .clamp(0, colors.len() - 2)
+            + 1;
+        let rest = colors.split_off(split);
+        boxes.push(colors);
+        boxes.push(rest);
+    }
+
+    boxes
+        .iter()
+        .map(|colors| {
+            let mut sum = [0u64; 3];
+            let mut total = 0u64;
+            for &(rgb, count) in colors {
+                for c in 0..3 {
+                    sum[c] += rgb[c] as u64 * count as u64;
+                }
+                total += count as u64;
+            }
+            sum.map(|s| ((s + total / 2) / total.max(1)) as u8)
+        })
+        .collect()
+}
+
+/// Finds the closest palette entry to a color, remembering the answers
+struct Nearest<'a> {
+    palette: &'a [Rgb],
+    cache: HashMap<Rgb, u8>,
+}
+
+impl<'a> Nearest<'a> {
+    fn new(palette: &'a [Rgb]) -> Self {
+        Self {
+            palette,
+            cache: HashMap::new(),
+        }
+    }
+
+    fn get(&mut self, rgb: Rgb) -> u8 {
+        let palette = self.palette;
+        *self.cache.entry(rgb).or_insert_with(|| {
+            let distance =
+                |p: &Rgb| -> i32 { (0..3).map(|c| (p[c] as i32 - rgb[c] as i32).pow(2)).sum() };
+            (0..palette.len())
+                .min_by_key(|&i| distance(&palette[i]))
+                .unwrap_or(0) as u8
+        })
+    }
+}
+
+/// Map each pixel to the nearest palette entry, pushing the difference onto the pixels to the
+/// right and below
+fn floyd_steinberg(image: &Image, palette: &[Rgb], nearest: &mut Nearest) -> Vec<Option<u8>> {
+    let width = image.width() as usize;
+    if width == 0 {
+        return Vec::new();
+    }
+    // the error carried onto this row and the next, with a pixel of padding either side
+    let mut errors = vec![[0i32; 3]; width + 2];
+    let mut next_errors = vec![[0i32; 3]; width + 2];
+    let mut indices = Vec::with_capacity(image.pixels().len() / 4);
+    for row in image.pixels().chunks_exact(width * 4) {
+        for (x, p) in row.chunks_exact(4).enumerate() {
+            if p[3] < ALPHA_THRESHOLD {
+                indices.push(None);
+                continue;
+            }
+            // errors are kept in 16ths
+            let rgb: Rgb =
+                [0, 1, 2].map(|c| (p[c] as i32 + errors[x + 1][c] / 16).clamp(0, 255) as u8);
+            let i = nearest.get(rgb);
+            indices.push(Some(i));
+            for c in 0..3 {
+                let error = rgb[c] as i32 - palette[i as usize][c] as i32;
+                errors[x + 2][c] += error * 7;
+                next_errors[x][c] += error * 3;
+                next_errors[x + 1][c] += error * 5;
+                next_errors[x + 2][c] += error;
+            }
+        }
+        std::mem::swap(&mut errors, &mut next_errors);
+        next_errors.fill([0; 3]);
+    }
+    indices
+}
+
+/// Write `n` in decimal
+fn write_decimal<W: io::Write + ?Sized>(w: &mut W, n: u32) -> io::Result<()> {
+    w.write_all(decimal(n, &mut [0; 10]))
+}
+
+/// Write a run of `count` of the same sixel, using `!` repeats where that's shorter
+fn write_run<W: io::Write + ?Sized>(w: &mut W, sixel: u8, count: u32) -> io::Result<()> {
+    match count {
+        0 => Ok(()),
+        1..=3 => w.write_all(&[sixel; 3][..count as usize]),
+        _ => {
+            w.write_all(b"!")?;
+            write_decimal(w, count)?;
+            w.write_all(&[sixel])
+        }
+    }
+}
+
+fn write_sixel<W: io::Write + ?Sized>(
+    width: u32,
+    height: u32,
+    palette: &[Rgb],
+    indices: &[Option<u8>],
+    w: &mut W,
+) -> io::Result<()> {
+    // `P2 = 1` leaves the pixels we don't draw alone, and the raster attributes say pixels
+    // are square and how big the image is
+    w.write_all(b"\x1bP0;1;0q\"1;1;")?;
+    write_decimal(w, width)?;
+    w.write_all(b";")?;
+    write_decimal(w, height)?;
+    for (i, rgb) in palette.iter().enumerate() {
+        w.write_all(b"#")?;
+        write_decimal(w, i as u32)?;
+        w.write_all(b";2")?;
+        // colors are in percent
+        for &c in rgb {
+            w.write_all(b";")?;
+            write_decimal(w, (c as u32 * 100 + 127) / 255)?;
+        }
+    }
+
+    if width == 0 || height == 0 {
+        // nothing to draw, just the header
+        return w.write_all(b"\x1b\\");
+    }
+    let width = width as usize;
+    let mut used = vec![false; palette.len()];
+    for band in indices.chunks(width * 6) {
+        let rows = band.len() / width;
+        used.fill(false);
+        for &i in band.iter().flatten() {
+            used[i as usize] = true;
+        }
+        let mut first = true;
+        for color in (0..palette.len()).filter(|&i| used[i]) {
+            if !first {
+                // back to the start of the band for the next color
+                w.write_all(b"$")?;
+            }
+            first = false;
+            w.write_all(b"#")?;
+            write_decimal(w, color as u32)?;
+
+            let (mut run, mut count) = (b'?', 0);
+            for x in 0..width {
+                let bits = (0..rows)
+                    .filter(|&dy| band[dy * width + x] == Some(color as u8))
+                    .fold(0, |bits, dy| bits | 1 << dy);
+                let sixel = b'?' + bits;
+                if sixel != run {
+                    write_run(w, run, count)?;
+                    (run, count) = (sixel, 0);
+                }
+                count += 1;
+            }
+            // blanks at the end of the line don't need sending
+            if run != b'?' {
+                write_run(w, run, count)?;
+            }
+        }
+        w.write_all(b"-")?;
+    }
+    w.write_all(b"\x1b\\")
+}
@@ -1,22 +1,85 @@
 use std::io::{self, Write};
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use libc::{c_void, STDIN_FILENO};
+use std::os::unix::io::RawFd;
 
-use super::StdoutEscSeq;
+use libc::{c_void, STDIN_FILENO, STDOUT_FILENO};
+
+use super::{
+    grapheme_width, graphemes, next_notification_id, settings_generation, window_size_of, Color,
+    ColorDepth, ColorScheme, Image, KittyCommand, KittyFormat, KittyPlacement,
+    NotificationProtocol, SixelEncoder, StdoutEscSeq, Style, TabStops, TermInfo, TerminalColor,
+    Token, TokenReader, Urgency, KITTY_PLACEHOLDER, ROW_COLUMN_DIACRITICS,
+};
 
 /// Wrap this in a `io::BufWriter` to get a faster handle to stdout, but without your
 /// write buffer being synchronised (which is literally fine... like who cares?)
-pub struct UnsafeStdout;
+///
+/// Writes go straight to a file descriptor, stdout unless you say otherwise. Interrupted
+/// writes are retried, and if the fd is non-blocking we wait for it to be writable instead of
+/// failing with `WouldBlock`. A `write` can still be short, `write_all` carries on from there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnsafeStdout {
+    fd: RawFd,
+}
+
+impl Default for UnsafeStdout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UnsafeStdout {
+    pub fn new() -> Self {
+        Self::from_fd(STDOUT_FILENO)
+    }
+
+    /// Write to `fd` instead, e.g. one for `/dev/tty` when stdout is redirected. This doesn't
+    /// take ownership of it, so it has to stay open for as long as this is used.
+    pub fn from_fd(fd: RawFd) -> Self {
+        Self { fd }
+    }
+
+    pub fn fd(&self) -> RawFd {
+        self.fd
+    }
+
+    /// Block until we can write without getting `EAGAIN`
+    fn wait_writable(&self) -> io::Result<()> {
+        let mut fd = libc::pollfd {
+            fd: self.fd,
+            events: libc::POLLOUT,
+            revents: 0,
+        };
+        loop {
+            if unsafe { libc::poll(&mut fd, 1, -1) } != -1 {
+                return Ok(());
+            }
+            let error = io::Error::last_os_error();
+            if error.kind() != io::ErrorKind::Interrupted {
+                return Err(error);
+            }
+        }
+    }
+}
 
 impl io::Write for UnsafeStdout {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        let bytes_written =
-            unsafe { libc::write(STDIN_FILENO, buf.as_ptr() as *const c_void, buf.len()) };
-
-        if bytes_written == -1 {
-            Err(io::Error::last_os_error())
-        } else {
-            Ok(bytes_written as usize)
+        loop {
+            let bytes_written =
+                unsafe { libc::write(self.fd, buf.as_ptr() as *const c_void, buf.len()) };
+            if bytes_written != -1 {
+                return Ok(bytes_written as usize);
+            }
+            let error = io::Error::last_os_error();
+            match error.kind() {
+                io::ErrorKind::Interrupted => continue,
+                io::ErrorKind::WouldBlock => self.wait_writable()?,
+                _ => return Err(error),
+            }
         }
     }
 
@@ -25,39 +88,1008 @@ impl io::Write for UnsafeStdout {
     }
 }
 
+/// How to tell the terminal to hold off drawing until we've finished a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SynchronizedOutput {
+    /// DEC private mode 2026 (`?2026h`/`?2026l`), kitty, WezTerm, foot, iTerm2, ...
+    Mode2026,
+    /// The older `DCS = 1 s`/`DCS = 2 s` version
+    Legacy,
+}
+
+/// The private mode for synchronized output
+const SYNCHRONIZED_OUTPUT_MODE: u32 = 2026;
+
+/// Identifies one hyperlink, so the pieces of it (on different lines, say) highlight together.
+/// These are unique within the process, see `TsStdout::begin_hyperlink`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HyperlinkId(u32);
+
+/// Where the next `HyperlinkId` comes from
+static NEXT_HYPERLINK_ID: AtomicU32 = AtomicU32::new(0);
+
+impl HyperlinkId {
+    pub fn new() -> Self {
+        Self(NEXT_HYPERLINK_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// The `id=` we send, which has the pid in it so other programs' links don't get mixed up
+    /// with ours
+    fn format(self, buf: &mut [u8; 24]) -> &str {
+        let mut rest = &mut buf[..];
+        // `ts`, two `u32`s and a `-` always fit
+        let _ = write!(rest, "ts{}-{}", std::process::id(), self.0);
+        let len = 24 - rest.len();
+        std::str::from_utf8(&buf[..len]).unwrap_or_default()
+    }
+}
+
+impl Default for HyperlinkId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// What we use for the terminal size if we can't ask it
+const DEFAULT_SIZE: (u32, u32) = (24, 80);
+
+/// Where we think the terminal's cursor is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cursor {
+    row: u32,
+    col: u32,
+    /// we've written to the last column, and the next character goes on the next line
+    /// (xterm's "pending wrap" state). `col` is the last column until then.
+    pending_wrap: bool,
+}
+
+impl Cursor {
+    const fn at(row: u32, col: u32) -> Self {
+        Self {
+            row,
+            col,
+            pending_wrap: false,
+        }
+    }
+}
+
+/// `reader.next_timeout`, but with a deadline for a whole exchange, so a stream of other input
+/// can't keep us waiting for a reply forever
+fn next_before(reader: &mut TokenReader, deadline: Instant) -> io::Result<Option<Token<'_>>> {
+    reader.next_timeout(deadline.saturating_duration_since(Instant::now()))
+}
+
+/// Whether the terminal on `fd` (or stdout, or stdin, if `None`) turns `\n` into `\r\n` on
+/// the way out (`OPOST` and `ONLCR`)
+fn translates_newlines(fd: Option<RawFd>) -> bool {
+    let mut termios = unsafe { std::mem::zeroed::<libc::termios>() };
+    let mut tcgetattr = |fd| unsafe { libc::tcgetattr(fd, &mut termios) } == 0;
+    let ok = match fd {
+        Some(fd) => tcgetattr(fd),
+        None => tcgetattr(STDOUT_FILENO) || tcgetattr(STDIN_FILENO),
+    };
+    // not a terminal at all, so assume the usual cooked settings
+    !ok || termios.c_oflag & (libc::OPOST | libc::ONLCR) == libc::OPOST | libc::ONLCR
+}
+
 /// Wrapper around Stdout -- uses ```UnsafeStdout``` internally, so is buffered thread-locally.
 /// All commands do not return io errors, as these are basically always ignored and just look
-/// gross. when you have to `let _ =` or `.unwrap()`
-pub struct TsStdout {
-    buf_writer: io::BufWriter<UnsafeStdout>,
+/// gross. when you have to `let _ =` or `.unwrap()`. The first one is kept though, see
+/// `take_error`, and there are `try_*` versions of the basic methods that do return them.
+///
+/// Escape sequences come from the terminfo entry for `$TERM` where there is a capability for
+/// them, see `set_terminfo`. Colors are brought down to what the terminal can show, see
+/// `set_color_depth`.
+///
+/// It also keeps track of where the cursor is from what's written (see `cursor_position`),
+/// for as long as it only sees text and escape sequences it understands.
+///
+/// Output goes to the terminal by default, but it can go to any `io::Write` (see
+/// `with_writer`), e.g. a `Vec<u8>` to check what a screen draws against a `transcript`.
+pub struct TsStdout<W: io::Write = UnsafeStdout> {
+    buf_writer: io::BufWriter<W>,
+    /// the first error a method that doesn't return them ran into, see `take_error`
+    error: Option<io::Error>,
+    /// the style we last told the terminal to use, so `set_style` only has to send the diff
+    style: Style,
+    /// where we get escape sequences from, if we know what terminal we're talking to
+    terminfo: Option<Arc<TermInfo>>,
+    /// colors are mapped to the closest one the terminal can show before they're sent
+    color_depth: ColorDepth,
+    /// how `frame` stops the terminal drawing half a frame, if it can
+    synchronized_output: Option<SynchronizedOutput>,
+    /// how `notify` gets the terminal to show a notification
+    notification_protocol: NotificationProtocol,
+    /// whether we're in tmux, and kitty graphics need wrapping up to get through it
+    tmux_passthrough: bool,
+    /// where the cursor is, `None` if we've lost track
+    cursor: Option<Cursor>,
+    /// the hyperlink we're in the middle of, see `begin_hyperlink`
+    hyperlink: Option<HyperlinkId>,
+    /// what `SaveCursorPosition` (or `EnterAlternateScreen`) saved
+    saved_cursor: Option<Cursor>,
+    /// what `ScoSaveCursorPosition` saved
+    sco_saved_cursor: Option<Cursor>,
+    /// `(rows, cols)`
+    size: (u32, u32),
+    /// top and bottom rows of the scroll region (inclusive)
+    scroll_region: (u32, u32),
+    /// where `\t` goes, `None` if we've lost track (we set or cleared one without knowing
+    /// which column the cursor was in)
+    tab_stops: Option<TabStops>,
+    /// whether we've set `tab_stops` ourselves, rather than assuming the usual ones
+    tab_stops_known: bool,
+    /// `settings_generation()` when we last checked whether `\n` means `\r\n`, and what we
+    /// found
+    newline_mode: (usize, bool),
+    /// what `set_newline_translation` said, which we use instead of checking
+    newline_translation: Option<bool>,
+    /// the terminal whose settings we check, from `from_fd`
+    fd: Option<RawFd>,
+}
+
+impl Default for TsStdout {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TsStdout {
+    /// Write to stdout
     pub fn new() -> Self {
+        Self::from_fd(STDOUT_FILENO)
+    }
+
+    /// Write to `fd` instead of stdout, see `UnsafeStdout::from_fd`
+    pub fn from_fd(fd: RawFd) -> Self {
+        let terminfo = TermInfo::current();
+        let mut stdout = Self::with_writer(UnsafeStdout::from_fd(fd));
+        match window_size_of(fd) {
+            // a pty nobody has set the size of says 0x0, which is no use to anyone
+            Ok(size) if size.rows > 0 && size.cols > 0 => {
+                stdout.set_size(size.rows as u32, size.cols as u32);
+            }
+            _ => (),
+        }
+        stdout.color_depth = ColorDepth::detect_for(fd, terminfo.as_deref());
+        stdout.terminfo = terminfo;
+        stdout.notification_protocol = NotificationProtocol::detect();
+        stdout.tmux_passthrough = std::env::var_os("TMUX").is_some_and(|tmux| !tmux.is_empty());
+        stdout.newline_translation = None;
+        stdout.fd = Some(fd);
+        stdout
+    }
+}
+
+impl<W: io::Write> TsStdout<W> {
+    /// Write to `writer`. Nothing is detected, since `writer` might not be a terminal at all:
+    /// we use the built-in escape sequences with true color, a 24x80 screen where `\n` does a
+    /// `\r` too, and no notifications, so the same calls always write the same bytes. Set
+    /// anything else you need with the `set_*` methods.
+    ///
+    /// ```rs
+    /// let mut stdout = TsStdout::with_writer(Vec::new());
+    /// stdout.exec([&StdoutEscSeq::MoveTo(0, 0)]).write_str("hi");
+    /// let bytes = stdout.into_writer()?;
+    /// ```
+    pub fn with_writer(writer: W) -> Self {
         Self {
-            buf_writer: io::BufWriter::new(UnsafeStdout),
+            buf_writer: io::BufWriter::new(writer),
+            error: None,
+            style: Style::new(),
+            color_depth: ColorDepth::TrueColor,
+            terminfo: None,
+            synchronized_output: None,
+            notification_protocol: NotificationProtocol::Bell,
+            tmux_passthrough: false,
+            cursor: None,
+            hyperlink: None,
+            saved_cursor: None,
+            sco_saved_cursor: None,
+            size: DEFAULT_SIZE,
+            scroll_region: (0, DEFAULT_SIZE.0 - 1),
+            tab_stops: Some(TabStops::new(DEFAULT_SIZE.1)),
+            tab_stops_known: false,
+            newline_mode: (usize::MAX, true),
+            newline_translation: Some(true),
+            fd: None,
+        }
+    }
+
+    pub fn writer(&self) -> &W {
+        self.buf_writer.get_ref()
+    }
+
+    /// Writing to this directly skips the buffer, so `flush` first
+    pub fn writer_mut(&mut self) -> &mut W {
+        self.buf_writer.get_mut()
+    }
+
+    /// Flush, and hand back the writer
+    pub fn into_writer(mut self) -> io::Result<W> {
+        self.try_flush()?;
+        Ok(self.buf_writer.into_parts().0)
+    }
+
+    /// Say whether the terminal turns `\n` into `\r\n` (`OPOST` and `ONLCR`), for
+    /// `cursor_position`, or `None` to check the terminal's settings. Terminals check by
+    /// default, and anything made with `with_writer` says yes.
+    pub fn set_newline_translation(&mut self, newline_translation: Option<bool>) -> &mut Self {
+        self.newline_translation = newline_translation;
+        self
+    }
+
+    /// Use `terminfo` to pick escape sequences, or the built-in xterm-style ones if `None`.
+    /// By default we use the entry for `$TERM` if there is one.
+    pub fn set_terminfo(&mut self, terminfo: Option<Arc<TermInfo>>) -> &mut Self {
+        self.terminfo = terminfo;
+        self
+    }
+
+    pub fn terminfo(&self) -> Option<&TermInfo> {
+        self.terminfo.as_deref()
+    }
+
+    /// Override the detected color depth, e.g. for a `--color=always` flag. Styles set after
+    /// this are downsampled to `depth`.
+    pub fn set_color_depth(&mut self, depth: ColorDepth) -> &mut Self {
+        self.color_depth = depth;
+        self
+    }
+
+    /// What we think the terminal can show, see `ColorDepth::detect`
+    pub fn color_depth(&self) -> ColorDepth {
+        self.color_depth
+    }
+
+    /// Ask the terminal whether it supports synchronized output (mode 2026) with DECRQM, and
+    /// use it for `frame` if it does. We wait up to `timeout` in all for the reply, so call
+    /// this before reading any input: anything else that comes in while we wait is thrown
+    /// away. Like `query_color`, we ask where the cursor is straight after, so terminals that
+    /// don't answer DECRQM don't keep us waiting.
+    pub fn detect_synchronized_output(
+        &mut self,
+        reader: &mut TokenReader,
+        timeout: Duration,
+    ) -> io::Result<Option<SynchronizedOutput>> {
+        self.try_exec([
+            &StdoutEscSeq::RequestPrivateMode(SYNCHRONIZED_OUTPUT_MODE),
+            &StdoutEscSeq::RequestCursorPosition,
+        ])?;
+        self.try_flush()?;
+        let deadline = Instant::now() + timeout;
+        let mut supported = false;
+        loop {
+            match next_before(reader, deadline)? {
+                Some(Token::ModeReport(SYNCHRONIZED_OUTPUT_MODE, state)) => {
+                    supported = state.is_supported();
+                }
+                Some(Token::CursorPosition(..)) | None => break,
+                Some(_) => continue,
+            }
+        }
+        self.synchronized_output = supported.then_some(SynchronizedOutput::Mode2026);
+        Ok(self.synchronized_output)
+    }
+
+    /// Ask the terminal what one of its colors is set to (OSC 4, 10, 11 or 12), waiting up to
+    /// `timeout` in all for the reply. Like `detect_synchronized_output`, anything else that comes in
+    /// meanwhile is thrown away. Returns `None` if the terminal didn't say.
+    ///
+    /// Lots of terminals don't answer these at all, so we ask where the cursor is straight
+    /// after, which everything answers: if that reply comes back first, we know not to wait.
+    pub fn query_color(
+        &mut self,
+        color: TerminalColor,
+        reader: &mut TokenReader,
+        timeout: Duration,
+    ) -> io::Result<Option<(u8, u8, u8)>> {
+        self.try_exec([
+            &StdoutEscSeq::RequestColor(color),
+            &StdoutEscSeq::RequestCursorPosition,
+        ])?;
+        self.try_flush()?;
+        let deadline = Instant::now() + timeout;
+        let mut reported = None;
+        loop {
+            match next_before(reader, deadline)? {
+                Some(Token::ColorReport(reported_color, rgb)) if reported_color == color => {
+                    reported = Some(rgb);
+                }
+                Some(Token::CursorPosition(..)) | None => return Ok(reported),
+                Some(_) => continue,
+            }
+        }
+    }
+
+    /// Work out whether the terminal's background is light or dark. We ask for the background
+    /// color (see `query_color`), and if the terminal doesn't say, go by `COLORFGBG`. If that
+    /// isn't set either, we guess `Dark`.
+    pub fn detect_color_scheme(
+        &mut self,
+        reader: &mut TokenReader,
+        timeout: Duration,
+    ) -> io::Result<ColorScheme> {
+        let background = self.query_color(TerminalColor::Background, reader, timeout)?;
+        Ok(match background {
+            Some(rgb) => ColorScheme::from_background(rgb),
+            None => ColorScheme::from_env().unwrap_or_default(),
+        })
+    }
+
+    /// Say how (or whether) the terminal supports synchronized output, instead of detecting
+    /// it. `Legacy` can't be detected, so this is the only way to use it.
+    pub fn set_synchronized_output(
+        &mut self,
+        synchronized_output: Option<SynchronizedOutput>,
+    ) -> &mut Self {
+        self.synchronized_output = synchronized_output;
+        self
+    }
+
+    pub fn synchronized_output(&self) -> Option<SynchronizedOutput> {
+        self.synchronized_output
+    }
+
+    /// Override the detected notification protocol, see `NotificationProtocol::detect`
+    pub fn set_notification_protocol(&mut self, protocol: NotificationProtocol) -> &mut Self {
+        self.notification_protocol = protocol;
+        self
+    }
+
+    pub fn notification_protocol(&self) -> NotificationProtocol {
+        self.notification_protocol
+    }
+
+    /// Pop up a desktop notification, using whatever the terminal supports (see
+    /// `set_notification_protocol`). `urgency` only makes a difference on kitty. On terminals
+    /// that can't do notifications this just rings the bell, which is also what happens on
+    /// some terminals that can when the window has focus.
+    ///
+    /// ```rs
+    /// stdout.notify("build finished", "3 warnings", Urgency::Normal).flush();
+    /// ```
+    pub fn notify(&mut self, title: &str, body: &str, urgency: Urgency) -> &mut Self {
+        let seq = match self.notification_protocol {
+            NotificationProtocol::Osc9 => StdoutEscSeq::Notify(title, body),
+            NotificationProtocol::Osc777 => StdoutEscSeq::NotifyOsc777(title, body),
+            NotificationProtocol::Kitty => {
+                StdoutEscSeq::NotifyKitty(next_notification_id(), title, body, urgency)
+            }
+            NotificationProtocol::Bell => StdoutEscSeq::Bell,
+        };
+        self.exec([&seq])
+    }
+
+    /// Get the user's attention without a notification: ring the bell with xterm's urgent
+    /// bell mode on, so the window manager flags the window up too. The mode is put back
+    /// after.
+    pub fn request_attention(&mut self) -> &mut Self {
+        self.exec([
+            &StdoutEscSeq::EnableUrgentBell,
+            &StdoutEscSeq::Bell,
+            &StdoutEscSeq::DisableUrgentBell,
+        ])
+    }
+
+    /// Draw `image` as sixel graphics at the cursor. Where the cursor ends up afterwards
+    /// depends on the terminal's settings, so we stop tracking it (see `cursor_position`).
+    pub fn draw_sixel(&mut self, encoder: &SixelEncoder, image: &Image) -> &mut Self {
+        let result = encoder.write_to(image, &mut self.buf_writer);
+        self.cursor = None;
+        self.keep_error(result)
+    }
+
+    /// Whether to wrap kitty graphics commands up for tmux to pass on to the terminal it's
+    /// running in (which needs `set -g allow-passthrough on`). This is on if `$TMUX` is set.
+    pub fn set_tmux_passthrough(&mut self, tmux_passthrough: bool) -> &mut Self {
+        self.tmux_passthrough = tmux_passthrough;
+        self
+    }
+
+    pub fn tmux_passthrough(&self) -> bool {
+        self.tmux_passthrough
+    }
+
+    /// Send `image` to the terminal with the kitty graphics protocol, to keep as `id` (which
+    /// replaces any image it already has with that id). It isn't shown until you place it, see
+    /// `kitty_place` and `draw_kitty_placeholders`.
+    pub fn kitty_transmit(&mut self, id: u32, image: &Image) -> &mut Self {
+        self.exec([&StdoutEscSeq::Kitty(KittyCommand::Transmit {
+            id,
+            format: KittyFormat::Rgba,
+            width: image.width(),
+            height: image.height(),
+            data: image.pixels(),
+        })])
+    }
+
+    /// Show an image that's been sent at the cursor
+    pub fn kitty_place(&mut self, placement: KittyPlacement) -> &mut Self {
+        self.exec([&StdoutEscSeq::Kitty(KittyCommand::Place(placement))])
+    }
+
+    /// Show an image with its top left at `(row, col)`, leaving the cursor there. If
+    /// `placement` has a placement id and is already on the screen, this moves it.
+    pub fn kitty_move(&mut self, placement: KittyPlacement, row: u32, col: u32) -> &mut Self {
+        let placement = KittyPlacement {
+            keep_cursor: true,
+            ..placement
+        };
+        self.exec([&StdoutEscSeq::MoveTo(row, col)])
+            .kitty_place(placement)
+    }
+
+    /// Take every placement of image `id` off the screen, and forget the image too if `free`
+    pub fn kitty_delete(&mut self, id: u32, free: bool) -> &mut Self {
+        self.exec([&StdoutEscSeq::Kitty(KittyCommand::Delete {
+            id,
+            placement_id: None,
+            free,
+        })])
+    }
+
+    /// Show image `image_id` over the `rows` x `cols` cells starting at `(row, col)` with
+    /// Unicode placeholders: text the terminal swaps for pieces of the image. Since they're
+    /// just text, they get scrolled, cleared and moved around (by tmux, say) like text. The
+    /// image needs a virtual placement first (see `KittyPlacement::virtual_placement`), which
+    /// says how many rows and columns to scale it to.
+    ///
+    /// The image id goes in the foreground color, so this leaves the foreground set to that.
+    /// It can't be more than 297 rows or columns.
+    ///
+    /// ```rs
+    /// let placement = KittyPlacement {
+    ///     rows: 5,
+    ///     cols: 10,
+    ///     virtual_placement: true,
+    ///     ..KittyPlacement::new(7)
+    /// };
+    /// stdout
+    ///     .kitty_transmit(7, &image)
+    ///     .kitty_place(placement)
+    ///     .draw_kitty_placeholders(7, 2, 4, 5, 10);
+    /// ```
+    pub fn draw_kitty_placeholders(
+        &mut self,
+        image_id: u32,
+        row: u32,
+        col: u32,
+        rows: u32,
+        cols: u32,
+    ) -> &mut Self {
+        let [top, r, g, b] = image_id.to_be_bytes();
+        // this has to get there exactly, so it skips `set_style`'s downsampling
+        let style = Style {
+            fg: Some(Color::Rgb(r, g, b)),
+            ..self.style
+        };
+        let result = self.style.write_diff_to(&style, &mut self.buf_writer);
+        self.keep_error(result).style = style;
+
+        let rows = rows.min(ROW_COLUMN_DIACRITICS.len() as u32);
+        let cols = cols.min(ROW_COLUMN_DIACRITICS.len() as u32);
+        let mut line = String::new();
+        for i in 0..rows {
+            // the first cell says which row and column it is (and the top byte of the id),
+            // and the rest follow on from the cell to their left
+            line.clear();
+            line.push(KITTY_PLACEHOLDER);
+            line.push(ROW_COLUMN_DIACRITICS[i as usize]);
+            line.push(ROW_COLUMN_DIACRITICS[0]);
+            if top != 0 {
+                line.push(ROW_COLUMN_DIACRITICS[top as usize]);
+            }
+            for _ in 1..cols {
+                line.push(KITTY_PLACEHOLDER);
+            }
+            self.exec([&StdoutEscSeq::MoveTo(row + i, col)])
+                .write_str(&line);
         }
+        self
+    }
+
+    /// Start a hyperlink to `uri` with a new `HyperlinkId`: everything written until
+    /// `end_hyperlink` is clickable, on terminals that support OSC 8. If you have to break the
+    /// link up (to draw something else in between, or because it goes over several lines),
+    /// carry on with `resume_hyperlink` and `hyperlink_id` so the pieces highlight together.
+    ///
+    /// ```rs
+    /// stdout
+    ///     .begin_hyperlink("file:///var/log/syslog")
+    ///     .write_str("syslog")
+    ///     .end_hyperlink();
+    /// ```
+    pub fn begin_hyperlink(&mut self, uri: &str) -> &mut Self {
+        self.resume_hyperlink(HyperlinkId::new(), uri)
+    }
+
+    /// Start (or carry on) the hyperlink `id` to `uri`
+    pub fn resume_hyperlink(&mut self, id: HyperlinkId, uri: &str) -> &mut Self {
+        let mut buf = [0; 24];
+        let id_str = id.format(&mut buf);
+        self.exec([&StdoutEscSeq::BeginHyperlink(Some(id_str), uri)]);
+        self.hyperlink = Some(id);
+        self
+    }
+
+    pub fn end_hyperlink(&mut self) -> &mut Self {
+        self.exec([&StdoutEscSeq::EndHyperlink])
+    }
+
+    /// The hyperlink we're in the middle of, if any
+    pub fn hyperlink_id(&self) -> Option<HyperlinkId> {
+        self.hyperlink
+    }
+
+    /// Write `text` as a link to `uri`
+    pub fn write_hyperlink(&mut self, uri: &str, text: &str) -> &mut Self {
+        self.begin_hyperlink(uri).write_str(text).end_hyperlink()
+    }
+
+    /// Tell us the terminal changed size (e.g. on `Event::Resize`), which resets the scroll
+    /// region. Terminals don't agree on where the cursor ends up after a resize, so we forget
+    /// where it is until the next `MoveTo` or `sync_cursor_position`.
+    pub fn set_size(&mut self, rows: u32, cols: u32) -> &mut Self {
+        self.size = (rows.max(1), cols.max(1));
+        self.scroll_region = (0, self.size.0 - 1);
+        self.cursor = None;
+        if let Some(tab_stops) = &mut self.tab_stops {
+            tab_stops.resize(self.size.1);
+        }
+        self
+    }
+
+    /// `(rows, cols)`, from when we were created or the last `set_size`
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Where the cursor is as `(row, col)` from `(0, 0)`, worked out from everything written
+    /// since we last knew. `None` until something puts it somewhere we know (`MoveTo`,
+    /// `sync_cursor_position`, ...), or after something we can't follow (e.g. an escape
+    /// sequence in `write_str`, or a resize).
+    pub fn cursor_position(&self) -> Option<(u32, u32)> {
+        self.cursor.map(|cursor| (cursor.row, cursor.col))
+    }
+
+    /// Whether the last column has been written to and the next character will wrap onto the
+    /// next line, in which case `cursor_position` still says the last column
+    pub fn pending_wrap(&self) -> bool {
+        self.cursor.is_some_and(|cursor| cursor.pending_wrap)
+    }
+
+    /// Where we think the terminal's tab stops are, for following `\t` and `TabForward` etc.
+    /// We start out assuming the usual ones every 8 columns, since there's no way to ask.
+    /// `None` if a `SetTabStop` or `ClearTabStop` went somewhere we couldn't follow.
+    pub fn tab_stops(&self) -> Option<&TabStops> {
+        self.tab_stops.as_ref()
+    }
+
+    /// `tab_stops`, but only once they've been cleared (`ClearAllTabStops`, or
+    /// `set_tab_stops`) and we aren't just assuming the usual ones, which the user might have
+    /// changed with `tabs` before we started. Worth checking before writing `\t` to move the
+    /// cursor.
+    pub fn known_tab_stops(&self) -> Option<&TabStops> {
+        self.tab_stops.as_ref().filter(|_| self.tab_stops_known)
+    }
+
+    /// Set the terminal's tab stops to exactly `stops` (the ones that fit on the screen),
+    /// leaving the cursor where it was. Getting the cursor back uses `SaveCursorPosition`, so
+    /// this overwrites whatever that saved. The terminal keeps them after we exit, see
+    /// `Termset::set_tab_stops` to put them back.
+    ///
+    /// ```rs
+    /// // columns for a table, so each cell is just `\t` and its text
+    /// stdout.set_tab_stops(&TabStops::with_stops(cols, [0, 24, 40]));
+    /// stdout.write_str("name\tsize\tmodified\r\n");
+    /// ```
+    pub fn set_tab_stops(&mut self, stops: &TabStops) -> &mut Self {
+        let cols = self.size.1;
+        self.exec([
+            &StdoutEscSeq::SaveCursorPosition,
+            &StdoutEscSeq::ClearAllTabStops,
+        ]);
+        for col in stops.iter().take_while(|&col| col < cols) {
+            self.exec([&StdoutEscSeq::MoveToColumn(col), &StdoutEscSeq::SetTabStop]);
+        }
+        self.exec([&StdoutEscSeq::RestorCursorPosition]);
+        let mut stops = stops.clone();
+        stops.resize(cols);
+        self.tab_stops = Some(stops);
+        self
+    }
+
+    /// Ask the terminal where the cursor really is (DSR 6) and start tracking from there. We
+    /// wait up to `timeout` for the reply, and anything else that comes in meanwhile is thrown
+    /// away, same as `detect_synchronized_output`. Returns `None` if the terminal didn't say.
+    pub fn sync_cursor_position(
+        &mut self,
+        reader: &mut TokenReader,
+        timeout: Duration,
+    ) -> io::Result<Option<(u32, u32)>> {
+        self.try_exec([&StdoutEscSeq::RequestCursorPosition])?;
+        self.try_flush()?;
+        let deadline = Instant::now() + timeout;
+        loop {
+            match next_before(reader, deadline)? {
+                Some(Token::CursorPosition(row, col)) => {
+                    // some terminals say the column after the last one when they're waiting to
+                    // wrap
+                    let (rows, cols) = self.size;
+                    let cursor = Cursor {
+                        row: row.min(rows - 1),
+                        col: col.min(cols - 1),
+                        pending_wrap: col >= cols,
+                    };
+                    self.cursor = Some(cursor);
+                    return Ok(Some((cursor.row, cursor.col)));
+                }
+                Some(_) => continue,
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Start a frame: everything written through the returned guard is shown all at once
+    /// when it's dropped (which also flushes). Without synchronized output (see
+    /// `detect_synchronized_output`) this just flushes at the end.
+    ///
+    /// ```rs
+    /// let mut frame = stdout.frame();
+    /// frame.exec([&StdoutEscSeq::MoveTo(0, 0)]).write_str("all at once");
+    /// ```
+    pub fn frame(&mut self) -> Frame<'_, W> {
+        match self.synchronized_output {
+            Some(SynchronizedOutput::Mode2026) => {
+                self.exec([&StdoutEscSeq::BeginSynchronizedUpdate]);
+            }
+            Some(SynchronizedOutput::Legacy) => {
+                self.exec([&StdoutEscSeq::BeginSynchronizedUpdateLegacy]);
+            }
+            None => (),
+        }
+        Frame { stdout: self }
     }
 
     /// execute a serious of commands (escape sequences) in order
-    pub fn exec<'a, T>(&mut self, commands: T) -> &mut Self
+    pub fn exec<'a, 'b: 'a, T>(&mut self, commands: T) -> &mut Self
+    where
+        T: IntoIterator<Item = &'a StdoutEscSeq<'b>>,
+    {
+        let result = self.try_exec(commands);
+        self.keep_error(result)
+    }
+
+    /// `exec`, but stopping at the first error and returning it
+    pub fn try_exec<'a, 'b: 'a, T>(&mut self, commands: T) -> io::Result<()>
     where
-        T: IntoIterator<Item = &'a StdoutEscSeq>,
+        T: IntoIterator<Item = &'a StdoutEscSeq<'b>>,
     {
         for command in commands.into_iter() {
-            let _ = self.buf_writer.write_all(&command.as_bytes());
+            // colors go through `set_style` so they get downsampled
+            let style = match *command {
+                StdoutEscSeq::SetForeground(color) => Style {
+                    fg: Some(color),
+                    ..self.style
+                },
+                StdoutEscSeq::SetBackground(color) => Style {
+                    bg: Some(color),
+                    ..self.style
+                },
+                StdoutEscSeq::SetUnderlineColor(color) => Style {
+                    underline_color: Some(color),
+                    ..self.style
+                },
+                // the whole thing, reset first, so it also undoes anything that changed the
+                // style behind our back (use `set_style` to just send the diff)
+                StdoutEscSeq::SetStyle(style) => {
+                    let style = style.downsample(self.color_depth);
+                    style.write_to(&mut self.buf_writer)?;
+                    self.style = style;
+                    continue;
+                }
+                _ => {
+                    match (command, &self.terminfo) {
+                        (StdoutEscSeq::Kitty(kitty), _) => {
+                            kitty.write_to(&mut self.buf_writer, self.tmux_passthrough)
+                        }
+                        (_, Some(terminfo)) => command.write_to_for(terminfo, &mut self.buf_writer),
+                        (_, None) => command.write_to(&mut self.buf_writer),
+                    }?;
+                    self.track_seq(command);
+                    match command {
+                        StdoutEscSeq::ResetStyle => self.style = Style::new(),
+                        // `resume_hyperlink` sets this again after
+                        StdoutEscSeq::BeginHyperlink(..) | StdoutEscSeq::EndHyperlink => {
+                            self.hyperlink = None;
+                        }
+                        _ => (),
+                    }
+                    continue;
+                }
+            };
+            self.try_set_style(&style)?;
         }
-        self
+        Ok(())
+    }
+
+    /// Switch to `style`, only sending the SGR parameters that actually changed since the
+    /// last style set through this handle. Colors the terminal can't show are swapped for the
+    /// closest one it can.
+    pub fn set_style(&mut self, style: &Style) -> &mut Self {
+        let result = self.try_set_style(style);
+        self.keep_error(result)
+    }
+
+    pub fn try_set_style(&mut self, style: &Style) -> io::Result<()> {
+        let style = style.downsample(self.color_depth);
+        self.style.write_diff_to(&style, &mut self.buf_writer)?;
+        self.style = style;
+        Ok(())
+    }
+
+    /// Go back to the default style
+    pub fn reset_style(&mut self) -> &mut Self {
+        self.set_style(&Style::new())
+    }
+
+    /// The style we think the terminal is currently using
+    pub fn style(&self) -> &Style {
+        &self.style
     }
 
     /// write a string to stdout
     pub fn write_str(&mut self, s: &str) -> &mut Self {
-        let _ = self.buf_writer.write_all(s.as_bytes());
-        self
+        let result = self.try_write_str(s);
+        self.keep_error(result)
+    }
+
+    pub fn try_write_str(&mut self, s: &str) -> io::Result<()> {
+        self.buf_writer.write_all(s.as_bytes())?;
+        self.track_text(s);
+        Ok(())
     }
 
     pub fn flush(&mut self) -> &mut Self {
-        let _ = self.buf_writer.flush();
+        let result = self.try_flush();
+        self.keep_error(result)
+    }
+
+    pub fn try_flush(&mut self) -> io::Result<()> {
+        self.buf_writer.flush()
+    }
+
+    /// The first error since the last `take_error` from a method that doesn't return them.
+    /// Once something's failed, what the terminal shows can't be trusted, so this is worth
+    /// checking after a frame.
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Get the error `error` would return, and forget it
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Hang on to `result`'s error if it's the first one
+    fn keep_error(&mut self, result: io::Result<()>) -> &mut Self {
+        if let Err(error) = result {
+            self.error.get_or_insert(error);
+        }
         self
-    }    
+    }
+
+    fn newline_does_carriage_return(&mut self) -> bool {
+        if let Some(newline_translation) = self.newline_translation {
+            return newline_translation;
+        }
+        let generation = settings_generation();
+        if self.newline_mode.0 != generation {
+            self.newline_mode = (generation, translates_newlines(self.fd));
+        }
+        self.newline_mode.1
+    }
+
+    /// Move down a line, scrolling if we're at the bottom of the scroll region (or the screen)
+    fn line_feed(&self, cursor: &mut Cursor) {
+        if cursor.row != self.scroll_region.1 && cursor.row + 1 < self.size.0 {
+            cursor.row += 1;
+        }
+        cursor.pending_wrap = false;
+    }
+
+    /// Follow the cursor through some text, like the terminal would
+    fn track_text(&mut self, s: &str) {
+        let Some(mut cursor) = self.cursor else {
+            return;
+        };
+        let cols = self.size.1;
+        for grapheme in graphemes(s) {
+            match grapheme {
+                "\r" => cursor = Cursor::at(cursor.row, 0),
+                "\r\n" => {
+                    self.line_feed(&mut cursor);
+                    cursor.col = 0;
+                }
+                // VT and FF are line feeds too
+                "\n" | "\x0b" | "\x0c" => {
+                    self.line_feed(&mut cursor);
+                    if self.newline_does_carriage_return() {
+                        cursor.col = 0;
+                    }
+                }
+                "\t" => {
+                    let Some(tab_stops) = &self.tab_stops else {
+                        self.cursor = None;
+                        return;
+                    };
+                    cursor = Cursor::at(cursor.row, tab_stops.next(cursor.col, 1));
+                }
+                "\x08" => cursor = Cursor::at(cursor.row, cursor.col.saturating_sub(1)),
+                // an escape sequence we'd have to parse to follow, so give up
+                "\x1b" => {
+                    self.cursor = None;
+                    return;
+                }
+                grapheme => {
+                    let width = grapheme_width(grapheme) as u32;
+                    if width == 0 {
+                        continue;
+                    }
+                    // wrap first if we're waiting to, or a wide character doesn't fit
+                    if cursor.pending_wrap || cursor.col + width > cols {
+                        self.line_feed(&mut cursor);
+                        cursor.col = 0;
+                    }
+                    cursor.col += width;
+                    if cursor.col >= cols {
+                        cursor.col = cols - 1;
+                        cursor.pending_wrap = true;
+                    }
+                }
+            }
+        }
+        self.cursor = Some(cursor);
+    }
+
+    /// Follow the cursor through an escape sequence we just sent
+    fn track_seq(&mut self, seq: &StdoutEscSeq) {
+        let (rows, cols) = self.size;
+        let (top, bottom) = self.scroll_region;
+        // moving up or down stops at the edge of the scroll region, if we start inside it
+        let up = |cursor: Cursor, n: u32| {
+            let top = if cursor.row >= top { top } else { 0 };
+            cursor.row.saturating_sub(n.max(1)).max(top)
+        };
+        let down = |cursor: Cursor, n: u32| {
+            let bottom = if cursor.row <= bottom {
+                bottom
+            } else {
+                rows - 1
+            };
+            cursor.row.saturating_add(n.max(1)).min(bottom)
+        };
+        let cursor = self.cursor;
+        self.cursor = match *seq {
+            StdoutEscSeq::MoveTo(row, col) => {
+                Some(Cursor::at(row.min(rows - 1), col.min(cols - 1)))
+            }
+            StdoutEscSeq::MoveToColumn(col) => cursor.map(|c| Cursor::at(c.row, col.min(cols - 1))),
+            StdoutEscSeq::MoveToRow(row) => cursor.map(|c| Cursor::at(row.min(rows - 1), c.col)),
+            StdoutEscSeq::MoveUp(n) => cursor.map(|c| Cursor::at(up(c, n), c.col)),
+            StdoutEscSeq::MoveDown(n) => cursor.map(|c| Cursor::at(down(c, n), c.col)),
+            StdoutEscSeq::MoveToPreviousLine(n) => cursor.map(|c| Cursor::at(up(c, n), 0)),
+            StdoutEscSeq::MoveToNextLine(n) => cursor.map(|c| Cursor::at(down(c, n), 0)),
+            StdoutEscSeq::MoveLeft(n) => {
+                cursor.map(|c| Cursor::at(c.row, c.col.saturating_sub(n.max(1))))
+            }
+            StdoutEscSeq::MoveRight(n) => {
+                cursor.map(|c| Cursor::at(c.row, c.col.saturating_add(n.max(1)).min(cols - 1)))
+            }
+            StdoutEscSeq::TabForward(n) => match (cursor, &self.tab_stops) {
+                (Some(c), Some(tab_stops)) => {
+                    Some(Cursor::at(c.row, tab_stops.next(c.col, n.max(1))))
+                }
+                _ => None,
+            },
+            StdoutEscSeq::TabBackward(n) => match (cursor, &self.tab_stops) {
+                (Some(c), Some(tab_stops)) => {
+                    Some(Cursor::at(c.row, tab_stops.previous(c.col, n.max(1))))
+                }
+                _ => None,
+            },
+            StdoutEscSeq::SetTabStop | StdoutEscSeq::ClearTabStop => {
+                match (cursor, &mut self.tab_stops) {
+                    (Some(c), Some(tab_stops)) if *seq == StdoutEscSeq::SetTabStop => {
+                        tab_stops.set(c.col);
+                    }
+                    (Some(c), Some(tab_stops)) => {
+                        tab_stops.clear(c.col);
+                    }
+                    // we don't know which column it was
+                    _ => self.tab_stops = None,
+                }
+                cursor
+            }
+            StdoutEscSeq::ClearAllTabStops => {
+                self.tab_stops = Some(TabStops::with_stops(cols, []));
+                self.tab_stops_known = true;
+                cursor
+            }
+            StdoutEscSeq::SaveCursorPosition | StdoutEscSeq::EnterAlternateScreen => {
+                self.saved_cursor = cursor;
+                cursor
+            }
+            StdoutEscSeq::RestorCursorPosition | StdoutEscSeq::LeaveAlternateScreen => {
+                self.saved_cursor
+            }
+            StdoutEscSeq::ScoSaveCursorPosition => {
+                self.sco_saved_cursor = cursor;
+                cursor
+            }
+            StdoutEscSeq::ScoRestoreCursorPosition => self.sco_saved_cursor,
+            // the cursor goes after the image, which depends on the cell size
+            StdoutEscSeq::Kitty(KittyCommand::Place(placement))
+                if !placement.keep_cursor && !placement.virtual_placement =>
+            {
+                None
+            }
+            StdoutEscSeq::SetScrollRegion(top, bottom) => {
+                self.scroll_region = (top, bottom.min(rows - 1));
+                Some(Cursor::at(0, 0))
+            }
+            StdoutEscSeq::ResetScrollRegion => {
+                self.scroll_region = (0, rows - 1);
+                Some(Cursor::at(0, 0))
+            }
+            StdoutEscSeq::InsertLines(_) | StdoutEscSeq::DeleteLines(_) => {
+                cursor.map(|c| Cursor::at(c.row, 0))
+            }
+            // these leave the cursor where it is, but terminals don't agree on whether they
+            // cancel a pending wrap
+            StdoutEscSeq::InsertChars(_)
+            | StdoutEscSeq::DeleteChars(_)
+            | StdoutEscSeq::EraseChars(_)
+            | StdoutEscSeq::EraseToEndOfLine
+            | StdoutEscSeq::EraseToStartOfLine
+            | StdoutEscSeq::EraseEntireLine => cursor.filter(|cursor| !cursor.pending_wrap),
+            _ => cursor,
+        };
+    }
+}
+
+/// A batch of writes that the terminal shows all at once, see `TsStdout::frame`
+pub struct Frame<'a, W: io::Write = UnsafeStdout> {
+    stdout: &'a mut TsStdout<W>,
+}
+
+impl<W: io::Write> Deref for Frame<'_, W> {
+    type Target = TsStdout<W>;
+
+    fn deref(&self) -> &TsStdout<W> {
+        self.stdout
+    }
+}
+
+impl<W: io::Write> DerefMut for Frame<'_, W> {
+    fn deref_mut(&mut self) -> &mut TsStdout<W> {
+        self.stdout
+    }
+}
+
+impl<W: io::Write> Drop for Frame<'_, W> {
+    fn drop(&mut self) {
+        match self.stdout.synchronized_output {
+            Some(SynchronizedOutput::Mode2026) => {
+                self.stdout.exec([&StdoutEscSeq::EndSynchronizedUpdate]);
+            }
+            Some(SynchronizedOutput::Legacy) => {
+                self.stdout
+                    .exec([&StdoutEscSeq::EndSynchronizedUpdateLegacy]);
+            }
+            None => (),
+        }
+        self.stdout.flush();
+    }
 }
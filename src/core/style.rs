@@ -0,0 +1,484 @@
+use std::io;
+use std::ops::{BitOr, BitOrAssign};
+
+use super::{decimal, ESC_SEQ_PREFIX};
+
+/// A color that SGR sequences can set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+    /// An index into the xterm 256 color palette (`0..16` are the named colors above)
+    Indexed(u8),
+    /// 24-bit "truecolor"
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// The 16 named colors, in palette order
+    pub const NAMED: [Color; 16] = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::White,
+        Color::BrightBlack,
+        Color::BrightRed,
+        Color::BrightGreen,
+        Color::BrightYellow,
+        Color::BrightBlue,
+        Color::BrightMagenta,
+        Color::BrightCyan,
+        Color::BrightWhite,
+    ];
+
+    /// The palette index of a named color, `None` for `Indexed` and `Rgb`
+    pub fn named_index(&self) -> Option<u8> {
+        Self::NAMED
+            .iter()
+            .position(|named| named == self)
+            .map(|i| i as u8)
+    }
+
+    /// Push the SGR parameters for this color. `base` is 30 for the foreground and 40 for the
+    /// background, the extended forms are `base + 8` (38/48/58).
+    fn push_params(&self, base: u8, params: &mut Params) {
+        match (self.named_index(), *self) {
+            // there's no short form for the underline color
+            (Some(i), _) if base != UNDERLINE_COLOR_BASE => {
+                params.push(if i < 8 { base + i } else { base + 60 + i - 8 })
+            }
+            (Some(i), _) | (None, Color::Indexed(i)) => {
+                params.push(base + 8);
+                params.push(5);
+                params.push(i);
+            }
+            (None, Color::Rgb(r, g, b)) => {
+                params.push(base + 8);
+                params.push(2);
+                params.push(r);
+                params.push(g);
+                params.push(b);
+            }
+            (None, _) => unreachable!(),
+        }
+    }
+}
+
+const FOREGROUND_BASE: u8 = 30;
+const BACKGROUND_BASE: u8 = 40;
+const UNDERLINE_COLOR_BASE: u8 = 50;
+
+/// The different kinds of underline. Anything but `Single` is an extension (kitty, wezterm,
+/// foot, vte, ...), terminals that don't know about it usually fall back to a straight line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UnderlineStyle {
+    Single,
+    Double,
+    Curly,
+    Dotted,
+    Dashed,
+}
+
+impl UnderlineStyle {
+    fn push_params(&self, params: &mut Params) {
+        match *self {
+            Self::Single => params.push(4),
+            Self::Double => params.push_str("4:2"),
+            Self::Curly => params.push_str("4:3"),
+            Self::Dotted => params.push_str("4:4"),
+            Self::Dashed => params.push_str("4:5"),
+        }
+    }
+}
+
+/// A set of on/off text attributes, combine them with `|`. Underlines live in
+/// `Style::underline`, since they come in different shapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Attributes(u16);
+
+impl Attributes {
+    pub const NONE: Self = Self(0);
+    pub const BOLD: Self = Self(1 << 0);
+    pub const DIM: Self = Self(1 << 1);
+    pub const ITALIC: Self = Self(1 << 2);
+    pub const BLINK: Self = Self(1 << 3);
+    pub const REVERSE: Self = Self(1 << 4);
+    pub const HIDDEN: Self = Self(1 << 5);
+    pub const STRIKETHROUGH: Self = Self(1 << 6);
+    pub const OVERLINE: Self = Self(1 << 7);
+
+    /// `(attribute, SGR to turn it on, SGR to turn it off)`, bold and dim share an "off"
+    const SGR: [(Self, u8, u8); 8] = [
+        (Self::BOLD, 1, 22),
+        (Self::DIM, 2, 22),
+        (Self::ITALIC, 3, 23),
+        (Self::BLINK, 5, 25),
+        (Self::REVERSE, 7, 27),
+        (Self::HIDDEN, 8, 28),
+        (Self::STRIKETHROUGH, 9, 29),
+        (Self::OVERLINE, 53, 55),
+    ];
+
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl BitOr for Attributes {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Attributes {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Enough for every parameter a diff could need (each color can take up to 16 bytes)
+const PARAMS_CAPACITY: usize = 128;
+
+/// SGR parameters, joined with `;`. These live on the stack so styling doesn't allocate.
+struct Params {
+    buf: [u8; PARAMS_CAPACITY],
+    len: usize,
+}
+
+impl Params {
+    fn new() -> Self {
+        Self {
+            buf: [0; PARAMS_CAPACITY],
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, param: u8) {
+        self.push_str_bytes(decimal(param as u32, &mut [0; 10]));
+    }
+
+    fn push_str(&mut self, param: &str) {
+        self.push_str_bytes(param.as_bytes());
+    }
+
+    fn push_str_bytes(&mut self, param: &[u8]) {
+        if self.len != 0 {
+            self.buf[self.len] = b';';
+            self.len += 1;
+        }
+        self.buf[self.len..self.len + param.len()].copy_from_slice(param);
+        self.len += param.len();
+    }
+
+    /// How long the whole SGR sequence is
+    fn sgr_len(&self) -> usize {
+        ESC_SEQ_PREFIX.len() + self.len + 1
+    }
+
+    fn write_sgr<W: io::Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(&ESC_SEQ_PREFIX)?;
+        w.write_all(&self.buf[..self.len])?;
+        w.write_all(b"m")
+    }
+}
+
+/// How text looks. `None` colors (and no underline) mean the terminal's default.
+///
+/// Styles are built up by chaining, and layered with `patch`:
+///
+/// ```rs
+/// let error = Style::new().fg(Color::Red).bold();
+/// let highlighted = error.patch(Style::new().bg(Color::Rgb(0x30, 0x30, 0x30)));
+/// stdout.set_style(&highlighted).write_str("oh no").reset_style();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub underline_color: Option<Color>,
+    pub underline: Option<UnderlineStyle>,
+    pub attributes: Attributes,
+}
+
+impl Style {
+    /// The default style, i.e. what you get after `ESC [ 0 m`
+    pub const fn new() -> Self {
+        Self {
+            fg: None,
+            bg: None,
+            underline_color: None,
+            underline: None,
+            attributes: Attributes::NONE,
+        }
+    }
+
+    pub fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    pub fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+
+    /// The color of the underline (SGR 58), if the terminal supports it
+    pub fn underline_color(mut self, color: Color) -> Self {
+        self.underline_color = Some(color);
+        self
+    }
+
+    pub fn attributes(mut self, attributes: Attributes) -> Self {
+        self.attributes |= attributes;
+        self
+    }
+
+    pub fn bold(self) -> Self {
+        self.attributes(Attributes::BOLD)
+    }
+
+    pub fn dim(self) -> Self {
+        self.attributes(Attributes::DIM)
+    }
+
+    pub fn italic(self) -> Self {
+        self.attributes(Attributes::ITALIC)
+    }
+
+    pub fn underline(self) -> Self {
+        self.underline_style(UnderlineStyle::Single)
+    }
+
+    pub fn underline_style(mut self, underline: UnderlineStyle) -> Self {
+        self.underline = Some(underline);
+        self
+    }
+
+    pub fn blink(self) -> Self {
+        self.attributes(Attributes::BLINK)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.attributes(Attributes::REVERSE)
+    }
+
+    pub fn hidden(self) -> Self {
+        self.attributes(Attributes::HIDDEN)
+    }
+
+    pub fn strikethrough(self) -> Self {
+        self.attributes(Attributes::STRIKETHROUGH)
+    }
+
+    pub fn overline(self) -> Self {
+        self.attributes(Attributes::OVERLINE)
+    }
+
+    /// Layer `other` on top of this style: its colors and underline win wherever they're set,
+    /// and the attributes of both are combined
+    pub fn patch(self, other: Style) -> Self {
+        Self {
+            fg: other.fg.or(self.fg),
+            bg: other.bg.or(self.bg),
+            underline_color: other.underline_color.or(self.underline_color),
+            underline: other.underline.or(self.underline),
+            attributes: self.attributes | other.attributes,
+        }
+    }
+
+    /// Push everything that needs setting to get from the default style to this one
+    fn push_params(&self, params: &mut Params) {
+        for (attribute, on, _) in Attributes::SGR {
+            if self.attributes.contains(attribute) {
+                params.push(on);
+            }
+        }
+        if let Some(underline) = self.underline {
+            underline.push_params(params);
+        }
+        if let Some(fg) = self.fg {
+            fg.push_params(FOREGROUND_BASE, params);
+        }
+        if let Some(bg) = self.bg {
+            bg.push_params(BACKGROUND_BASE, params);
+        }
+        if let Some(underline_color) = self.underline_color {
+            underline_color.push_params(UNDERLINE_COLOR_BASE, params);
+        }
+    }
+
+    /// The SGR sequence that sets exactly this style, whatever was set before
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        // writing to a `Vec` can't fail
+        let _ = self.write_to(&mut bytes);
+        bytes
+    }
+
+    /// Write what `as_bytes` returns straight into `w`, without allocating
+    pub fn write_to<W: io::Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        self.params_from_scratch().write_sgr(w)
+    }
+
+    fn params_from_scratch(&self) -> Params {
+        let mut params = Params::new();
+        params.push(0);
+        self.push_params(&mut params);
+        params
+    }
+
+    /// The shortest SGR sequence that takes the terminal from this style to `next`. This is
+    /// empty if they're the same.
+    pub fn diff(&self, next: &Style) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let _ = self.write_diff_to(next, &mut bytes);
+        bytes
+    }
+
+    /// Write what `diff` returns straight into `w`, without allocating
+    pub fn write_diff_to<W: io::Write + ?Sized>(&self, next: &Style, w: &mut W) -> io::Result<()> {
+        if self == next {
+            return Ok(());
+        }
+
+        // either change just what's different...
+        let mut params = Params::new();
+        let (from, to) = (self.attributes, next.attributes);
+        // 22 turns off both bold and dim, so if we have to send it, turn whichever one we're
+        // keeping back on afterwards
+        let (bold_and_dim, rest) = Attributes::SGR.split_at(2);
+        let turned_off = |&(attribute, _, _): &(Attributes, u8, u8)| {
+            from.contains(attribute) && !to.contains(attribute)
+        };
+        let reset_bold_and_dim = bold_and_dim.iter().any(turned_off);
+        if reset_bold_and_dim {
+            params.push(22);
+        }
+        for &(attribute, on, _) in bold_and_dim {
+            if to.contains(attribute) && (reset_bold_and_dim || !from.contains(attribute)) {
+                params.push(on);
+            }
+        }
+        for entry @ &(attribute, on, off) in rest {
+            if turned_off(entry) {
+                params.push(off);
+            } else if to.contains(attribute) && !from.contains(attribute) {
+                params.push(on);
+            }
+        }
+        if self.underline != next.underline {
+            match next.underline {
+                Some(underline) => underline.push_params(&mut params),
+                None => params.push(24),
+            }
+        }
+        for (base, reset, from, to) in [
+            (FOREGROUND_BASE, 39, self.fg, next.fg),
+            (BACKGROUND_BASE, 49, self.bg, next.bg),
+            (
+                UNDERLINE_COLOR_BASE,
+                59,
+                self.underline_color,
+                next.underline_color,
+            ),
+        ] {
+            if from != to {
+                match to {
+                    Some(color) => color.push_params(base, &mut params),
+                    None => params.push(reset),
+                }
+            }
+        }
+
+        // ...or reset and start from scratch, whichever is shorter
+        let from_scratch = next.params_from_scratch();
+        if from_scratch.sgr_len() < params.sgr_len() {
+            from_scratch.write_sgr(w)
+        } else {
+            params.write_sgr(w)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(from: Style, to: Style) -> String {
+        String::from_utf8(from.diff(&to)).unwrap()
+    }
+
+    #[test]
+    fn same_style_sends_nothing() {
+        let style = Style::new().bold().fg(Color::Red);
+        assert_eq!(diff(style, style), "");
+    }
+
+    #[test]
+    fn bold_and_dim_share_22() {
+        // the color makes starting from scratch longer
+        let red = Style::new().fg(Color::Red);
+        assert_eq!(diff(red.bold(), red.dim()), "\x1b[22;2m");
+        assert_eq!(diff(red.bold().dim(), red.dim()), "\x1b[22;2m");
+        assert_eq!(diff(red.bold().dim(), red.bold()), "\x1b[22;1m");
+        assert_eq!(diff(red.dim(), red.bold().dim()), "\x1b[1m");
+        assert_eq!(diff(red.bold().italic(), red.italic()), "\x1b[22m");
+    }
+
+    #[test]
+    fn only_changes_are_sent() {
+        let bold = Style::new().bold();
+        assert_eq!(diff(bold.fg(Color::Red), bold.fg(Color::Blue)), "\x1b[34m");
+        assert_eq!(diff(bold.fg(Color::Red), bold), "\x1b[39m");
+        assert_eq!(
+            diff(bold, bold.italic().bg(Color::Rgb(1, 2, 3))),
+            "\x1b[3;48;2;1;2;3m"
+        );
+    }
+
+    #[test]
+    fn reset_when_its_shorter() {
+        let busy = Style::new()
+            .bold()
+            .italic()
+            .underline()
+            .fg(Color::Red)
+            .bg(Color::Blue);
+        assert_eq!(diff(busy, Style::new()), "\x1b[0m");
+        assert_eq!(diff(Style::new().bold(), Style::new().dim()), "\x1b[0;2m");
+        assert_eq!(diff(busy, Style::new().dim()), "\x1b[0;2m");
+        assert_eq!(busy.as_bytes(), b"\x1b[0;1;3;4;31;44m");
+    }
+}
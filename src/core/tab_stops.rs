@@ -0,0 +1,159 @@
+/// How far apart terminals put tab stops to start with
+const TAB_WIDTH: u32 = 8;
+
+/// The columns a `\t` can stop at, one row's worth. Terminals start with a stop every 8
+/// columns, and `StdoutEscSeq::SetTabStop` etc. change them.
+///
+/// ```rs
+/// let mut stops = TabStops::new(80);
+/// stops.clear_all().set(20).set(36);
+/// assert_eq!(stops.next(0, 1), 20);
+/// assert_eq!(stops.next(36, 1), 79);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TabStops {
+    /// one per column
+    stops: Vec<bool>,
+}
+
+impl TabStops {
+    /// The usual stops, every 8 columns
+    pub fn new(cols: u32) -> Self {
+        let mut stops = Self { stops: Vec::new() };
+        stops.resize(cols);
+        stops
+    }
+
+    /// Stops at exactly `cols_with_stops`, leaving out any past the end
+    pub fn with_stops(cols: u32, cols_with_stops: impl IntoIterator<Item = u32>) -> Self {
+        let mut stops = Self {
+            stops: vec![false; cols as usize],
+        };
+        for col in cols_with_stops {
+            stops.set(col);
+        }
+        stops
+    }
+
+    pub fn cols(&self) -> u32 {
+        self.stops.len() as u32
+    }
+
+    /// Add or remove columns at the end. New ones get the usual stops, like they do in xterm
+    /// when the window gets wider.
+    pub fn resize(&mut self, cols: u32) -> &mut Self {
+        let old_cols = self.cols();
+        self.stops.resize(cols as usize, false);
+        for col in old_cols..cols {
+            self.stops[col as usize] = col % TAB_WIDTH == 0;
+        }
+        self
+    }
+
+    pub fn set(&mut self, col: u32) -> &mut Self {
+        if let Some(stop) = self.stops.get_mut(col as usize) {
+            *stop = true;
+        }
+        self
+    }
+
+    pub fn clear(&mut self, col: u32) -> &mut Self {
+        if let Some(stop) = self.stops.get_mut(col as usize) {
+            *stop = false;
+        }
+        self
+    }
+
+    pub fn clear_all(&mut self) -> &mut Self {
+        self.stops.fill(false);
+        self
+    }
+
+    pub fn is_set(&self, col: u32) -> bool {
+        self.stops.get(col as usize).copied().unwrap_or(false)
+    }
+
+    /// The columns with stops, from left to right
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..self.cols()).filter(|&col| self.is_set(col))
+    }
+
+    /// Where the cursor ends up after `n` tabs from `col`: the `n`th stop to the right, or the
+    /// last column if there aren't that many
+    pub fn next(&self, col: u32, n: u32) -> u32 {
+        let last = self.cols().saturating_sub(1);
+        let mut col = col.min(last);
+        for _ in 0..n {
+            col = (col + 1..last)
+                .find(|&col| self.is_set(col))
+                .unwrap_or(last);
+        }
+        col
+    }
+
+    /// Where the cursor ends up after `n` back tabs from `col`: the `n`th stop to the left,
+    /// or the first column if there aren't that many
+    pub fn previous(&self, col: u32, n: u32) -> u32 {
+        let mut col = col.min(self.cols().saturating_sub(1));
+        for _ in 0..n {
+            col = (0..col).rev().find(|&col| self.is_set(col)).unwrap_or(0);
+        }
+        col
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_stops_at_the_last_column() {
+        let stops = TabStops::new(80);
+        assert_eq!(stops.next(0, 1), 8);
+        assert_eq!(stops.next(8, 1), 16);
+        assert_eq!(stops.next(3, 2), 16);
+        assert_eq!(stops.next(72, 1), 79);
+        assert_eq!(stops.next(79, 1), 79);
+        assert_eq!(stops.next(0, 100), 79);
+        // past the end counts as the last column
+        assert_eq!(stops.next(200, 1), 79);
+        assert_eq!(stops.next(5, 0), 5);
+    }
+
+    #[test]
+    fn previous_stops_at_the_first_column() {
+        let stops = TabStops::new(80);
+        assert_eq!(stops.previous(79, 1), 72);
+        assert_eq!(stops.previous(9, 1), 8);
+        assert_eq!(stops.previous(8, 1), 0);
+        assert_eq!(stops.previous(0, 1), 0);
+        assert_eq!(stops.previous(79, 100), 0);
+        assert_eq!(stops.previous(200, 1), 72);
+        assert_eq!(stops.previous(5, 0), 5);
+    }
+
+    #[test]
+    fn custom_stops() {
+        let mut stops = TabStops::new(80);
+        stops.clear_all().set(20).set(36);
+        assert_eq!(stops.next(0, 1), 20);
+        assert_eq!(stops.next(0, 2), 36);
+        assert_eq!(stops.next(36, 1), 79);
+        assert_eq!(stops.previous(79, 1), 36);
+        assert_eq!(stops.previous(20, 1), 0);
+    }
+
+    #[test]
+    fn no_columns() {
+        let stops = TabStops::new(0);
+        assert_eq!(stops.next(0, 1), 0);
+        assert_eq!(stops.previous(0, 1), 0);
+    }
+
+    #[test]
+    fn resize_adds_the_usual_stops() {
+        let mut stops = TabStops::with_stops(10, [3]);
+        stops.resize(20);
+        assert_eq!(stops.iter().collect::<Vec<_>>(), [3, 16]);
+    }
+}
@@ -0,0 +1,861 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+
+/// Magic number of the legacy format, numbers are 16 bits
+const MAGIC_LEGACY: u16 = 0o432;
+/// Magic number of the extended number format (ncurses 6.1+), numbers are 32 bits
+const MAGIC_32BIT: u16 = 0o1036;
+
+/// Where we look for compiled entries after `$TERMINFO`, `~/.terminfo` and `$TERMINFO_DIRS`
+const DEFAULT_DIRS: [&str; 3] = ["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo"];
+
+// the names of the predefined capabilities, in the order they appear in compiled entries
+// (taken from ncurses' `boolnames`, `numnames` and `strnames`)
+const BOOL_NAMES: [&str; 44] = [
+    "bw", "am", "xsb", "xhp", "xenl", "eo", "gn", "hc", "km", "hs", "in", "da", "db", "mir",
+    "msgr", "os", "eslok", "xt", "hz", "ul", "xon", "nxon", "mc5i", "chts", "nrrmc", "npc",
+    "ndscr", "ccc", "bce", "hls", "xhpa", "crxm", "daisy", "xvpa", "sam", "cpix", "lpix", "OTbs",
+    "OTns", "OTnc", "OTMT", "OTNL", "OTpt", "OTxr",
+];
+
+const NUM_NAMES: [&str; 39] = [
+    "cols", "it", "lines", "lm", "xmc", "pb", "vt", "wsl", "nlab", "lh", "lw", "ma", "wnum",
+    "colors", "pairs", "ncv", "bufsz", "spinv", "spinh", "maddr", "mjump", "mcs", "mls", "npins",
+    "orc", "orl", "orhi", "orvi", "cps", "widcs", "btns", "bitwin", "bitype", "OTug", "OTdC",
+    "OTdN", "OTdB", "OTdT", "OTkn",
+];
+
+const STRING_NAMES: [&str; 414] = [
+    "cbt", "bel", "cr", "csr", "tbc", "clear", "el", "ed", "hpa", "cmdch", "cup", "cud1", "home",
+    "civis", "cub1", "mrcup", "cnorm", "cuf1", "ll", "cuu1", "cvvis", "dch1", "dl1", "dsl", "hd",
+    "smacs", "blink", "bold", "smcup", "smdc", "dim", "smir", "invis", "prot", "rev", "smso",
+    "smul", "ech", "rmacs", "sgr0", "rmcup", "rmdc", "rmir", "rmso", "rmul", "flash", "ff", "fsl",
+    "is1", "is2", "is3", "if", "ich1", "il1", "ip", "kbs", "ktbc", "kclr", "kctab", "kdch1",
+    "kdl1", "kcud1", "krmir", "kel", "ked", "kf0", "kf1", "kf10", "kf2", "kf3", "kf4", "kf5",
+    "kf6", "kf7", "kf8", "kf9", "khome", "kich1", "kil1", "kcub1", "kll", "knp", "kpp", "kcuf1",
+    "kind", "kri", "khts", "kcuu1", "rmkx", "smkx", "lf0", "lf1", "lf10", "lf2", "lf3", "lf4",
+    "lf5", "lf6", "lf7", "lf8", "lf9", "rmm", "smm", "nel", "pad", "dch", "dl", "cud", "ich",
+    "indn", "il", "cub", "cuf", "rin", "cuu", "pfkey", "pfloc", "pfx", "mc0", "mc4", "mc5", "rep",
+    "rs1", "rs2", "rs3", "rf", "rc", "vpa", "sc", "ind", "ri", "sgr", "hts", "wind", "ht", "tsl",
+    "uc", "hu", "iprog", "ka1", "ka3", "kb2", "kc1", "kc3", "mc5p", "rmp", "acsc", "pln", "kcbt",
+    "smxon", "rmxon", "smam", "rmam", "xonc", "xoffc", "enacs", "smln", "rmln", "kbeg", "kcan",
+    "kclo", "kcmd", "kcpy", "kcrt", "kend", "kent", "kext", "kfnd", "khlp", "kmrk", "kmsg", "kmov",
+    "knxt", "kopn", "kopt", "kprv", "kprt", "krdo", "kref", "krfr", "krpl", "krst", "kres", "ksav",
+    "kspd", "kund", "kBEG", "kCAN", "kCMD", "kCPY", "kCRT", "kDC", "kDL", "kslt", "kEND", "kEOL",
+    "kEXT", "kFND", "kHLP", "kHOM", "kIC", "kLFT", "kMSG", "kMOV", "kNXT", "kOPT", "kPRV", "kPRT",
+    "kRDO", "kRPL", "kRIT", "kRES", "kSAV", "kSPD", "kUND", "rfi", "kf11", "kf12", "kf13", "kf14",
+    "kf15", "kf16", "kf17", "kf18", "kf19", "kf20", "kf21", "kf22", "kf23", "kf24", "kf25", "kf26",
+    "kf27", "kf28", "kf29", "kf30", "kf31", "kf32", "kf33", "kf34", "kf35", "kf36", "kf37", "kf38",
+    "kf39", "kf40", "kf41", "kf42", "kf43", "kf44", "kf45", "kf46", "kf47", "kf48", "kf49", "kf50",
+    "kf51", "kf52", "kf53", "kf54", "kf55", "kf56", "kf57", "kf58", "kf59", "kf60", "kf61", "kf62",
+    "kf63", "el1", "mgc", "smgl", "smgr", "fln", "sclk", "dclk", "rmclk", "cwin", "wingo", "hup",
+    "dial", "qdial", "tone", "pulse", "hook", "pause", "wait", "u0", "u1", "u2", "u3", "u4", "u5",
+    "u6", "u7", "u8", "u9", "op", "oc", "initc", "initp", "scp", "setf", "setb", "cpi", "lpi",
+    "chr", "cvr", "defc", "swidm", "sdrfq", "sitm", "slm", "smicm", "snlq", "snrmq", "sshm",
+    "ssubm", "ssupm", "sum", "rwidm", "ritm", "rlm", "rmicm", "rshm", "rsubm", "rsupm", "rum",
+    "mhpa", "mcud1", "mcub1", "mcuf1", "mvpa", "mcuu1", "porder", "mcud", "mcub", "mcuf", "mcuu",
+    "scs", "smgb", "smgbp", "smglp", "smgrp", "smgt", "smgtp", "sbim", "scsd", "rbim", "rcsd",
+    "subcs", "supcs", "docr", "zerom", "csnm", "kmous", "minfo", "reqmp", "getm", "setaf", "setab",
+    "pfxl", "devt", "csin", "s0ds", "s1ds", "s2ds", "s3ds", "smglr", "smgtb", "birep", "binel",
+    "bicr", "colornm", "defbi", "endbi", "setcolor", "slines", "dispc", "smpch", "rmpch", "smsc",
+    "rmsc", "pctrm", "scesc", "scesa", "ehhlm", "elhlm", "elohlm", "erhlm", "ethlm", "evhlm",
+    "sgr1", "slength", "OTi2", "OTrs", "OTnl", "OTbc", "OTko", "OTma", "OTG2", "OTG3", "OTG1",
+    "OTG4", "OTGR", "OTGL", "OTGU", "OTGD", "OTGH", "OTGV", "OTGC", "meml", "memu", "box1",
+];
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// A little-endian reader over a compiled terminfo entry
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        let slice = self
+            .bytes
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| invalid("terminfo entry is truncated"))?;
+        self.pos += len;
+        Ok(slice)
+    }
+
+    fn u16(&mut self) -> io::Result<u16> {
+        let b = self.take(2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn i16(&mut self) -> io::Result<i16> {
+        Ok(self.u16()? as i16)
+    }
+
+    fn count(&mut self) -> io::Result<usize> {
+        match self.i16()? {
+            n if n < 0 => Ok(0),
+            n => Ok(n as usize),
+        }
+    }
+
+    fn number(&mut self, wide: bool) -> io::Result<i32> {
+        if wide {
+            let b = self.take(4)?;
+            Ok(i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        } else {
+            Ok(self.i16()? as i32)
+        }
+    }
+
+    /// Sections start on even offsets
+    fn align(&mut self) {
+        self.pos += self.pos % 2;
+    }
+}
+
+/// The NUL terminated string starting at `offset` in `table`
+fn table_string(table: &[u8], offset: usize) -> io::Result<&[u8]> {
+    let rest = table
+        .get(offset..)
+        .ok_or_else(|| invalid("terminfo string offset is out of bounds"))?;
+    let len = rest.iter().position(|&b| b == 0).unwrap_or(rest.len());
+    Ok(&rest[..len])
+}
+
+/// A parsed terminfo entry, i.e. what the terminal we're running in can do and which escape
+/// sequences it wants for it.
+///
+/// ```rs
+/// let terminfo = TermInfo::from_env()?;
+/// if let Some(cup) = terminfo.string("cup") {
+///     stdout.write_bytes(&tparm(cup, &[Param::from(5), Param::from(10)])?);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TermInfo {
+    /// The names of the terminal, the last one is usually a long description
+    pub names: Vec<String>,
+    bools: HashMap<String, bool>,
+    numbers: HashMap<String, i32>,
+    strings: HashMap<String, Vec<u8>>,
+}
+
+static CURRENT: Lazy<Option<Arc<TermInfo>>> = Lazy::new(|| TermInfo::from_env().ok().map(Arc::new));
+
+impl TermInfo {
+    /// The entry for `$TERM`, loaded once and shared. `None` if `$TERM` isn't set or we
+    /// couldn't find (or read) its entry.
+    pub fn current() -> Option<Arc<TermInfo>> {
+        CURRENT.clone()
+    }
+
+    /// Load the entry for `$TERM`
+    pub fn from_env() -> io::Result<Self> {
+        match env::var("TERM") {
+            Ok(term) if !term.is_empty() => Self::from_name(&term),
+            _ => Err(io::Error::new(io::ErrorKind::NotFound, "$TERM is not set")),
+        }
+    }
+
+    /// Search the usual places for the compiled entry called `name`
+    pub fn from_name(name: &str) -> io::Result<Self> {
+        if name.is_empty() || name.contains('/') || name.starts_with('.') {
+            return Err(invalid("invalid terminal name"));
+        }
+        let first = name.chars().next().unwrap();
+        for dir in Self::search_dirs() {
+            // Linux uses the first letter, macOS uses its hex value
+            for subdir in [first.to_string(), format!("{:x}", first as u32)] {
+                let path = dir.join(subdir).join(name);
+                match fs::read(&path) {
+                    Ok(bytes) => return Self::from_bytes(&bytes),
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no terminfo entry for {:?}", name),
+        ))
+    }
+
+    /// Load a compiled entry from a file
+    pub fn from_path(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    /// The directories we look in, in order: `$TERMINFO`, `~/.terminfo`, `$TERMINFO_DIRS`
+    /// (where an empty entry means the defaults) and then the defaults
+    fn search_dirs() -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
+        if let Some(dir) = env::var_os("TERMINFO") {
+            dirs.push(PathBuf::from(dir));
+        }
+        if let Some(home) = env::var_os("HOME") {
+            dirs.push(Path::new(&home).join(".terminfo"));
+        }
+        if let Ok(terminfo_dirs) = env::var("TERMINFO_DIRS") {
+            for dir in terminfo_dirs.split(':') {
+                match dir {
+                    "" => dirs.extend(DEFAULT_DIRS.iter().map(PathBuf::from)),
+                    dir => dirs.push(PathBuf::from(dir)),
+                }
+            }
+        }
+        dirs.extend(DEFAULT_DIRS.iter().map(PathBuf::from));
+        dirs
+    }
+
+    /// Parse a compiled entry, in either the legacy or the 32-bit format, including the
+    /// extended (user-defined) capabilities if there are any
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let mut cursor = Cursor { bytes, pos: 0 };
+        let wide = match cursor.u16()? {
+            MAGIC_LEGACY => false,
+            MAGIC_32BIT => true,
+            _ => return Err(invalid("not a compiled terminfo entry")),
+        };
+        let names_size = cursor.count()?;
+        let bools_count = cursor.count()?;
+        let numbers_count = cursor.count()?;
+        let strings_count = cursor.count()?;
+        let table_size = cursor.count()?;
+
+        let mut terminfo = Self::default();
+        let names = table_string(cursor.take(names_size)?, 0)?;
+        terminfo.names = String::from_utf8_lossy(names)
+            .split('|')
+            .map(String::from)
+            .collect();
+
+        let bools = cursor.take(bools_count)?;
+        for (name, _) in BOOL_NAMES.iter().zip(bools).filter(|(_, &b)| b == 1) {
+            terminfo.bools.insert(name.to_string(), true);
+        }
+        cursor.align();
+
+        for name in NUM_NAMES
+            .iter()
+            .chain(std::iter::repeat(&""))
+            .take(numbers_count)
+        {
+            let number = cursor.number(wide)?;
+            if number >= 0 && !name.is_empty() {
+                terminfo.numbers.insert(name.to_string(), number);
+            }
+        }
+
+        let offsets = (0..strings_count)
+            .map(|_| cursor.i16())
+            .collect::<io::Result<Vec<_>>>()?;
+        let table = cursor.take(table_size)?;
+        for (name, &offset) in STRING_NAMES.iter().zip(&offsets) {
+            if offset >= 0 {
+                let value = table_string(table, offset as usize)?;
+                terminfo.strings.insert(name.to_string(), value.to_vec());
+            }
+        }
+
+        cursor.align();
+        if cursor.pos < bytes.len() {
+            terminfo.parse_extended(&mut cursor, wide)?;
+        }
+        Ok(terminfo)
+    }
+
+    /// The extended section has its own counts, then the values, then a string table with the
+    /// string values followed by the names of all the extended capabilities
+    fn parse_extended(&mut self, cursor: &mut Cursor, wide: bool) -> io::Result<()> {
+        let bools_count = cursor.count()?;
+        let numbers_count = cursor.count()?;
+        let strings_count = cursor.count()?;
+        let _table_items = cursor.count()?;
+        let table_size = cursor.count()?;
+
+        let bools = cursor.take(bools_count)?.to_vec();
+        cursor.align();
+        let numbers = (0..numbers_count)
+            .map(|_| cursor.number(wide))
+            .collect::<io::Result<Vec<_>>>()?;
+        let value_offsets = (0..strings_count)
+            .map(|_| cursor.i16())
+            .collect::<io::Result<Vec<_>>>()?;
+        let name_offsets = (0..bools_count + numbers_count + strings_count)
+            .map(|_| cursor.i16())
+            .collect::<io::Result<Vec<_>>>()?;
+        let table = cursor.take(table_size)?;
+
+        // the names start right after the last string value
+        let mut names_start = 0;
+        for &offset in value_offsets.iter().filter(|&&offset| offset >= 0) {
+            let end = offset as usize + table_string(table, offset as usize)?.len() + 1;
+            names_start = names_start.max(end);
+        }
+        let names_table = table.get(names_start..).unwrap_or(&[]);
+        let mut names = name_offsets.iter().map(|&offset| {
+            let name = table_string(names_table, offset.max(0) as usize)?;
+            Ok::<_, io::Error>(String::from_utf8_lossy(name).into_owned())
+        });
+
+        for &value in &bools {
+            let name = names.next().unwrap()?;
+            if value == 1 {
+                self.bools.insert(name, true);
+            }
+        }
+        for &value in &numbers {
+            let name = names.next().unwrap()?;
+            if value >= 0 {
+                self.numbers.insert(name, value);
+            }
+        }
+        for &offset in &value_offsets {
+            let name = names.next().unwrap()?;
+            if offset >= 0 {
+                let value = table_string(table, offset as usize)?;
+                self.strings.insert(name, value.to_vec());
+            }
+        }
+        Ok(())
+    }
+
+    /// A boolean capability, e.g. `am` or `Tc`. Missing capabilities are `false`.
+    pub fn flag(&self, name: &str) -> bool {
+        self.bools.get(name).copied().unwrap_or(false)
+    }
+
+    /// A numeric capability, e.g. `colors` or `cols`
+    pub fn number(&self, name: &str) -> Option<i32> {
+        self.numbers.get(name).copied()
+    }
+
+    /// A string capability, e.g. `cup` or `smcup`. Pass it through `tparm` if it takes
+    /// parameters.
+    pub fn string(&self, name: &str) -> Option<&[u8]> {
+        self.strings.get(name).map(Vec::as_slice)
+    }
+
+    /// Expand the string capability `name` with `params`, with any `$<..>` padding removed.
+    /// `None` if the terminal doesn't have it (or it's malformed).
+    pub fn expand(&self, name: &str, params: &[Param]) -> Option<Vec<u8>> {
+        let mut out = Vec::new();
+        tparm_to(self.string(name)?, params, true, &mut out).ok()?;
+        Some(out)
+    }
+
+    /// `expand`, but straight into `w`, and without allocating unless the expansion is
+    /// unusually long. Returns `false` without writing anything if the terminal doesn't have
+    /// `name` (or it's malformed).
+    pub fn expand_to<W: io::Write + ?Sized>(
+        &self,
+        name: &str,
+        params: &[Param],
+        w: &mut W,
+    ) -> io::Result<bool> {
+        let Some(cap) = self.string(name) else {
+            return Ok(false);
+        };
+        // expanded somewhere else first, so a malformed capability doesn't write half of itself
+        let mut buf = StackBuf::new();
+        if tparm_to(cap, params, true, &mut buf).is_ok() {
+            w.write_all(buf.as_slice())?;
+            return Ok(true);
+        }
+        match self.expand(name, params) {
+            Some(expanded) => w.write_all(&expanded).map(|()| true),
+            None => Ok(false),
+        }
+    }
+}
+
+/// A parameter for `tparm`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Param {
+    Number(i32),
+    Str(Vec<u8>),
+}
+
+impl From<i32> for Param {
+    fn from(n: i32) -> Self {
+        Self::Number(n)
+    }
+}
+
+impl From<u32> for Param {
+    fn from(n: u32) -> Self {
+        Self::Number(n.min(i32::MAX as u32) as i32)
+    }
+}
+
+impl From<&str> for Param {
+    fn from(s: &str) -> Self {
+        Self::Str(s.as_bytes().to_vec())
+    }
+}
+
+/// Remove `$<..>` delays, which only matter for real hardware terminals
+pub fn strip_padding(cap: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(cap.len());
+    let mut i = 0;
+    while i < cap.len() {
+        if cap[i..].starts_with(b"$<") {
+            if let Some(end) = cap[i..].iter().position(|&b| b == b'>') {
+                i += end + 1;
+                continue;
+            }
+        }
+        out.push(cap[i]);
+        i += 1;
+    }
+    out
+}
+
+/// Skip over a conditional branch, starting just after `%t` or `%e`. If `to_else` we stop
+/// after a `%e` at the same depth (to run the else part), otherwise only after `%;`.
+fn skip_branch(cap: &[u8], mut i: usize, to_else: bool) -> usize {
+    let mut depth = 0;
+    while i + 1 < cap.len() {
+        if cap[i] != b'%' {
+            i += 1;
+            continue;
+        }
+        match cap[i + 1] {
+            b'?' => depth += 1,
+            b';' if depth == 0 => return i + 2,
+            b';' => depth -= 1,
+            b'e' if depth == 0 && to_else => return i + 2,
+            _ => (),
+        }
+        i += 2;
+    }
+    cap.len()
+}
+
+/// How deep the `tparm` stack can get, same as ncurses
+const STACK_SIZE: usize = 20;
+
+/// A `Param` on the `tparm` stack, which borrows strings instead of copying them
+#[derive(Debug, Clone, Copy)]
+enum Value<'a> {
+    Number(i32),
+    Str(&'a [u8]),
+}
+
+impl<'a> Value<'a> {
+    fn new(param: &'a Param) -> Self {
+        match param {
+            Param::Number(n) => Self::Number(*n),
+            Param::Str(s) => Self::Str(s),
+        }
+    }
+
+    fn as_number(self) -> i32 {
+        match self {
+            Self::Number(n) => n,
+            Self::Str(_) => 0,
+        }
+    }
+
+    /// How long it is as a string (`%l`)
+    fn len(self) -> usize {
+        match self {
+            Self::Number(n) => {
+                let mut digits = Digits::new();
+                let _ = write!(digits, "{}", n);
+                digits.len
+            }
+            Self::Str(s) => s.len(),
+        }
+    }
+}
+
+/// The `tparm` stack, which lives on the real stack
+struct Stack<'a> {
+    values: [Value<'a>; STACK_SIZE],
+    len: usize,
+}
+
+impl<'a> Stack<'a> {
+    fn push(&mut self, value: Value<'a>) -> io::Result<()> {
+        let slot = self
+            .values
+            .get_mut(self.len)
+            .ok_or_else(|| invalid("tparm stack overflow"))?;
+        *slot = value;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Popping an empty stack gives `0`, like ncurses
+    fn pop(&mut self) -> Value<'a> {
+        if self.len == 0 {
+            return Value::Number(0);
+        }
+        self.len -= 1;
+        self.values[self.len]
+    }
+
+    fn pop_number(&mut self) -> i32 {
+        self.pop().as_number()
+    }
+}
+
+/// A number's digits, formatted without allocating
+struct Digits {
+    bytes: [u8; 16],
+    len: usize,
+}
+
+impl Digits {
+    fn new() -> Self {
+        Self {
+            bytes: [0; 16],
+            len: 0,
+        }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl io::Write for Digits {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(self.bytes.len() - self.len);
+        self.bytes[self.len..self.len + len].copy_from_slice(&buf[..len]);
+        self.len += len;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Somewhere to expand a capability without allocating, so nothing is written if it turns
+/// out to be malformed. Writing more than fits is an error.
+struct StackBuf {
+    bytes: [u8; 256],
+    len: usize,
+}
+
+impl StackBuf {
+    fn new() -> Self {
+        Self {
+            bytes: [0; 256],
+            len: 0,
+        }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl io::Write for StackBuf {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let end = self.len + buf.len();
+        if end > self.bytes.len() {
+            return Err(io::ErrorKind::WriteZero.into());
+        }
+        self.bytes[self.len..end].copy_from_slice(buf);
+        self.len = end;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn write_repeated<W: io::Write + ?Sized>(w: &mut W, byte: u8, count: usize) -> io::Result<()> {
+    for _ in 0..count {
+        w.write_all(&[byte])?;
+    }
+    Ok(())
+}
+
+/// Format a `%d`-style conversion: `%[[:]flags][width[.precision]][doxXs]`
+fn format_param<W: io::Write + ?Sized>(
+    spec: &[u8],
+    conversion: u8,
+    value: Value,
+    w: &mut W,
+) -> io::Result<()> {
+    let spec = spec.strip_prefix(b":").unwrap_or(spec);
+    let flags_len = spec.iter().take_while(|b| b"-+# ".contains(b)).count();
+    let (flags, rest) = spec.split_at(flags_len);
+    let rest = std::str::from_utf8(rest).unwrap_or("");
+    let (width, precision): (usize, Option<usize>) = match rest.split_once('.') {
+        Some((width, precision)) => (width.parse().unwrap_or(0), precision.parse().ok()),
+        None => (rest.parse().unwrap_or(0), None),
+    };
+
+    let mut digits = Digits::new();
+    // what goes in front of the digits, and how many zeros go between them
+    let (sign, prefix, zeros, body): (&[u8], &[u8], usize, &[u8]) = match (conversion, value) {
+        (b's', value) => {
+            let body = match value {
+                Value::Str(s) => s,
+                Value::Number(n) => {
+                    let _ = write!(digits, "{}", n);
+                    digits.as_slice()
+                }
+            };
+            let len = precision.map_or(body.len(), |precision| precision.min(body.len()));
+            (b"", b"", 0, &body[..len])
+        }
+        (_, value) => {
+            let n = value.as_number();
+            let _ = match conversion {
+                b'o' => write!(digits, "{:o}", n),
+                b'x' => write!(digits, "{:x}", n),
+                b'X' => write!(digits, "{:X}", n),
+                _ => write!(digits, "{}", n.unsigned_abs()),
+            };
+            let zeros = precision.map_or(0, |precision| precision.saturating_sub(digits.len));
+            let prefix: &[u8] = match conversion {
+                _ if !flags.contains(&b'#') => b"",
+                b'o' if zeros == 0 && digits.as_slice().first() != Some(&b'0') => b"0",
+                b'x' if n != 0 => b"0x",
+                b'X' if n != 0 => b"0X",
+                _ => b"",
+            };
+            let sign: &[u8] = match conversion {
+                b'd' if n < 0 => b"-",
+                b'd' if flags.contains(&b'+') => b"+",
+                b'd' if flags.contains(&b' ') => b" ",
+                _ => b"",
+            };
+            (sign, prefix, zeros, digits.as_slice())
+        }
+    };
+
+    let pad = width.saturating_sub(sign.len() + prefix.len() + zeros + body.len());
+    let left = flags.contains(&b'-');
+    if !left {
+        write_repeated(w, b' ', pad)?;
+    }
+    w.write_all(sign)?;
+    w.write_all(prefix)?;
+    write_repeated(w, b'0', zeros)?;
+    w.write_all(body)?;
+    if left {
+        write_repeated(w, b' ', pad)?;
+    }
+    Ok(())
+}
+
+/// Interpret the `%` parameter language used in string capabilities (like `cup`), with up to 9
+/// parameters. Static variables (`%P[A-Z]`) only live for a single call.
+pub fn tparm(cap: &[u8], params: &[Param]) -> io::Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(cap.len());
+    tparm_to(cap, params, false, &mut out)?;
+    Ok(out)
+}
+
+/// `tparm`, written straight into `w` without allocating, and leaving out `$<..>` delays if
+/// `strip_padding`. If it fails part way through, some of it has already been written.
+fn tparm_to<W: io::Write + ?Sized>(
+    cap: &[u8],
+    params: &[Param],
+    strip_padding: bool,
+    w: &mut W,
+) -> io::Result<()> {
+    let mut args = [Value::Number(0); 9];
+    for (arg, param) in args.iter_mut().zip(params) {
+        *arg = Value::new(param);
+    }
+    let mut stack = Stack {
+        values: [Value::Number(0); STACK_SIZE],
+        len: 0,
+    };
+    let mut vars = [None; 52];
+    let var_index = |b: u8| match b {
+        b'a'..=b'z' => Ok((b - b'a') as usize),
+        b'A'..=b'Z' => Ok((b - b'A') as usize + 26),
+        _ => Err(invalid("bad tparm variable name")),
+    };
+
+    let mut i = 0;
+    while i < cap.len() {
+        if cap[i] != b'%' {
+            if strip_padding && cap[i..].starts_with(b"$<") {
+                if let Some(end) = cap[i..].iter().position(|&b| b == b'>') {
+                    i += end + 1;
+                    continue;
+                }
+            }
+            w.write_all(&cap[i..i + 1])?;
+            i += 1;
+            continue;
+        }
+        let op = *cap
+            .get(i + 1)
+            .ok_or_else(|| invalid("tparm string ends in %"))?;
+        i += 2;
+        match op {
+            b'%' => w.write_all(b"%")?,
+            b'c' => w.write_all(&[stack.pop_number() as u8])?,
+            b'p' => {
+                let n = cap.get(i).map_or(0, |b| b.wrapping_sub(b'1')) as usize;
+                stack.push(args.get(n).copied().unwrap_or(Value::Number(0)))?;
+                i += 1;
+            }
+            b'P' => {
+                let index = var_index(*cap.get(i).unwrap_or(&0))?;
+                vars[index] = Some(stack.pop());
+                i += 1;
+            }
+            b'g' => {
+                let index = var_index(*cap.get(i).unwrap_or(&0))?;
+                stack.push(vars[index].unwrap_or(Value::Number(0)))?;
+                i += 1;
+            }
+            b'\'' => {
+                stack.push(Value::Number(*cap.get(i).unwrap_or(&0) as i32))?;
+                i += 2;
+            }
+            b'{' => {
+                let len = cap[i..].iter().position(|&b| b == b'}').unwrap_or(0);
+                let n = std::str::from_utf8(&cap[i..i + len])
+                    .ok()
+                    .and_then(|n| n.parse().ok())
+                    .unwrap_or(0);
+                stack.push(Value::Number(n))?;
+                i += len + 1;
+            }
+            b'l' => {
+                let len = stack.pop().len();
+                stack.push(Value::Number(len as i32))?;
+            }
+            b'+' | b'-' | b'*' | b'/' | b'm' | b'&' | b'|' | b'^' | b'=' | b'>' | b'<' | b'A'
+            | b'O' => {
+                let b = stack.pop_number();
+                let a = stack.pop_number();
+                stack.push(Value::Number(match op {
+                    b'+' => a.wrapping_add(b),
+                    b'-' => a.wrapping_sub(b),
+                    b'*' => a.wrapping_mul(b),
+                    b'/' => a.checked_div(b).unwrap_or(0),
+                    b'm' => a.checked_rem(b).unwrap_or(0),
+                    b'&' => a & b,
+                    b'|' => a | b,
+                    b'^' => a ^ b,
+                    b'=' => (a == b) as i32,
+                    b'>' => (a > b) as i32,
+                    b'<' => (a < b) as i32,
+                    b'A' => (a != 0 && b != 0) as i32,
+                    _ => (a != 0 || b != 0) as i32,
+                }))?;
+            }
+            b'!' => {
+                let a = stack.pop_number();
+                stack.push(Value::Number((a == 0) as i32))?;
+            }
+            b'~' => {
+                let a = stack.pop_number();
+                stack.push(Value::Number(!a))?;
+            }
+            b'i' => {
+                for arg in args.iter_mut().take(2) {
+                    if let Value::Number(n) = arg {
+                        *n = n.saturating_add(1);
+                    }
+                }
+            }
+            b'?' | b';' => (),
+            b't' => {
+                if stack.pop_number() == 0 {
+                    i = skip_branch(cap, i, true);
+                }
+            }
+            // we only get here by finishing a "then" part, so skip the else part
+            b'e' => i = skip_branch(cap, i, false),
+            _ => {
+                // a printf-style conversion, e.g. `%d`, `%02x` or `%:-3s`
+                let start = i - 1;
+                let len = cap[start..]
+                    .iter()
+                    .position(|b| b"doxXs".contains(b))
+                    .ok_or_else(|| invalid("unknown tparm operator"))?;
+                let conversion = cap[start + len];
+                format_param(&cap[start..start + len], conversion, stack.pop(), w)?;
+                i = start + len + 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// ncurses' own compiled xterm-256color, extended capabilities and all
+    const XTERM_256COLOR: &[u8] = include_bytes!("../../tests/terminfo/xterm-256color");
+
+    fn xterm() -> TermInfo {
+        TermInfo::from_bytes(XTERM_256COLOR).unwrap()
+    }
+
+    #[test]
+    fn parses_a_compiled_entry() {
+        let xterm = xterm();
+        assert_eq!(xterm.names[0], "xterm-256color");
+        assert!(xterm.flag("am"));
+        assert!(!xterm.flag("hz"));
+        assert_eq!(xterm.number("colors"), Some(256));
+        assert_eq!(xterm.string("smcup"), Some(&b"\x1b[?1049h\x1b[22;0;0t"[..]));
+        // extended
+        assert!(xterm.flag("AX"));
+        assert_eq!(xterm.string("kUP5"), Some(&b"\x1b[1;5A"[..]));
+        assert_eq!(xterm.string("Se"), Some(&b"\x1b[2 q"[..]));
+
+        assert!(TermInfo::from_bytes(b"not terminfo").is_err());
+        assert!(TermInfo::from_bytes(&XTERM_256COLOR[..100]).is_err());
+    }
+
+    #[test]
+    fn cup_counts_from_one() {
+        let xterm = xterm();
+        let cup = xterm.expand("cup", &[5.into(), 10.into()]).unwrap();
+        assert_eq!(cup, b"\x1b[6;11H");
+        let cup = xterm.expand("cup", &[i32::MAX.into(), 0.into()]).unwrap();
+        assert_eq!(cup, format!("\x1b[{};1H", i32::MAX).as_bytes());
+        assert_eq!(xterm.expand("nope", &[]), None);
+    }
+
+    #[test]
+    fn setaf_picks_a_branch() {
+        let xterm = xterm();
+        let setaf = |color: i32| xterm.expand("setaf", &[color.into()]).unwrap();
+        assert_eq!(setaf(1), b"\x1b[31m");
+        assert_eq!(setaf(9), b"\x1b[91m");
+        assert_eq!(setaf(100), b"\x1b[38;5;100m");
+    }
+
+    #[test]
+    fn if_then_else() {
+        let xterm = xterm();
+        assert_eq!(
+            xterm.expand("XM", &[1.into()]).unwrap(),
+            b"\x1b[?1006;1000h"
+        );
+        assert_eq!(
+            xterm.expand("XM", &[0.into()]).unwrap(),
+            b"\x1b[?1006;1000l"
+        );
+
+        let cap = b"%?%p1%t%p2%s%e%p1%d%;";
+        assert_eq!(tparm(cap, &[1.into(), "yes".into()]).unwrap(), b"yes");
+        assert_eq!(tparm(cap, &[0.into(), "yes".into()]).unwrap(), b"0");
+    }
+}
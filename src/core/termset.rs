@@ -1,19 +1,31 @@
 use crate::core::err::*;
+use crate::core::{
+    Color, StdoutEscSeq, TabStops, TerminalColor, TitleKind, TsStdout, UnsafeStdout,
+};
 use libc::{self, EBADF, ENOTTY};
 use nix::errno::errno;
+use std::io::{self, Write};
 use std::mem;
+use std::os::unix::io::RawFd;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[cfg(target_os = "linux")]
 pub struct Termset {
+    /// the terminal whose settings we change
+    fd: RawFd,
+    /// where the `on_restore` sequences go if we're dropped without `restore_through`
+    out_fd: RawFd,
     entry_config: Box<libc::termios>,
     config: libc::termios,
+    /// escape sequences to send when we're restored (or dropped), last first
+    on_restore: Vec<Vec<u8>>,
 }
 
-type TCSA = i32;
+type Tcsa = i32;
 
-pub const TCSA_NOW: TCSA = libc::TCSANOW as TCSA;
-pub const TCSA_DRAIN: TCSA = libc::TCSADRAIN as TCSA;
-pub const TCSA_FLUSH: TCSA = libc::TCSAFLUSH as TCSA;
+pub const TCSA_NOW: Tcsa = libc::TCSANOW as Tcsa;
+pub const TCSA_DRAIN: Tcsa = libc::TCSADRAIN as Tcsa;
+pub const TCSA_FLUSH: Tcsa = libc::TCSAFLUSH as Tcsa;
 
 type LFlag = u32;
 
@@ -24,9 +36,76 @@ pub const ICANON: LFlag = libc::ICANON as LFlag;
 /// suspend process on CTRL-C or CTRL-Z
 pub const ISIG: LFlag = libc::ISIG as LFlag;
 
+type OFlag = u32;
+
+/// output is post-processed (needed for `ONLCR`)
+pub const OPOST: OFlag = libc::OPOST as OFlag;
+/// `\n` is written out as `\r\n`
+pub const ONLCR: OFlag = libc::ONLCR as OFlag;
+
+/// Bumped whenever a `Termset` changes the terminal's settings, so anything that caches them
+/// (like `TsStdout` does for `ONLCR`) knows to look again
+static SETTINGS_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+pub(crate) fn settings_generation() -> usize {
+    SETTINGS_GENERATION.load(Ordering::Relaxed)
+}
+
+/// The size of the terminal window, as reported by `TIOCGWINSZ`. The pixel fields are `0` on
+/// terminals that don't report them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct WinSize {
+    pub rows: u16,
+    pub cols: u16,
+    pub x_pixels: u16,
+    pub y_pixels: u16,
+}
+
+/// Get the current size of the terminal. Tries stdout first and falls back to stdin, so this
+/// still works if one of them is redirected.
+pub fn window_size() -> io::Result<WinSize> {
+    window_size_of(libc::STDOUT_FILENO).or_else(|_| window_size_of(libc::STDIN_FILENO))
+}
+
+/// Get the current size of the terminal on `fd`
+pub fn window_size_of(fd: RawFd) -> io::Result<WinSize> {
+    let mut winsize: libc::winsize = unsafe { mem::zeroed() };
+    if unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut winsize as *mut libc::winsize) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(WinSize {
+        rows: winsize.ws_row,
+        cols: winsize.ws_col,
+        x_pixels: winsize.ws_xpixel,
+        y_pixels: winsize.ws_ypixel,
+    })
+}
+
+impl WinSize {
+    /// How big a character cell is, `(width, height)` in pixels, if the terminal reports its
+    /// size in pixels
+    pub fn cell_size(&self) -> Option<(u16, u16)> {
+        if self.rows == 0 || self.cols == 0 || self.x_pixels == 0 || self.y_pixels == 0 {
+            return None;
+        }
+        Some((self.x_pixels / self.cols, self.y_pixels / self.rows))
+    }
+}
+
 impl Termset {
     /// TODO: docs
     pub fn new() -> Result<Self, TermsetCreationError> {
+        Self::from_fds(libc::STDIN_FILENO, libc::STDOUT_FILENO)
+    }
+
+    /// Change the settings of the terminal on `fd` instead of stdin, and send anything that
+    /// needs undoing there too, e.g. for one opened from `/dev/tty` when stdin and stdout are
+    /// redirected. Goes with `TsStdout::from_fd`.
+    pub fn from_fd(fd: RawFd) -> Result<Self, TermsetCreationError> {
+        Self::from_fds(fd, fd)
+    }
+
+    fn from_fds(fd: RawFd, out_fd: RawFd) -> Result<Self, TermsetCreationError> {
         let termios = unsafe {
             // using things from <termios.h> here. <termios.h> defines the structure of the
             // termios file, which provides the terminal interface for POSIX compatibility. My
@@ -34,7 +113,7 @@ impl Termset {
             // which you can modify to change the way the terminal works. This is just the POSIX
             // compliant way of doing this
             let mut termios = mem::MaybeUninit::uninit();
-            let e = libc::tcgetattr(libc::STDIN_FILENO, termios.as_mut_ptr());
+            let e = libc::tcgetattr(fd, termios.as_mut_ptr());
             if e == -1 {
                 return Err(match errno() {
                     EBADF => TermsetCreationError::BadFileDescriptor,
@@ -47,33 +126,38 @@ impl Termset {
         };
 
         Ok(Termset {
+            fd,
+            out_fd,
             entry_config: Box::new(termios),
             config: termios,
+            on_restore: Vec::new(),
         })
     }
 
     /// Push updates to the terminal, optional_actions is an optional bitset
     /// Find out about the actions https://www.ibm.com/docs/en/aix/7.2?topic=files-termiosh-file
-    pub fn update(&self, optional_actions: Option<TCSA>) {
+    pub fn update(&self, optional_actions: Option<Tcsa>) {
         let optional_actions = optional_actions.unwrap_or(TCSA_FLUSH);
         unsafe {
             libc::tcsetattr(
-                libc::STDIN_FILENO,
+                self.fd,
                 optional_actions as libc::c_int,
                 &self.config as *const libc::termios,
             );
         }
+        SETTINGS_GENERATION.fetch_add(1, Ordering::Relaxed);
     }
 
     /// Reset the terminal to its entry settings
     pub fn reset(&self) {
         unsafe {
             libc::tcsetattr(
-                libc::STDIN_FILENO,
+                self.fd,
                 libc::TCSANOW,
                 &*self.entry_config as *const libc::termios,
             );
         }
+        SETTINGS_GENERATION.fetch_add(1, Ordering::Relaxed);
     }
 
     // /// register a signal hook to restore the terminal on `SIGINT`
@@ -85,16 +169,101 @@ impl Termset {
 
     /// Restore instantly, consuming this object
     pub fn restore(self) {
-        self.reset();
+        drop(self);
+    }
+
+    /// `restore`, but anything still in `stdout`'s buffer goes out first, then whatever
+    /// `restore_with` etc. need to undo goes out through `stdout`'s writer
+    pub fn restore_through<W: io::Write>(mut self, stdout: &mut TsStdout<W>) {
+        stdout.flush();
+        let writer = stdout.writer_mut();
+        for seq in self.on_restore.drain(..).rev() {
+            // nothing we can do about it if this fails
+            let _ = writer.write_all(&seq);
+        }
+        let _ = writer.flush();
+        // `drop` does the rest
+    }
+
+    /// Send `seq` when the terminal is restored, to undo something you've changed. These go
+    /// out in the reverse order they were added.
+    ///
+    /// Unless you use `restore_through`, they're written straight to stdout (or the terminal
+    /// from `from_fd`), so flush your `TsStdout` first or they'll come out before whatever
+    /// it's still holding on to.
+    pub fn restore_with(&mut self, seq: &StdoutEscSeq) {
+        self.on_restore.push(seq.as_bytes());
+    }
+
+    /// Save the window title and icon name on the terminal's title stack now, and pop them
+    /// back off when the terminal is restored, so whatever you set the title to doesn't stick
+    /// around after you exit. Terminals without a title stack ignore this.
+    ///
+    /// ```rs
+    /// termset.save_title(&mut stdout);
+    /// stdout.exec([&StdoutEscSeq::SetTitle("editing main.rs")]);
+    /// ```
+    pub fn save_title<W: io::Write>(&mut self, stdout: &mut TsStdout<W>) {
+        stdout
+            .exec([&StdoutEscSeq::PushTitle(TitleKind::Both)])
+            .flush();
+        self.restore_with(&StdoutEscSeq::PopTitle(TitleKind::Both));
+    }
+
+    /// Change one of the terminal's colors now, and put it back how the user had it when the
+    /// terminal is restored
+    ///
+    /// ```rs
+    /// termset.set_color(&mut stdout, TerminalColor::Background, Color::Rgb(0x1d, 0x20, 0x21));
+    /// ```
+    pub fn set_color<W: io::Write>(
+        &mut self,
+        stdout: &mut TsStdout<W>,
+        color: TerminalColor,
+        value: Color,
+    ) {
+        stdout.exec([&StdoutEscSeq::SetColor(color, value)]).flush();
+        let reset = StdoutEscSeq::ResetColor(color).as_bytes();
+        if !self.on_restore.contains(&reset) {
+            self.on_restore.push(reset);
+        }
+    }
+
+    /// Set the terminal's tab stops now (see `TsStdout::set_tab_stops`), and put the usual
+    /// ones back every 8 columns when the terminal is restored. There's no way to ask what
+    /// they were before, so that's the best we can do.
+    pub fn set_tab_stops<W: io::Write>(&mut self, stdout: &mut TsStdout<W>, stops: &TabStops) {
+        stdout.set_tab_stops(stops).flush();
+        let (rows, cols) = stdout.size();
+        let mut reset = TsStdout::with_writer(Vec::new());
+        reset
+            .set_size(rows, cols)
+            .set_tab_stops(&TabStops::new(cols));
+        // writing to a `Vec` can't fail
+        let Ok(reset) = reset.into_writer() else {
+            return;
+        };
+        if !self.on_restore.contains(&reset) {
+            self.on_restore.push(reset);
+        }
     }
 
     pub fn disable_lflag(&mut self, flags: LFlag) {
         self.config.c_lflag &= !flags;
     }
+
+    pub fn disable_oflag(&mut self, flags: OFlag) {
+        self.config.c_oflag &= !flags;
+    }
 }
 
 impl Drop for Termset {
+    /// Without a `TsStdout` to go through, see `restore_with`
     fn drop(&mut self) {
+        for seq in self.on_restore.drain(..).rev() {
+            // nothing we can do about it if this fails
+            let _ = UnsafeStdout::from_fd(self.out_fd).write_all(&seq);
+        }
         self.reset();
     }
 }
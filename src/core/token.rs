@@ -1,4 +1,11 @@
-use libc::c_void;
+use std::fmt;
+use std::io;
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+use libc::{c_int, c_void};
+
+use super::{base64, Color, KittyCommand, Param, Style, TermInfo, Urgency};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum StdinEscSeq {
@@ -6,69 +13,851 @@ pub enum StdinEscSeq {
     MoveRight(u32),
     MoveDown(u32),
     MoveLeft(u32),
+    /// Ctrl or Alt + Left
+    WordLeft,
+    /// Ctrl or Alt + Right
+    WordRight,
+    Home,
+    End,
+    Insert,
+    Delete,
+    PageUp,
+    PageDown,
+    /// `ESC` followed by a char, which is what most terminals send for Alt + that char
+    Alt(char),
+    /// The Escape key on its own. Since it's also how every escape sequence starts, this only
+    /// turns up once nothing else has followed it for a while, see `TokenReader::flush_esc`.
+    Escape,
+    /// A well-formed escape sequence that we don't recognise (yet), or bytes that aren't
+    /// UTF-8. The bytes are dropped.
+    Unknown,
 }
 
 impl StdinEscSeq {
     /// Tries to convert this to an `StdoutEscSeq` -- what this means might not be obvious...
     /// An example of one that *is* obvious would be `MoveUp(u32)`, which is translated to
     /// the StdoutEscSeq of the same name
-    pub fn as_stdout_esc_seq(&self) -> Option<StdoutEscSeq> {
+    pub fn as_stdout_esc_seq(&self) -> Option<StdoutEscSeq<'static>> {
         match *self {
             Self::MoveUp(count) => Some(StdoutEscSeq::MoveUp(count)),
             Self::MoveRight(count) => Some(StdoutEscSeq::MoveRight(count)),
             Self::MoveDown(count) => Some(StdoutEscSeq::MoveDown(count)),
             Self::MoveLeft(count) => Some(StdoutEscSeq::MoveLeft(count)),
-            // _ => None,
+            _ => None,
+        }
+    }
+}
+
+/// The selection an OSC 52 clipboard sequence reads or writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ClipboardSelection {
+    /// The system clipboard (`c`), this is almost certainly the one you want
+    Clipboard,
+    /// The primary selection (`p`), i.e. middle-click paste on X11
+    Primary,
+    /// The secondary selection (`s`), which basically nothing uses
+    Secondary,
+}
+
+impl ClipboardSelection {
+    pub fn as_byte(&self) -> u8 {
+        match *self {
+            Self::Clipboard => b'c',
+            Self::Primary => b'p',
+            Self::Secondary => b's',
+        }
+    }
+
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'c' => Some(Self::Clipboard),
+            b'p' => Some(Self::Primary),
+            b's' => Some(Self::Secondary),
+            _ => None,
+        }
+    }
+}
+
+/// Cursor shapes for `StdoutEscSeq::SetCursorStyle` (DECSCUSR)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CursorStyle {
+    /// Whatever the user has configured
+    Default,
+    BlinkingBlock,
+    SteadyBlock,
+    BlinkingUnderline,
+    SteadyUnderline,
+    BlinkingBar,
+    SteadyBar,
+}
+
+impl CursorStyle {
+    /// The DECSCUSR parameter for this style
+    pub fn as_param(&self) -> u32 {
+        match *self {
+            Self::Default => 0,
+            Self::BlinkingBlock => 1,
+            Self::SteadyBlock => 2,
+            Self::BlinkingUnderline => 3,
+            Self::SteadyUnderline => 4,
+            Self::BlinkingBar => 5,
+            Self::SteadyBar => 6,
+        }
+    }
+}
+
+/// Which titles `StdoutEscSeq::PushTitle` and `PopTitle` save or restore (XTWINOPS 22/23)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TitleKind {
+    /// The window title and the icon name
+    Both,
+    IconName,
+    WindowTitle,
+}
+
+impl TitleKind {
+    /// The XTWINOPS parameter for this
+    pub fn as_param(&self) -> u32 {
+        match *self {
+            Self::Both => 0,
+            Self::IconName => 1,
+            Self::WindowTitle => 2,
+        }
+    }
+}
+
+/// One of the terminal's colors, which can be asked about and changed with
+/// `StdoutEscSeq::RequestColor` and `SetColor` (OSC 4, 10, 11 and 12)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TerminalColor {
+    /// The default text color
+    Foreground,
+    /// The default background color
+    Background,
+    Cursor,
+    /// An entry in the 256 color palette, i.e. what `Color::Indexed` (or one of the named
+    /// colors) looks like
+    Palette(u8),
+}
+
+/// Writes the OSC command (and palette index) for the color, e.g. `11` or `4;1`
+impl SeqPart for TerminalColor {
+    fn write_to<W: io::Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        match *self {
+            Self::Foreground => w.write_all(b"10"),
+            Self::Background => w.write_all(b"11"),
+            Self::Cursor => w.write_all(b"12"),
+            Self::Palette(i) => {
+                w.write_all(b"4;")?;
+                (i as u32).write_to(w)
+            }
         }
     }
 }
 
+/// Read an X11 color spec like `rgb:ffff/8080/0000` (1 to 4 hex digits per channel), which is
+/// how terminals reply to `StdoutEscSeq::RequestColor`. `rgba:` is fine too, the alpha is
+/// ignored.
+pub(crate) fn parse_rgb_spec(spec: &[u8]) -> Option<(u8, u8, u8)> {
+    let channels = spec
+        .strip_prefix(b"rgb:")
+        .or_else(|| spec.strip_prefix(b"rgba:"))?;
+    let mut channels = channels.split(|&b| b == b'/').map(|channel| {
+        if !(1..=4).contains(&channel.len()) {
+            return None;
+        }
+        let value = u32::from_str_radix(std::str::from_utf8(channel).ok()?, 16).ok()?;
+        let max = (1 << (4 * channel.len())) - 1;
+        Some(((value * 255 + max / 2) / max) as u8)
+    });
+    Some((channels.next()??, channels.next()??, channels.next()??))
+}
+
+/// An RGB color as an X11 color spec (`rgb:rr/gg/bb`), for `StdoutEscSeq::SetColor`
+struct RgbSpec((u8, u8, u8));
+
+impl SeqPart for RgbSpec {
+    fn write_to<W: io::Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        let (r, g, b) = self.0;
+        write!(w, "rgb:{:02x}/{:02x}/{:02x}", r, g, b)
+    }
+}
+
+/// What the terminal said about a mode in its reply to `StdoutEscSeq::RequestPrivateMode`
+/// (DECRPM)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ModeState {
+    NotRecognized,
+    Set,
+    Reset,
+    /// Always on, and can't be turned off
+    PermanentlySet,
+    /// Always off, and can't be turned on
+    PermanentlyReset,
+}
+
+impl ModeState {
+    fn from_param(param: u32) -> Self {
+        match param {
+            1 => Self::Set,
+            2 => Self::Reset,
+            3 => Self::PermanentlySet,
+            4 => Self::PermanentlyReset,
+            _ => Self::NotRecognized,
+        }
+    }
+
+    /// Whether the terminal knows about the mode and lets us use it
+    pub fn is_supported(&self) -> bool {
+        matches!(self, Self::Set | Self::Reset | Self::PermanentlySet)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub enum StdoutEscSeq {
+pub enum StdoutEscSeq<'a> {
     MoveUp(u32),
     MoveRight(u32),
     MoveDown(u32),
     MoveLeft(u32),
+    /// Move to `(row, col)`, counting from `(0, 0)` at the top left (CUP)
+    MoveTo(u32, u32),
+    /// Move to a column on the current row, counting from `0` (CHA)
+    MoveToColumn(u32),
+    /// Move to a row, staying in the same column, counting from `0` (VPA)
+    MoveToRow(u32),
+    /// Move to the start of the line `n` lines down (CNL)
+    MoveToNextLine(u32),
+    /// Move to the start of the line `n` lines up (CPL)
+    MoveToPreviousLine(u32),
+    /// DECTCEM
+    HideCursor,
+    /// DECTCEM
+    ShowCursor,
+    /// DECSCUSR
+    SetCursorStyle(CursorStyle),
+    /// Switch to the alternate screen, saving the cursor and clearing it first (`?1049h`). This
+    /// is what full-screen programs want.
+    EnterAlternateScreen,
+    /// Back to the main screen, with the cursor where it was before `EnterAlternateScreen`
+    /// (`?1049l`)
+    LeaveAlternateScreen,
+    /// Switch to the alternate screen without saving the cursor or clearing it (`?1047h`)
+    SwitchToAlternateScreen,
+    /// Clear the alternate screen and switch back to the main one (`?1047l`)
+    SwitchToMainScreen,
+    /// The original alternate screen switch (`?47h`). Lots of terminals don't clear the screen
+    /// or save the cursor for this, use `EnterAlternateScreen` instead.
     SaveScreen,
+    /// Undoes `SaveScreen` (`?47l`)
     RestoreScreen,
+    /// Only scroll the rows from `top` to `bottom` (inclusive, counting from `0`) when text
+    /// goes off the end, or for `ScrollUp`/`InsertLines` etc. (DECSTBM). This also moves the
+    /// cursor to the top left.
+    SetScrollRegion(u32, u32),
+    /// Scroll the whole screen again (DECSTBM with no margins)
+    ResetScrollRegion,
+    /// Scroll the contents of the scroll region up `n` lines, adding blank ones at the bottom
+    /// (SU)
+    ScrollUp(u32),
+    /// Scroll the contents of the scroll region down `n` lines, adding blank ones at the top
+    /// (SD)
+    ScrollDown(u32),
+    /// Insert `n` blank lines at the cursor, pushing the ones below down (IL)
+    InsertLines(u32),
+    /// Delete `n` lines at the cursor, pulling the ones below up (DL)
+    DeleteLines(u32),
+    /// Insert `n` blank characters at the cursor, pushing the rest of the line right (ICH)
+    InsertChars(u32),
+    /// Delete `n` characters at the cursor, pulling the rest of the line left (DCH)
+    DeleteChars(u32),
+    /// Blank out `n` characters from the cursor, without moving anything (ECH)
+    EraseChars(u32),
+    /// Set a tab stop in the cursor's column (HTS, `ESC H`)
+    SetTabStop,
+    /// Clear the tab stop in the cursor's column, if there is one (TBC, `CSI g`)
+    ClearTabStop,
+    /// Clear every tab stop, after which tabs go straight to the last column (TBC, `CSI 3g`)
+    ClearAllTabStops,
+    /// Move the cursor forward `n` tab stops, or to the last column if there aren't that many
+    /// (CHT). Like writing `\t` `n` times, which is safer since some terminals (tmux for one)
+    /// ignore this.
+    TabForward(u32),
+    /// Move the cursor back `n` tab stops, or to the first column if there aren't that many
+    /// (CBT)
+    TabBackward(u32),
+    /// Save the cursor position, and the attributes and charset too (DECSC, `ESC 7`). There is
+    /// only one save slot, shared with the alternate screen on some terminals.
     SaveCursorPosition,
+    /// Restore what `SaveCursorPosition` saved (DECRC, `ESC 8`)
     RestorCursorPosition,
+    /// Save just the cursor position, the SCO way (`CSI s`). Not all terminals support this
+    /// and it clashes with left/right margin mode, so prefer `SaveCursorPosition`
+    ScoSaveCursorPosition,
+    /// Restore what `ScoSaveCursorPosition` saved (`CSI u`)
+    ScoRestoreCursorPosition,
+    /// Set the foreground color (SGR 30-37, 90-97, 38)
+    SetForeground(Color),
+    /// Set the background color (SGR 40-47, 100-107, 48)
+    SetBackground(Color),
+    /// Set the color of underlines (SGR 58), which not every terminal supports
+    SetUnderlineColor(Color),
+    /// Reset everything and then set exactly this style, even through `TsStdout::exec`. To
+    /// only send what changed, use `Style::diff` or `TsStdout::set_style`.
+    SetStyle(Style),
+    /// Back to the default style (SGR 0)
+    ResetStyle,
     EraseEntireScreen,
+    /// Erase from the cursor to the end of the screen
+    EraseToEndOfScreen,
+    /// Erase from the start of the screen up to (and including) the cursor
+    EraseToStartOfScreen,
+    /// Clear the scrollback, leaving what's on the screen (`3J`, an xterm extension)
+    EraseScrollback,
+    /// Erase from the cursor to the end of the line
+    EraseToEndOfLine,
+    /// Erase from the start of the line up to (and including) the cursor
+    EraseToStartOfLine,
+    EraseEntireLine,
+    /// Set the window title (OSC 2). Control characters are left out, since they'd end the
+    /// sequence early.
+    SetTitle(&'a str),
+    /// Set the icon name (OSC 1), which is what some window managers show in task bars and
+    /// tabs. Most terminals treat this the same as the title nowadays.
+    SetIconName(&'a str),
+    /// Set the window title and the icon name together (OSC 0)
+    SetTitleAndIconName(&'a str),
+    /// Save the title (and/or icon name) on the terminal's title stack (XTWINOPS 22), so
+    /// `PopTitle` can put it back later. Not every terminal has a title stack, see
+    /// `Termset::save_title`.
+    PushTitle(TitleKind),
+    /// Restore the title (and/or icon name) from the title stack (XTWINOPS 23)
+    PopTitle(TitleKind),
+    /// Ring the bell (`BEL`)
+    Bell,
+    /// Have the bell set the window's urgency hint too, so the window manager flags it up
+    /// (`?1042h`, xterm)
+    EnableUrgentBell,
+    /// Undoes `EnableUrgentBell` (`?1042l`)
+    DisableUrgentBell,
+    /// Pop up a desktop notification with OSC 9 (`title` and `body`). This only has room for
+    /// one line, so the title goes in front of the body. ConEmu reads a body starting with a
+    /// number and `;` as one of its own commands.
+    Notify(&'a str, &'a str),
+    /// Pop up a desktop notification with OSC 777 `notify` (`title` and `body`). `;` is left
+    /// out of the title, since it would end it.
+    NotifyOsc777(&'a str, &'a str),
+    /// Pop up a desktop notification with kitty's OSC 99 (`id`, `title`, `body`, urgency). The
+    /// text is sent as base64 in as many chunks as it needs, and the notification shows up
+    /// after the last one. `id` should be different for each notification.
+    NotifyKitty(u32, &'a str, &'a str, Urgency),
+    /// A kitty graphics protocol command. `TsStdout` wraps these up for tmux when it needs to
+    /// (see `TsStdout::set_tmux_passthrough`), writing them any other way doesn't.
+    Kitty(KittyCommand<'a>),
+    /// Start a hyperlink to `uri` (OSC 8), which everything written until `EndHyperlink` is
+    /// part of. Cells with the same `id` and `uri` highlight together when hovered, even if
+    /// they aren't next to each other. Bytes that aren't allowed in the sequence get
+    /// percent-encoded. Terminals that don't support it just show the text.
+    BeginHyperlink(Option<&'a str>, &'a str),
+    /// End the current hyperlink (OSC 8 with no URI)
+    EndHyperlink,
+    /// Put some text on the clipboard with OSC 52. This works over SSH, but some terminals
+    /// have it turned off (or limit the size) by default
+    SetClipboard(ClipboardSelection, &'a str),
+    /// Hold off drawing anything until `EndSynchronizedUpdate`, so the user never sees half
+    /// a frame (`?2026h`). Terminals that don't support it just ignore it.
+    BeginSynchronizedUpdate,
+    /// Draw everything since `BeginSynchronizedUpdate` (`?2026l`)
+    EndSynchronizedUpdate,
+    /// The older DCS version of `BeginSynchronizedUpdate` (`DCS = 1 s ST`), for terminals
+    /// from before mode 2026
+    BeginSynchronizedUpdateLegacy,
+    /// The older DCS version of `EndSynchronizedUpdate` (`DCS = 2 s ST`)
+    EndSynchronizedUpdateLegacy,
+    /// Ask where the cursor is (DSR 6), the reply comes back as `Token::CursorPosition`
+    RequestCursorPosition,
+    /// Ask whether a private (`?`) mode is supported and set (DECRQM). The reply comes back as
+    /// `Token::ModeReport`, if the terminal knows about DECRQM at all.
+    RequestPrivateMode(u32),
+    /// Ask the terminal for the contents of the clipboard with OSC 52, the reply comes back as
+    /// `Token::Clipboard`. Lots of terminals don't allow this, in which case you get nothing.
+    /// Replies that don't fit in the `TokenReader`'s buffer (`BUF_SIZE`) are thrown away.
+    RequestClipboard(ClipboardSelection),
+    /// Ask the terminal what one of its colors is set to, the reply comes back as
+    /// `Token::ColorReport`. Not every terminal answers, see `TsStdout::query_color`.
+    RequestColor(TerminalColor),
+    /// Change one of the terminal's colors, to the RGB value of the `Color` (see
+    /// `Color::to_rgb`). This sticks around after we exit, so use `Termset::set_color` unless
+    /// you're resetting it yourself.
+    SetColor(TerminalColor, Color),
+    /// Put one of the terminal's colors back how the user had it (OSC 104, 110, 111 and 112)
+    ResetColor(TerminalColor),
+}
+
+/// Format `n` in decimal into `digits`, without touching the heap
+pub(crate) fn decimal(n: u32, digits: &mut [u8; 10]) -> &[u8] {
+    let mut n = n;
+    let mut i = digits.len();
+    loop {
+        i -= 1;
+        digits[i] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    &digits[i..]
+}
+
+/// Something that can go in the middle of an escape sequence
+trait SeqPart {
+    fn write_to<W: io::Write + ?Sized>(&self, w: &mut W) -> io::Result<()>;
+}
+
+impl SeqPart for u32 {
+    fn write_to<W: io::Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(decimal(*self, &mut [0; 10]))
+    }
+}
+
+impl SeqPart for char {
+    fn write_to<W: io::Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(self.encode_utf8(&mut [0; 4]).as_bytes())
+    }
+}
+
+impl SeqPart for &str {
+    fn write_to<W: io::Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(self.as_bytes())
+    }
+}
+
+/// Text in an OSC sequence with the control characters (C0 and C1) left out, since they'd end
+/// it early or mess it up
+struct WithoutControls<'a>(&'a str);
+
+impl SeqPart for WithoutControls<'_> {
+    fn write_to<W: io::Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        for chunk in self.0.split(|c: char| c.is_control()) {
+            w.write_all(chunk.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+/// Like `WithoutControls`, but leaving out `;` too, for the parts of OSC sequences that are
+/// followed by another parameter
+struct WithoutSeparators<'a>(&'a str);
+
+impl SeqPart for WithoutSeparators<'_> {
+    fn write_to<W: io::Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        for chunk in self.0.split(|c: char| c.is_control() || c == ';') {
+            w.write_all(chunk.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+/// The most bytes of text (before base64) we put in one OSC 99 chunk
+const KITTY_NOTIFICATION_CHUNK: usize = 1024;
+
+/// Write an OSC 99 notification, splitting the title and body into chunks (on `char`
+/// boundaries, so each one decodes to valid UTF-8)
+fn write_kitty_notification<W: io::Write + ?Sized>(
+    w: &mut W,
+    id: u32,
+    title: &str,
+    body: &str,
+    urgency: Urgency,
+) -> io::Result<()> {
+    let parts = [("title", title), ("body", body)];
+    for (part, text) in parts {
+        if text.is_empty() && part == "title" {
+            continue;
+        }
+        let mut rest = text;
+        loop {
+            let mut len = rest.len().min(KITTY_NOTIFICATION_CHUNK);
+            while !rest.is_char_boundary(len) {
+                len -= 1;
+            }
+            let (chunk, after) = rest.split_at(len);
+            rest = after;
+            // `d=1` on the very last chunk shows the notification
+            let done = (part == "body" && rest.is_empty()) as u32;
+            w.write_all(&OSC_PREFIX)?;
+            write!(
+                w,
+                "99;i={}:e=1:d={}:u={}:p={};",
+                id,
+                done,
+                urgency.as_param(),
+                part
+            )?;
+            base64::encode_to(chunk.as_bytes(), w)?;
+            w.write_all(&[BEL_ASCII])?;
+            if rest.is_empty() {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Text in an OSC sequence with the bytes that would end it (or break it up) percent-encoded:
+/// controls, space, anything non-ASCII and whatever's in `reserved`
+struct PercentEncoded<'a> {
+    s: &'a str,
+    reserved: &'static [u8],
+}
+
+impl SeqPart for PercentEncoded<'_> {
+    fn write_to<W: io::Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        const HEX: &[u8; 16] = b"0123456789ABCDEF";
+        let bytes = self.s.as_bytes();
+        let mut start = 0;
+        for (i, &b) in bytes.iter().enumerate() {
+            if (b'!'..=b'~').contains(&b) && !self.reserved.contains(&b) {
+                continue;
+            }
+            w.write_all(&bytes[start..i])?;
+            w.write_all(&[b'%', HEX[(b >> 4) as usize], HEX[(b & 0xf) as usize]])?;
+            start = i + 1;
+        }
+        w.write_all(&bytes[start..])
+    }
 }
 
 macro_rules! esc_seq {
-    [$($value:expr),*] => {
+    [$w:expr; $($value:expr),*] => {
         {
-            let mut seq = vec![];
-            seq.extend(&ESC_SEQ_PREFIX);
+            $w.write_all(&ESC_SEQ_PREFIX)?;
             $(
-                seq.extend(format!("{}", $value).as_bytes());
+                SeqPart::write_to(&$value, $w)?;
             )*
-            seq
+            Ok(())
         }
     };
 }
 
-impl StdoutEscSeq {
+macro_rules! osc_seq {
+    [$w:expr; $($value:expr),*] => {
+        {
+            $w.write_all(&OSC_PREFIX)?;
+            $(
+                SeqPart::write_to(&$value, $w)?;
+            )*
+            $w.write_all(&[BEL_ASCII])
+        }
+    };
+}
+
+impl<'a> StdoutEscSeq<'a> {
+    /// The bytes for sequences that don't take any parameters, `None` for the ones that do.
+    /// This works in `const`s:
+    ///
+    /// ```rs
+    /// const HIDE_CURSOR: &[u8] = match StdoutEscSeq::HideCursor.static_bytes() {
+    ///     Some(bytes) => bytes,
+    ///     None => unreachable!(),
+    /// };
+    /// ```
+    pub const fn static_bytes(&self) -> Option<&'static [u8]> {
+        let bytes: &'static [u8] = match *self {
+            Self::HideCursor => b"\x1b[?25l",
+            Self::ShowCursor => b"\x1b[?25h",
+            Self::EnterAlternateScreen => b"\x1b[?1049h",
+            Self::LeaveAlternateScreen => b"\x1b[?1049l",
+            Self::SwitchToAlternateScreen => b"\x1b[?1047h",
+            Self::SwitchToMainScreen => b"\x1b[?1047l",
+            Self::SaveScreen => b"\x1b[?47h",
+            Self::RestoreScreen => b"\x1b[?47l",
+            Self::ResetScrollRegion => b"\x1b[r",
+            Self::SaveCursorPosition => b"\x1b7",
+            Self::RestorCursorPosition => b"\x1b8",
+            Self::SetTabStop => b"\x1bH",
+            Self::ClearTabStop => b"\x1b[g",
+            Self::ClearAllTabStops => b"\x1b[3g",
+            Self::ScoSaveCursorPosition => b"\x1b[s",
+            Self::ScoRestoreCursorPosition => b"\x1b[u",
+            Self::ResetStyle => b"\x1b[0m",
+            Self::EraseEntireScreen => b"\x1b[2J",
+            Self::EraseToEndOfScreen => b"\x1b[J",
+            Self::EraseToStartOfScreen => b"\x1b[1J",
+            Self::EraseScrollback => b"\x1b[3J",
+            Self::EraseToEndOfLine => b"\x1b[K",
+            Self::EraseToStartOfLine => b"\x1b[1K",
+            Self::EraseEntireLine => b"\x1b[2K",
+            Self::BeginSynchronizedUpdate => b"\x1b[?2026h",
+            Self::EndSynchronizedUpdate => b"\x1b[?2026l",
+            Self::BeginSynchronizedUpdateLegacy => b"\x1bP=1s\x1b\\",
+            Self::EndSynchronizedUpdateLegacy => b"\x1bP=2s\x1b\\",
+            Self::RequestCursorPosition => b"\x1b[6n",
+            Self::EndHyperlink => b"\x1b]8;;\x07",
+            Self::Bell => b"\x07",
+            Self::EnableUrgentBell => b"\x1b[?1042h",
+            Self::DisableUrgentBell => b"\x1b[?1042l",
+            Self::ResetColor(TerminalColor::Foreground) => b"\x1b]110\x07",
+            Self::ResetColor(TerminalColor::Background) => b"\x1b]111\x07",
+            Self::ResetColor(TerminalColor::Cursor) => b"\x1b]112\x07",
+            _ => return None,
+        };
+        Some(bytes)
+    }
+
+    /// Write the sequence straight into `w`, without allocating anything
+    pub fn write_to<W: io::Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        if let Some(bytes) = self.static_bytes() {
+            return w.write_all(bytes);
+        }
+        match *self {
+            Self::MoveUp(count) => esc_seq![w; count, 'A'],
+            Self::MoveRight(count) => esc_seq![w; count, 'C'],
+            Self::MoveDown(count) => esc_seq![w; count, 'B'],
+            Self::MoveLeft(count) => esc_seq![w; count, 'D'],
+            Self::MoveTo(row, col) => {
+                esc_seq![w; row.saturating_add(1), ';', col.saturating_add(1), 'H']
+            }
+            Self::MoveToColumn(col) => esc_seq![w; col.saturating_add(1), 'G'],
+            Self::MoveToRow(row) => esc_seq![w; row.saturating_add(1), 'd'],
+            Self::MoveToNextLine(count) => esc_seq![w; count, 'E'],
+            Self::MoveToPreviousLine(count) => esc_seq![w; count, 'F'],
+            Self::SetCursorStyle(style) => esc_seq![w; style.as_param(), " q"],
+            Self::SetScrollRegion(top, bottom) => {
+                esc_seq![w; top.saturating_add(1), ';', bottom.saturating_add(1), 'r']
+            }
+            Self::ScrollUp(count) => esc_seq![w; count, 'S'],
+            Self::ScrollDown(count) => esc_seq![w; count, 'T'],
+            Self::InsertLines(count) => esc_seq![w; count, 'L'],
+            Self::DeleteLines(count) => esc_seq![w; count, 'M'],
+            Self::InsertChars(count) => esc_seq![w; count, '@'],
+            Self::DeleteChars(count) => esc_seq![w; count, 'P'],
+            Self::EraseChars(count) => esc_seq![w; count, 'X'],
+            Self::TabForward(count) => esc_seq![w; count, 'I'],
+            Self::TabBackward(count) => esc_seq![w; count, 'Z'],
+            Self::SetForeground(color) => Style::new().write_diff_to(&Style::new().fg(color), w),
+            Self::SetBackground(color) => Style::new().write_diff_to(&Style::new().bg(color), w),
+            Self::SetUnderlineColor(color) => {
+                Style::new().write_diff_to(&Style::new().underline_color(color), w)
+            }
+            Self::SetStyle(style) => style.write_to(w),
+            Self::SetClipboard(selection, text) => {
+                w.write_all(&OSC_PREFIX)?;
+                w.write_all(&[b'5', b'2', b';', selection.as_byte(), b';'])?;
+                base64::encode_to(text.as_bytes(), w)?;
+                w.write_all(&[BEL_ASCII])
+            }
+            Self::SetTitle(title) => osc_seq![w; "2;", WithoutControls(title)],
+            Self::SetIconName(name) => osc_seq![w; "1;", WithoutControls(name)],
+            Self::SetTitleAndIconName(title) => osc_seq![w; "0;", WithoutControls(title)],
+            Self::PushTitle(kind) => esc_seq![w; "22;", kind.as_param(), 't'],
+            Self::PopTitle(kind) => esc_seq![w; "23;", kind.as_param(), 't'],
+            Self::Notify("", body) => {
+                osc_seq![w; "9;", WithoutControls(body)]
+            }
+            Self::Notify(title, body) => {
+                osc_seq![w; "9;", WithoutControls(title), ": ", WithoutControls(body)]
+            }
+            Self::NotifyOsc777(title, body) => {
+                osc_seq![w; "777;notify;", WithoutSeparators(title), ';', WithoutControls(body)]
+            }
+            Self::NotifyKitty(id, title, body, urgency) => {
+                write_kitty_notification(w, id, title, body, urgency)
+            }
+            Self::Kitty(command) => command.write_to(w, false),
+            Self::BeginHyperlink(id, uri) => {
+                let uri = PercentEncoded {
+                    s: uri,
+                    reserved: &[],
+                };
+                match id {
+                    // `:` and `=` separate the parameters, and `;` ends them
+                    Some(id) => {
+                        osc_seq![w; "8;id=", PercentEncoded { s: id, reserved: b":;=" }, ';', uri]
+                    }
+                    None => osc_seq![w; "8;;", uri],
+                }
+            }
+            Self::RequestPrivateMode(mode) => esc_seq![w; '?', mode, "$p"],
+            Self::RequestClipboard(selection) => {
+                osc_seq![w; "52;", selection.as_byte() as char, ";?"]
+            }
+            Self::RequestColor(color) => osc_seq![w; color, ";?"],
+            Self::SetColor(color, value) => osc_seq![w; color, ';', RgbSpec(value.to_rgb())],
+            Self::ResetColor(TerminalColor::Palette(i)) => osc_seq![w; "104;", i as u32],
+            _ => unreachable!("{:?} should have static bytes", self),
+        }
+    }
+
+    /// Append the sequence to `buf`. This only allocates if `buf` needs to grow.
+    pub fn encode_into(&self, buf: &mut Vec<u8>) {
+        // writing to a `Vec` can't fail
+        let _ = self.write_to(buf);
+    }
+
+    /// Write the sequence to the start of `buf`, returning how many bytes it took. Fails with
+    /// `WriteZero` if it doesn't fit.
+    pub fn encode_to_slice(&self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = buf.len();
+        let mut rest = buf;
+        self.write_to(&mut rest)?;
+        Ok(len - rest.len())
+    }
+
     pub fn as_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.encode_into(&mut buf);
+        buf
+    }
+}
+
+/// Lets `Display` go through `StdoutEscSeq::write_to`. Every sequence is ASCII, so chunks are
+/// always valid UTF-8.
+struct FmtWriter<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+impl io::Write for FmtWriter<'_, '_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let s =
+            std::str::from_utf8(buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.0
+            .write_str(s)
+            .map_err(|_| io::Error::other("formatter error"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Formats as the raw escape sequence, so `write!(out, "{}bold{}", seq, reset)` works
+impl fmt::Display for StdoutEscSeq<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_to(&mut FmtWriter(f)).map_err(|_| fmt::Error)
+    }
+}
+
+impl<'a> StdoutEscSeq<'a> {
+    /// The sequence the terminal described by `terminfo` wants for this, falling back to
+    /// `as_bytes` for things terminfo doesn't have a capability for (or that this terminal
+    /// doesn't have)
+    pub fn as_bytes_for(&self, terminfo: &TermInfo) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        // writing to a `Vec` can't fail
+        let _ = self.write_to_for(terminfo, &mut bytes);
+        bytes
+    }
+
+    /// Write the sequence `terminfo` has for this into `w`, `false` if it hasn't got one
+    fn write_terminfo_to<W: io::Write + ?Sized>(
+        &self,
+        terminfo: &TermInfo,
+        w: &mut W,
+    ) -> io::Result<bool> {
         match *self {
-            Self::MoveUp(count) => esc_seq![count, 'A'],
-            Self::MoveRight(count) => esc_seq![count, 'C'],
-            Self::MoveDown(count) => esc_seq![count, 'B'],
-            Self::MoveLeft(count) => esc_seq![count, 'D'],
-            Self::SaveScreen => esc_seq!["?47h"],
-            Self::RestoreScreen => esc_seq!["?47l"],
-            Self::SaveCursorPosition => vec![ESC_ASCII, 7], // TODO: fix
-            Self::RestorCursorPosition => vec![ESC_ASCII, 8],
-            Self::EraseEntireScreen => esc_seq!["2J"],
+            // the single-step versions (`cud1` especially, which is often `\n`) do more than
+            // just move on some terminals, so only use the parameterised ones
+            Self::MoveUp(count) => terminfo.expand_to("cuu", &[count.into()], w),
+            Self::MoveRight(count) => terminfo.expand_to("cuf", &[count.into()], w),
+            Self::MoveDown(count) => terminfo.expand_to("cud", &[count.into()], w),
+            Self::MoveLeft(count) => terminfo.expand_to("cub", &[count.into()], w),
+            Self::MoveTo(row, col) => terminfo.expand_to("cup", &[row.into(), col.into()], w),
+            Self::MoveToColumn(col) => terminfo.expand_to("hpa", &[col.into()], w),
+            Self::MoveToRow(row) => terminfo.expand_to("vpa", &[row.into()], w),
+            Self::HideCursor => terminfo.expand_to("civis", &[], w),
+            Self::ShowCursor => terminfo.expand_to("cnorm", &[], w),
+            Self::SetCursorStyle(CursorStyle::Default) => Ok(terminfo.expand_to("Se", &[], w)?
+                || terminfo.expand_to("Ss", &[Param::Number(0)], w)?),
+            Self::SetCursorStyle(style) => terminfo.expand_to("Ss", &[style.as_param().into()], w),
+            Self::EnterAlternateScreen => terminfo.expand_to("smcup", &[], w),
+            Self::LeaveAlternateScreen => terminfo.expand_to("rmcup", &[], w),
+            Self::SetScrollRegion(top, bottom) => {
+                terminfo.expand_to("csr", &[top.into(), bottom.into()], w)
+            }
+            Self::ScrollUp(count) => terminfo.expand_to("indn", &[count.into()], w),
+            Self::ScrollDown(count) => terminfo.expand_to("rin", &[count.into()], w),
+            Self::InsertLines(count) => terminfo.expand_to("il", &[count.into()], w),
+            Self::DeleteLines(count) => terminfo.expand_to("dl", &[count.into()], w),
+            Self::InsertChars(count) => terminfo.expand_to("ich", &[count.into()], w),
+            Self::DeleteChars(count) => terminfo.expand_to("dch", &[count.into()], w),
+            Self::EraseChars(count) => terminfo.expand_to("ech", &[count.into()], w),
+            Self::SetTabStop => terminfo.expand_to("hts", &[], w),
+            Self::ClearAllTabStops => terminfo.expand_to("tbc", &[], w),
+            Self::TabBackward(1) => terminfo.expand_to("cbt", &[], w),
+            Self::SaveCursorPosition => terminfo.expand_to("sc", &[], w),
+            Self::RestorCursorPosition => terminfo.expand_to("rc", &[], w),
+            Self::EraseToEndOfScreen => terminfo.expand_to("ed", &[], w),
+            Self::EraseScrollback => terminfo.expand_to("E3", &[], w),
+            Self::EraseToEndOfLine => terminfo.expand_to("el", &[], w),
+            Self::EraseToStartOfLine => terminfo.expand_to("el1", &[], w),
+            Self::ResetStyle => terminfo.expand_to("sgr0", &[], w),
+            _ => Ok(false),
         }
     }
+
+    /// Like `as_bytes_for`, but straight into `w`, without allocating (unless `terminfo` has
+    /// an unusually long sequence for it)
+    pub fn write_to_for<W: io::Write + ?Sized>(
+        &self,
+        terminfo: &TermInfo,
+        w: &mut W,
+    ) -> io::Result<()> {
+        if !self.write_terminfo_to(terminfo, w)? {
+            self.write_to(w)?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub enum Token<'a> {
     Esc(StdinEscSeq),
     Char(&'a str),
+    /// The terminal's reply to `StdoutEscSeq::RequestClipboard`
+    Clipboard(ClipboardSelection, String),
+    /// The terminal's reply to `StdoutEscSeq::RequestPrivateMode`, with the mode it's about
+    ModeReport(u32, ModeState),
+    /// The terminal's reply to `StdoutEscSeq::RequestCursorPosition`, as `(row, col)` counting
+    /// from `(0, 0)` like `StdoutEscSeq::MoveTo`. Shift + F3 on some terminals looks just like
+    /// a reply with the cursor on the first row.
+    CursorPosition(u32, u32),
+    /// The terminal's reply to a kitty graphics command (`StdoutEscSeq::Kitty`)
+    GraphicsResponse(GraphicsResponse),
+    /// The terminal's reply to `StdoutEscSeq::RequestColor`, with the color as RGB
+    ColorReport(TerminalColor, (u8, u8, u8)),
+}
+
+/// What the terminal said about a kitty graphics command
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GraphicsResponse {
+    /// The image id the command was about (`i=`)
+    pub image_id: Option<u32>,
+    /// The image number, for commands that used one instead of an id (`I=`)
+    pub image_number: Option<u32>,
+    pub placement_id: Option<u32>,
+    /// `Err` has the error code and message, like `ENOENT:Put command refers to non-existent
+    /// image with id: 7`
+    pub result: Result<(), String>,
+}
+
+/// What `TokenReader::scan_esc_seq` found at the front of the buffer
+enum Scanned {
+    Token(Token<'static>),
+    /// An OSC string, with the range of its body (between `ESC ]` and the terminator)
+    Osc(Range<usize>),
+    /// An APC string, with the range of its body (between `ESC _` and the terminator)
+    Apc(Range<usize>),
+    /// A sequence that doesn't fit in the buffer, the rest of it gets thrown away as it comes
+    /// in
+    TooLong(TooLong),
+}
+
+/// What kind of sequence didn't fit in the buffer, so we know where the rest of it ends
+#[derive(Clone, Copy)]
+enum TooLong {
+    /// ends at a final byte
+    Csi,
+    /// ends at `ST` or `BEL`
+    String,
 }
 
 /// The size of the TokenReader's internal buffer
@@ -77,11 +866,24 @@ pub const BUF_SIZE: usize = 4096;
 pub const ESC_ASCII: u8 = 0x1b;
 pub const ESC_SEQ_PREFIX: [u8; 2] = [0x1b, 0x5b];
 pub const MAX_ESC_SEQ_LENGTH: usize = 1 + ESC_SEQ_PREFIX.len();
+pub const BEL_ASCII: u8 = 0x07;
+pub const OSC_PREFIX: [u8; 2] = [0x1b, b']'];
+/// String terminator, ends OSC (or DCS/APC) sequences. Most terminals accept `BEL` too.
+pub const ST: [u8; 2] = [0x1b, b'\\'];
 
 pub struct TokenReader {
     buf: [u8; BUF_SIZE],
     index: usize,
     end: usize,
+    /// We're in the middle of a sequence (usually an OSC string) that was too big for the
+    /// buffer, so we're throwing bytes away until we see the end of it
+    discarding: Option<TooLong>,
+}
+
+impl Default for TokenReader {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TokenReader {
@@ -90,38 +892,51 @@ impl TokenReader {
             buf: [0; BUF_SIZE],
             index: 0,
             end: 0,
+            discarding: None,
         }
     }
 
-    /// Update internal buffer with a read from stdin
-    fn update_buf(&mut self) {
-        // TODO: make unchecked after testing
-        // shift unused bytes to the front
+    /// Shift unused bytes to the front of the buffer and do a single `read` from stdin into
+    /// the rest of it. This blocks if there is nothing to read, so only call it when you know
+    /// stdin is readable (e.g. after `poll`). Returns the number of bytes read, `0` means EOF.
+    pub fn read_available(&mut self) -> io::Result<usize> {
         self.buf.copy_within(self.index..self.end, 0);
-        let dst_index = self.end - self.index;
+        self.end -= self.index;
+        self.index = 0;
 
-        // TODO: https://man7.org/linux/man-pages/man2/read.2.html
-        //       - implement errno mapping
         // TODO: make this only read `MAX_ESC_SEQ_LENGTH + 1` to start, then try again
         //       if we read that many bytes, this time filling the whole buffer (or
         //       as much as we can)
         let bytes_read = unsafe {
             libc::read(
                 libc::STDIN_FILENO,
-                self.buf.as_mut_ptr().add(dst_index) as *mut c_void,
-                BUF_SIZE - dst_index,
+                self.buf.as_mut_ptr().add(self.end) as *mut c_void,
+                BUF_SIZE - self.end,
             )
         };
 
-        self.index = 0;
-
-        // failed read, retry instantly
         if bytes_read == -1 {
-            self.end = 0;
-            return;
+            return Err(io::Error::last_os_error());
         }
 
-        self.end = bytes_read as usize;
+        self.received(bytes_read as usize);
+        Ok(bytes_read as usize)
+    }
+
+    /// `len` more bytes have been put in the buffer after `end`
+    fn received(&mut self, len: usize) {
+        self.end += len;
+        if let Some(kind) = self.discarding {
+            if self.discard_rest(kind) {
+                self.discarding = None;
+            }
+        }
+    }
+
+    /// Update internal buffer with a read from stdin
+    fn update_buf(&mut self) {
+        // failed read, retry instantly
+        let _ = self.read_available();
     }
 
     unsafe fn get_relative(&self, offset: usize) -> &u8 {
@@ -129,30 +944,271 @@ impl TokenReader {
     }
 
     unsafe fn get_relative_slice(&self, slice_start: usize, slice_end: usize) -> &[u8] {
-        std::slice::from_raw_parts(self.get_relative(0) as *const u8, slice_end - slice_start)
+        std::slice::from_raw_parts(
+            self.get_relative(slice_start) as *const u8,
+            slice_end - slice_start,
+        )
     }
 
     fn bytes_left(&self) -> usize {
         self.end - self.index
     }
 
-    /// read the next bytes as an esc seq and segfault/panic/something-else if we fail
-    /// (that's why it's `unsafe`)
-    unsafe fn read_esc_seq(&mut self) -> StdinEscSeq {
-        let old_index = self.index;
-        self.index += ESC_SEQ_PREFIX.len() + 1;
-        match self.buf[old_index + 2] {
+    /// Work out what the escape sequence at the front of the buffer is, and how long it is.
+    /// Returns `None` if the sequence is cut off and we need to read more.
+    fn scan_esc_seq(&self) -> Option<(Scanned, usize)> {
+        let bytes = &self.buf[self.index..self.end];
+        match bytes.get(1)? {
+            b'[' => {
+                // CSI: parameter bytes, then intermediate bytes, then a final byte
+                let mut len = 2;
+                len += bytes[len..]
+                    .iter()
+                    .take_while(|b| (0x30..=0x3f).contains(*b))
+                    .count();
+                len += bytes[len..]
+                    .iter()
+                    .take_while(|b| (0x20..=0x2f).contains(*b))
+                    .count();
+                let Some(&final_byte) = bytes.get(len) else {
+                    // this is never going to fit, give up on it
+                    let len = self.is_full().then_some(BUF_SIZE)?;
+                    return Some((Scanned::TooLong(TooLong::Csi), len));
+                };
+                if !(0x40..=0x7e).contains(&final_byte) {
+                    // malformed, throw away everything up to here
+                    return Some((Scanned::Token(Token::Esc(StdinEscSeq::Unknown)), len));
+                }
+                let token = Self::csi_token(&bytes[2..len], final_byte);
+                Some((Scanned::Token(token), len + 1))
+            }
+            &kind @ (b']' | b'_') => {
+                // OSC or APC: a string terminated by `BEL` or `ST`
+                let body_end = bytes[2..]
+                    .iter()
+                    .zip(bytes[3..].iter().chain([&0]))
+                    .position(|(&a, &b)| a == BEL_ASCII || [a, b] == ST)
+                    .map(|i| i + 2);
+                match body_end {
+                    Some(body_end) => {
+                        let len = body_end + if bytes[body_end] == BEL_ASCII { 1 } else { 2 };
+                        match kind {
+                            b']' => Some((Scanned::Osc(2..body_end), len)),
+                            _ => Some((Scanned::Apc(2..body_end), len)),
+                        }
+                    }
+                    // this is never going to fit, give up on it
+                    None if self.is_full() => Some((Scanned::TooLong(TooLong::String), BUF_SIZE)),
+                    None => None,
+                }
+            }
+            b'O' => {
+                // SS3, sent for some keys in application cursor mode
+                let seq = match bytes.get(2)? {
+                    b'A' => StdinEscSeq::MoveUp(1),
+                    b'B' => StdinEscSeq::MoveDown(1),
+                    b'C' => StdinEscSeq::MoveRight(1),
+                    b'D' => StdinEscSeq::MoveLeft(1),
+                    b'H' => StdinEscSeq::Home,
+                    b'F' => StdinEscSeq::End,
+                    _ => StdinEscSeq::Unknown,
+                };
+                Some((Scanned::Token(Token::Esc(seq)), 3))
+            }
+            _ => match self.can_read_char(1) {
+                (true, len) => {
+                    let c = unsafe { self.get_relative_slice(1, 1 + len) };
+                    let c = std::str::from_utf8(c).ok()?.chars().next()?;
+                    Some((Scanned::Token(Token::Esc(StdinEscSeq::Alt(c))), 1 + len))
+                }
+                // `ESC ESC` or garbage (we have room for `ESC` + a 4 byte codepoint), either way
+                // it's not going to get any better
+                _ if self.bytes_left() > 4 || self.buf[self.index + 1] == ESC_ASCII => {
+                    Some((Scanned::Token(Token::Esc(StdinEscSeq::Unknown)), 1))
+                }
+                _ => None,
+            },
+        }
+    }
+
+    /// Make a token out of a complete CSI sequence (without the `ESC [` or final byte). These
+    /// are mostly keys, but some are replies to things we asked the terminal.
+    fn csi_token(body: &[u8], final_byte: u8) -> Token<'static> {
+        let number = |param: &[u8]| std::str::from_utf8(param).ok()?.parse::<u32>().ok();
+        match (body, final_byte) {
+            // DECRPM: `CSI ? <mode> ; <state> $ y`
+            ([b'?', params @ .., b'$'], b'y') => {
+                let mut params = params.splitn(2, |&b| b == b';');
+                match (
+                    params.next().and_then(number),
+                    params.next().and_then(number),
+                ) {
+                    (Some(mode), Some(state)) => {
+                        Token::ModeReport(mode, ModeState::from_param(state))
+                    }
+                    _ => Token::Esc(StdinEscSeq::Unknown),
+                }
+            }
+            // CPR: `CSI <row> ; <col> R`
+            (params, b'R') if params.contains(&b';') => {
+                let mut params = params.splitn(2, |&b| b == b';');
+                match (
+                    params.next().and_then(number),
+                    params.next().and_then(number),
+                ) {
+                    (Some(row), Some(col)) => {
+                        Token::CursorPosition(row.saturating_sub(1), col.saturating_sub(1))
+                    }
+                    _ => Token::Esc(StdinEscSeq::Unknown),
+                }
+            }
+            _ => Token::Esc(Self::csi_esc_seq(body, final_byte)),
+        }
+    }
+
+    /// Map a complete CSI sequence (without the `ESC [` or final byte) to a `StdinEscSeq`
+    fn csi_esc_seq(params: &[u8], final_byte: u8) -> StdinEscSeq {
+        let mut params = params
+            .split(|&b| b == b';')
+            .map(|param| std::str::from_utf8(param).ok()?.parse::<u32>().ok());
+        let first = params.next().flatten();
+        // xterm sends modified keys as `ESC [ 1 ; <modifier> <final>`, where modifier is
+        // 1 + (shift = 1 | alt = 2 | ctrl = 4)
+        let modifier = params.next().flatten().unwrap_or(1);
+        let word = matches!(modifier.saturating_sub(1) & 0b110, 0b010 | 0b100 | 0b110);
+
+        match final_byte {
             b'A' => StdinEscSeq::MoveUp(1),
             b'B' => StdinEscSeq::MoveDown(1),
+            b'C' if word => StdinEscSeq::WordRight,
+            b'D' if word => StdinEscSeq::WordLeft,
             b'C' => StdinEscSeq::MoveRight(1),
             b'D' => StdinEscSeq::MoveLeft(1),
-            _ => unimplemented!(),
+            b'H' => StdinEscSeq::Home,
+            b'F' => StdinEscSeq::End,
+            b'~' => match first {
+                Some(1 | 7) => StdinEscSeq::Home,
+                Some(4 | 8) => StdinEscSeq::End,
+                Some(2) => StdinEscSeq::Insert,
+                Some(3) => StdinEscSeq::Delete,
+                Some(5) => StdinEscSeq::PageUp,
+                Some(6) => StdinEscSeq::PageDown,
+                _ => StdinEscSeq::Unknown,
+            },
+            _ => StdinEscSeq::Unknown,
+        }
+    }
+
+    /// Make a token out of the body of an OSC string
+    fn osc_token(body: &[u8]) -> Token<'static> {
+        let mut parts = body.splitn(3, |&b| b == b';');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(b"52"), Some(selection), Some(data)) if data != b"?" => {
+                let selection = selection
+                    .first()
+                    .and_then(|&b| ClipboardSelection::from_byte(b))
+                    .unwrap_or(ClipboardSelection::Clipboard);
+                match base64::decode(data) {
+                    Some(text) => {
+                        Token::Clipboard(selection, String::from_utf8_lossy(&text).into_owned())
+                    }
+                    None => Token::Esc(StdinEscSeq::Unknown),
+                }
+            }
+            (Some(b"4"), Some(index), Some(spec)) => {
+                let index = std::str::from_utf8(index).ok().and_then(|i| i.parse().ok());
+                match (index, parse_rgb_spec(spec)) {
+                    (Some(index), Some(rgb)) => {
+                        Token::ColorReport(TerminalColor::Palette(index), rgb)
+                    }
+                    _ => Token::Esc(StdinEscSeq::Unknown),
+                }
+            }
+            (Some(command @ (b"10" | b"11" | b"12")), Some(spec), None) => {
+                let color = match command {
+                    b"10" => TerminalColor::Foreground,
+                    b"11" => TerminalColor::Background,
+                    _ => TerminalColor::Cursor,
+                };
+                match parse_rgb_spec(spec) {
+                    Some(rgb) => Token::ColorReport(color, rgb),
+                    None => Token::Esc(StdinEscSeq::Unknown),
+                }
+            }
+            _ => Token::Esc(StdinEscSeq::Unknown),
         }
     }
 
-    /// if we can read a char, return `(true, char_size_in_bytes)`
-    fn can_read_char(&self) -> (bool, usize) {
-        let byte = self.buf[self.index];
+    /// Make a token out of the body of an APC string. The only ones we know are kitty graphics
+    /// replies: `G<key>=<value>,...;<OK or error>`.
+    fn apc_token(body: &[u8]) -> Token<'static> {
+        let Some(body) = body.strip_prefix(b"G") else {
+            return Token::Esc(StdinEscSeq::Unknown);
+        };
+        let Some(split) = body.iter().position(|&b| b == b';') else {
+            return Token::Esc(StdinEscSeq::Unknown);
+        };
+        let mut response = GraphicsResponse {
+            image_id: None,
+            image_number: None,
+            placement_id: None,
+            result: Ok(()),
+        };
+        for key_value in body[..split].split(|&b| b == b',') {
+            let value = || {
+                std::str::from_utf8(&key_value[2..])
+                    .ok()?
+                    .parse::<u32>()
+                    .ok()
+            };
+            match key_value {
+                [b'i', b'=', ..] => response.image_id = value(),
+                [b'I', b'=', ..] => response.image_number = value(),
+                [b'p', b'=', ..] => response.placement_id = value(),
+                _ => (),
+            }
+        }
+        let message = &body[split + 1..];
+        if message != b"OK" {
+            response.result = Err(String::from_utf8_lossy(message).into_owned());
+        }
+        Token::GraphicsResponse(response)
+    }
+
+    /// Throw away bytes up to (and including) the end of a sequence that was `TooLong`,
+    /// returns `true` once we've found it
+    fn discard_rest(&mut self, kind: TooLong) -> bool {
+        let bytes = &self.buf[self.index..self.end];
+        if let TooLong::Csi = kind {
+            let end = bytes.iter().position(|b| !(0x20..=0x3f).contains(b));
+            match end {
+                Some(end) if (0x40..=0x7e).contains(&bytes[end]) => self.index += end + 1,
+                // malformed, so it ends before this byte
+                Some(end) => self.index += end,
+                None => self.index = self.end,
+            }
+            return end.is_some();
+        }
+        let terminator = bytes.iter().enumerate().find(|&(i, &b)| {
+            b == BEL_ASCII || (b == ESC_ASCII && bytes.get(i + 1) == Some(&b'\\'))
+        });
+        match terminator {
+            Some((i, &BEL_ASCII)) => self.index += i + 1,
+            Some((i, _)) => self.index += i + 2,
+            // keep a trailing `ESC`, it might be the start of `ST`
+            None if bytes.last() == Some(&ESC_ASCII) => self.index = self.end - 1,
+            None => self.index = self.end,
+        }
+        terminator.is_some()
+    }
+
+    /// if we can read a char `offset` bytes into the buffer, return `(true, char_size_in_bytes)`
+    fn can_read_char(&self, offset: usize) -> (bool, usize) {
+        if self.bytes_left() <= offset {
+            return (false, 0);
+        }
+
+        let byte = self.buf[self.index + offset];
 
         // TODO: check if this is ok!
         if byte == ESC_ASCII {
@@ -177,11 +1233,10 @@ impl TokenReader {
             if shifted == 0b0 {
                 return 1;
             }
-            return -1;
+            -1
         }
 
-        // this can't really be invalid utf8, but if it is, just loop forever ig
-        // TODO: umm... fix this?
+        // not the start of a char, `invalid_char` picks these up
         let len = utf8_codepoint_length(byte);
         if len == -1 {
             return (false, 0);
@@ -189,13 +1244,13 @@ impl TokenReader {
 
         // check we're not cut off
         let len = len as usize;
-        if self.bytes_left() < len {
+        if self.bytes_left() < offset + len {
             return (false, 0);
         }
 
         // check this is valid utf8
         (
-            std::str::from_utf8(unsafe { self.get_relative_slice(0, len) }).is_ok(),
+            std::str::from_utf8(unsafe { self.get_relative_slice(offset, offset + len) }).is_ok(),
             len,
         )
     }
@@ -208,36 +1263,262 @@ impl TokenReader {
         std::str::from_utf8_unchecked(&self.buf[old_index..self.index])
     }
 
-    /// Get the next Token from stdin
-    pub fn next(&mut self) -> Token {
-        loop {
-            if self.end == 0 {
-                self.update_buf();
-                continue;
-            }
+    /// Whether the front of the buffer is bytes that are never going to be UTF-8, however many
+    /// more we read (as opposed to a char that's just cut off)
+    fn invalid_char(&self) -> bool {
+        let bytes = &self.buf[self.index..self.end];
+        match std::str::from_utf8(&bytes[..bytes.len().min(4)]) {
+            Ok(_) => false,
+            Err(e) => e.valid_up_to() == 0 && e.error_len().is_some(),
+        }
+    }
 
-            // because of Polonius the Crab... something something... slow borrow checker
-            // if enabled... all the code goes in here:
+    /// There's no room left to read into, and nothing we can do with what's there until we
+    /// throw some of it away
+    pub(crate) fn is_full(&self) -> bool {
+        self.bytes_left() == BUF_SIZE
+    }
 
-            let fits_esc_seq_prefix = self.bytes_left() >= ESC_SEQ_PREFIX.len();
-            let fits_esc_seq = self.bytes_left() >= MAX_ESC_SEQ_LENGTH;
-            if fits_esc_seq_prefix
-                && unsafe { [*self.get_relative(0), *self.get_relative(1)] } == ESC_SEQ_PREFIX
-            {
-                if fits_esc_seq {
-                    return unsafe { Token::Esc(self.read_esc_seq()) };
-                } else {
-                    self.update_buf();
-                    continue;
+    /// Returns `true` if the next call to `try_next` would return a token, i.e. there is a
+    /// complete token sitting in the buffer
+    pub fn has_token(&self) -> bool {
+        if self.discarding.is_some() {
+            return false;
+        }
+        if self.bytes_left() > 0 && self.buf[self.index] == ESC_ASCII {
+            return self.scan_esc_seq().is_some();
+        }
+        self.can_read_char(0).0 || self.invalid_char()
+    }
+
+    /// Whether the buffer starts with an escape sequence that's cut off, which `flush_esc`
+    /// would give up waiting for the rest of
+    pub fn has_cut_off_esc(&self) -> bool {
+        self.discarding.is_none()
+            && self.bytes_left() > 0
+            && self.buf[self.index] == ESC_ASCII
+            && !matches!(self.buf.get(self.index + 1), Some(b']' | b'_'))
+            && self.scan_esc_seq().is_none()
+    }
+
+    /// Stop waiting for the rest of a cut off escape sequence (see `has_cut_off_esc`) and
+    /// make what we have into a token: a lone `ESC` is the Escape key, and `ESC [` or `ESC O`
+    /// is Alt + that key. Call this when nothing else has turned up for a little while, a
+    /// terminal sends the whole of a sequence in one go. `EventLoop` does this for you.
+    pub fn flush_esc(&mut self) -> Option<Token<'static>> {
+        if !self.has_cut_off_esc() {
+            return None;
+        }
+        let (seq, len) = match &self.buf[self.index..self.end] {
+            [_] => (StdinEscSeq::Escape, 1),
+            [_, c @ (b'[' | b'O')] => (StdinEscSeq::Alt(*c as char), 2),
+            bytes => (StdinEscSeq::Unknown, bytes.len()),
+        };
+        self.index += len;
+        Some(Token::Esc(seq))
+    }
+
+    /// Get the next Token that is already buffered, without reading from stdin. Returns
+    /// `None` if we need more bytes first (see `read_available`)
+    pub fn try_next(&mut self) -> Option<Token<'_>> {
+        if self.discarding.is_some() {
+            return None;
+        }
+
+        if self.bytes_left() > 0 && self.buf[self.index] == ESC_ASCII {
+            let (scanned, len) = self.scan_esc_seq()?;
+            let token = match scanned {
+                Scanned::Token(token) => token,
+                Scanned::TooLong(kind) => {
+                    self.discarding = Some(kind);
+                    Token::Esc(StdinEscSeq::Unknown)
                 }
+                Scanned::Osc(body) => {
+                    Self::osc_token(&self.buf[self.index + body.start..self.index + body.end])
+                }
+                Scanned::Apc(body) => {
+                    Self::apc_token(&self.buf[self.index + body.start..self.index + body.end])
+                }
+            };
+            self.index += len;
+            return Some(token);
+        }
+
+        match self.can_read_char(0) {
+            (true, char_len) => Some(unsafe { Token::Char(self.read_char(char_len)) }),
+            // waiting for more isn't going to help, so skip a byte and carry on after it
+            _ if self.invalid_char() => {
+                self.index += 1;
+                Some(Token::Esc(StdinEscSeq::Unknown))
             }
+            _ => None,
+        }
+    }
 
-            let (can_read_char, char_len) = self.can_read_char();
-            if can_read_char {
-                return unsafe { Token::Char(self.read_char(char_len)) };
+    /// Get the next Token from stdin, waiting at most `timeout` for one to turn up. Returns
+    /// `Ok(None)` if we ran out of time, and `UnexpectedEof` if stdin is closed.
+    pub fn next_timeout(&mut self, timeout: Duration) -> io::Result<Option<Token<'_>>> {
+        let deadline = Instant::now() + timeout;
+        while !self.has_token() {
+            let left = deadline.saturating_duration_since(Instant::now());
+            let mut fd = libc::pollfd {
+                fd: libc::STDIN_FILENO,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            // round up, so we don't spin on a deadline that's less than 1ms away
+            let left = left.as_nanos().div_ceil(1_000_000).min(c_int::MAX as u128) as c_int;
+            let ready = unsafe { libc::poll(&mut fd, 1, left) };
+            if ready == -1 {
+                let e = io::Error::last_os_error();
+                if e.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(e);
             }
+            if ready == 0 {
+                return Ok(None);
+            }
+            if self.read_available()? == 0 {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+        }
+        Ok(self.try_next())
+    }
 
+    /// Get the next Token from stdin
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Token<'_> {
+        // because of Polonius the Crab... something something... slow borrow checker
+        // if enabled... so we check first and only borrow once we know we're returning
+        while !self.has_token() {
             self.update_buf();
         }
+        self.try_next().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `read_available`, but reading `bytes` instead of stdin
+    fn receive(reader: &mut TokenReader, bytes: &[u8]) {
+        reader.buf.copy_within(reader.index..reader.end, 0);
+        reader.end -= reader.index;
+        reader.index = 0;
+        reader.buf[reader.end..reader.end + bytes.len()].copy_from_slice(bytes);
+        reader.received(bytes.len());
     }
-}
\ No newline at end of file
+
+    /// A reader with `bytes` already read in
+    fn reader(bytes: &[u8]) -> TokenReader {
+        let mut reader = TokenReader::new();
+        receive(&mut reader, bytes);
+        reader
+    }
+
+    #[test]
+    fn invalid_bytes_are_skipped() {
+        let mut reader = reader(b"\xffa\xc0\x80b");
+        assert_eq!(reader.try_next(), Some(Token::Esc(StdinEscSeq::Unknown)));
+        assert_eq!(reader.try_next(), Some(Token::Char("a")));
+        assert_eq!(reader.try_next(), Some(Token::Esc(StdinEscSeq::Unknown)));
+        assert_eq!(reader.try_next(), Some(Token::Esc(StdinEscSeq::Unknown)));
+        assert_eq!(reader.try_next(), Some(Token::Char("b")));
+        assert_eq!(reader.try_next(), None);
+    }
+
+    #[test]
+    fn cut_off_chars_wait() {
+        let reader = reader("é".as_bytes().split_last().unwrap().1);
+        assert!(!reader.has_token());
+    }
+
+    #[test]
+    fn flush_esc() {
+        let mut escape = reader(b"\x1b");
+        assert!(!escape.has_token());
+        assert!(escape.has_cut_off_esc());
+        assert_eq!(escape.flush_esc(), Some(Token::Esc(StdinEscSeq::Escape)));
+        assert_eq!(escape.flush_esc(), None);
+
+        let mut alt = reader(b"\x1b[");
+        assert_eq!(alt.flush_esc(), Some(Token::Esc(StdinEscSeq::Alt('['))));
+
+        // strings can take a while, so they aren't given up on
+        let osc = reader(b"\x1b]11;rgb:");
+        assert!(!osc.has_cut_off_esc());
+
+        let mut done = reader(b"\x1b[A");
+        assert!(!done.has_cut_off_esc());
+        assert_eq!(done.flush_esc(), None);
+    }
+
+    #[test]
+    fn too_long_csi_is_thrown_away() {
+        let mut bytes = b"\x1b[".to_vec();
+        bytes.resize(BUF_SIZE, b'1');
+        let mut reader = reader(&bytes);
+        assert_eq!(reader.try_next(), Some(Token::Esc(StdinEscSeq::Unknown)));
+        assert!(reader.discarding.is_some());
+
+        // the rest of it comes in later
+        receive(&mut reader, b"1;2");
+        assert_eq!(reader.try_next(), None);
+        receive(&mut reader, b"Ax");
+        assert_eq!(reader.try_next(), Some(Token::Char("x")));
+    }
+
+    #[test]
+    fn positions_saturate() {
+        let write = |seq: StdoutEscSeq| {
+            let mut out = Vec::new();
+            seq.write_to(&mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(write(StdoutEscSeq::MoveTo(4, 9)), "\x1b[5;10H");
+        let max = u32::MAX;
+        assert_eq!(
+            write(StdoutEscSeq::MoveTo(max, max)),
+            format!("\x1b[{max};{max}H")
+        );
+        assert_eq!(
+            write(StdoutEscSeq::MoveToColumn(max)),
+            format!("\x1b[{max}G")
+        );
+        assert_eq!(write(StdoutEscSeq::MoveToRow(max)), format!("\x1b[{max}d"));
+        assert_eq!(
+            write(StdoutEscSeq::SetScrollRegion(0, max)),
+            format!("\x1b[1;{max}r")
+        );
+    }
+
+    #[test]
+    fn parse_rgb_spec_scales_each_width() {
+        assert_eq!(parse_rgb_spec(b"rgb:f/8/0"), Some((255, 136, 0)));
+        assert_eq!(parse_rgb_spec(b"rgb:ff/80/00"), Some((255, 128, 0)));
+        assert_eq!(parse_rgb_spec(b"rgb:fff/800/000"), Some((255, 128, 0)));
+        assert_eq!(parse_rgb_spec(b"rgb:ffff/8080/0000"), Some((255, 128, 0)));
+        // the channels don't have to be the same width
+        assert_eq!(parse_rgb_spec(b"rgb:0/ff/FFFF"), Some((0, 255, 255)));
+    }
+
+    #[test]
+    fn parse_rgb_spec_ignores_alpha() {
+        assert_eq!(
+            parse_rgb_spec(b"rgba:ffff/0000/0000/8000"),
+            Some((255, 0, 0))
+        );
+    }
+
+    #[test]
+    fn parse_rgb_spec_rejects_junk() {
+        assert_eq!(parse_rgb_spec(b"rgb:fffff/0/0"), None);
+        assert_eq!(parse_rgb_spec(b"rgb://0"), None);
+        assert_eq!(parse_rgb_spec(b"rgb:ff/ff"), None);
+        assert_eq!(parse_rgb_spec(b"rgb:gg/00/00"), None);
+        assert_eq!(parse_rgb_spec(b"#ff8000"), None);
+        assert_eq!(parse_rgb_spec(b""), None);
+    }
+}
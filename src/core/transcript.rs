@@ -0,0 +1,182 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use super::{Output, OutputDecoder, BEL_ASCII, ESC_ASCII};
+
+/// Turn bytes written to a terminal into something readable, for snapshot tests. Text is
+/// kept as is, escape sequences and control characters go in braces (`{CSI 1;1H}`,
+/// `{OSC 2;title}`, `{ESC (B}`, `{CR}`, `{0x01}`), and every `{LF}` is followed by a real line
+/// break so diffs line up with lines on the screen. A `{` in the text is written `{{`, and
+/// inside the braces a `}` is `}}`, a `\` is `\\` and control characters are `\x1b` etc.
+///
+/// Sequences are split up the same way `OutputDecoder` (and so the terminal) does it.
+///
+/// ```rs
+/// let mut stdout = TsStdout::with_writer(Vec::new());
+/// screen.present(&mut stdout);
+/// println!("{}", transcript(&stdout.into_writer()?));
+/// ```
+pub fn transcript(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    let mut decoder = OutputDecoder::new(bytes);
+    let mut start = 0;
+    while let Some(output) = decoder.next() {
+        let raw = &bytes[start..decoder.position()];
+        start = decoder.position();
+        match output {
+            Output::Text(text) => out.push_str(&text.replace('{', "{{")),
+            _ if raw[0] == ESC_ASCII => write_esc_seq(&mut out, raw),
+            _ if raw == b"\n" => out.push_str("{LF}\n"),
+            _ => {
+                // control characters, and bytes that aren't UTF-8
+                for &byte in raw {
+                    match byte {
+                        0..=0x1f | 0x7f => out.push_str(control_name(byte)),
+                        _ => {
+                            let _ = write!(out, "{{0x{byte:02x}}}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Compare `bytes` against the transcript saved in `path`, and panic if they're different.
+/// Run with `TERMSET_BLESS=1` to write the file instead, e.g. the first time or after
+/// changing what a screen draws on purpose.
+#[track_caller]
+pub fn assert_transcript(path: impl AsRef<Path>, bytes: &[u8]) {
+    let path = path.as_ref();
+    let actual = transcript(bytes);
+    if std::env::var_os("TERMSET_BLESS").is_some() {
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        if let Err(e) = fs::write(path, &actual) {
+            panic!("couldn't write {}: {e}", path.display());
+        }
+        return;
+    }
+
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) => expected,
+        Err(e) => panic!(
+            "couldn't read {}: {e}\nrun with TERMSET_BLESS=1 to create it, the transcript is:\n{actual}",
+            path.display()
+        ),
+    };
+    if expected == actual {
+        return;
+    }
+    let (line, expected_line, actual_line) = expected
+        .split('\n')
+        .map(Some)
+        .chain(std::iter::repeat(None))
+        .zip(actual.split('\n').map(Some).chain(std::iter::repeat(None)))
+        .enumerate()
+        .find(|(_, (expected, actual))| expected != actual)
+        .map(|(i, (expected, actual))| (i + 1, expected, actual))
+        .unwrap();
+    panic!(
+        "transcript doesn't match {} at line {line}\nexpected: {}\n  actual: {}\nrun with TERMSET_BLESS=1 to update it",
+        path.display(),
+        expected_line.unwrap_or("<end of file>"),
+        actual_line.unwrap_or("<end of transcript>"),
+    );
+}
+
+/// Write `seq`, which starts with an `ESC`
+fn write_esc_seq(out: &mut String, seq: &[u8]) {
+    let Some(&kind) = seq.get(1) else {
+        out.push_str("{ESC}");
+        return;
+    };
+    let (name, body) = match kind {
+        b'[' => ("CSI", &seq[2..]),
+        b']' | b'P' | b'_' | b'^' | b'X' => {
+            let name = match kind {
+                b']' => "OSC",
+                b'P' => "DCS",
+                b'_' => "APC",
+                b'^' => "PM",
+                _ => "SOS",
+            };
+            // leave the terminator off, if it got one
+            let body = &seq[2..];
+            let body = match body.strip_suffix(b"\x1b\\") {
+                Some(body) => body,
+                None if kind == b']' => body.strip_suffix(&[BEL_ASCII]).unwrap_or(body),
+                None => body,
+            };
+            (name, body)
+        }
+        _ => ("ESC", &seq[1..]),
+    };
+
+    out.push('{');
+    out.push_str(name);
+    out.push(' ');
+    for chunk in body.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '}' => out.push_str("}}"),
+                '\\' => out.push_str("\\\\"),
+                c if c.is_control() => {
+                    let _ = write!(out, "\\x{:02x}", c as u32);
+                }
+                c => out.push(c),
+            }
+        }
+        for byte in chunk.invalid() {
+            let _ = write!(out, "\\x{byte:02x}");
+        }
+    }
+    out.push('}');
+}
+
+fn control_name(byte: u8) -> &'static str {
+    match byte {
+        0x07 => "{BEL}",
+        0x08 => "{BS}",
+        0x09 => "{HT}",
+        0x0b => "{VT}",
+        0x0c => "{FF}",
+        0x0d => "{CR}",
+        0x7f => "{DEL}",
+        _ => CONTROL_HEX[byte as usize],
+    }
+}
+
+#[rustfmt::skip]
+const CONTROL_HEX: [&str; 32] = [
+    "{0x00}", "{0x01}", "{0x02}", "{0x03}", "{0x04}", "{0x05}", "{0x06}", "{0x07}",
+    "{0x08}", "{0x09}", "{0x0a}", "{0x0b}", "{0x0c}", "{0x0d}", "{0x0e}", "{0x0f}",
+    "{0x10}", "{0x11}", "{0x12}", "{0x13}", "{0x14}", "{0x15}", "{0x16}", "{0x17}",
+    "{0x18}", "{0x19}", "{0x1a}", "{0x1b}", "{0x1c}", "{0x1d}", "{0x1e}", "{0x1f}",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequences_end_where_the_terminal_ends_them() {
+        assert_eq!(
+            transcript(b"\x1b(Bx\x1b7\x1b[1;2Hy"),
+            "{ESC (B}x{ESC 7}{CSI 1;2H}y"
+        );
+        assert_eq!(transcript(b"\x1b[1\r\n"), "{CSI 1}{CR}{LF}\n");
+        assert_eq!(transcript(b"a\x07\x01\xff"), "a{BEL}{0x01}{0xff}");
+    }
+
+    #[test]
+    fn braces_are_escaped() {
+        assert_eq!(transcript(b"{x}"), "{{x}");
+        assert_eq!(transcript(b"\x1b]2;a}b\x07}"), "{OSC 2;a}}b}}");
+        assert_eq!(transcript(b"\x1b]2;a\\x1b\x1b\\"), "{OSC 2;a\\\\x1b}");
+        assert_eq!(transcript(b"\x1bPq#0\x1b"), "{DCS q#0\\x1b}");
+    }
+}
@@ -0,0 +1,305 @@
+use std::cmp::Ordering;
+
+use super::unicode_tables::{
+    EMOJI, EMOJI_PRESENTATION, EXTENDED_PICTOGRAPHIC, GRAPHEME_BREAK, WIDE, ZERO_WIDTH,
+};
+
+pub use super::unicode_tables::UNICODE_VERSION;
+
+/// The Grapheme_Cluster_Break property from UAX #29
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GraphemeCat {
+    Cr,
+    Lf,
+    Control,
+    Extend,
+    Zwj,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    L,
+    V,
+    T,
+    Lv,
+    Lvt,
+    Other,
+}
+
+fn lookup<T: Copy>(table: &[(u32, u32, T)], c: char) -> Option<T> {
+    let c = c as u32;
+    table
+        .binary_search_by(|&(start, end, _)| {
+            if end < c {
+                Ordering::Less
+            } else if start > c {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .ok()
+        .map(|i| table[i].2)
+}
+
+fn in_table(table: &[(u32, u32)], c: char) -> bool {
+    let c = c as u32;
+    table
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                Ordering::Less
+            } else if start > c {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+fn grapheme_cat(c: char) -> GraphemeCat {
+    if c.is_ascii() {
+        return match c {
+            '\r' => GraphemeCat::Cr,
+            '\n' => GraphemeCat::Lf,
+            '\0'..='\x1f' | '\x7f' => GraphemeCat::Control,
+            _ => GraphemeCat::Other,
+        };
+    }
+    lookup(GRAPHEME_BREAK, c).unwrap_or(GraphemeCat::Other)
+}
+
+/// The number of columns `c` takes up on its own: `2` for wide (CJK, most emoji, ...), `0` for
+/// combining marks, zero-width and control characters, and `1` for everything else.
+///
+/// This can't see what `c` is next to, so use `grapheme_width` or `display_width` if you can.
+pub fn char_width(c: char) -> usize {
+    match c {
+        ' '..='~' => 1,
+        '\0'..='\x1f' | '\x7f'..='\u{9f}' => 0,
+        c if in_table(ZERO_WIDTH, c) => 0,
+        c if in_table(WIDE, c) => 2,
+        _ => 1,
+    }
+}
+
+/// The number of columns a grapheme cluster (see `graphemes`) takes up. The whole cluster
+/// takes up as much room as its first visible character, apart from:
+///
+/// - a pair of regional indicators is a flag, which is 2 wide
+/// - `U+FE0F` asks for an emoji to be drawn as an emoji (2 wide), `U+FE0E` as text (1 wide)
+pub fn grapheme_width(grapheme: &str) -> usize {
+    let mut chars = grapheme.chars();
+    let Some(first) = chars.next() else {
+        return 0;
+    };
+    if grapheme_cat(first) == GraphemeCat::RegionalIndicator {
+        return if chars.next().is_some() { 2 } else { 1 };
+    }
+
+    let Some((base, width)) = grapheme
+        .chars()
+        .map(|c| (c, char_width(c)))
+        .find(|&(_, width)| width > 0)
+    else {
+        return 0;
+    };
+    if in_table(EMOJI, base) {
+        if grapheme.contains('\u{FE0F}') {
+            return 2;
+        }
+        if grapheme.contains('\u{FE0E}') && in_table(EMOJI_PRESENTATION, base) {
+            return 1;
+        }
+    }
+    width
+}
+
+/// The number of columns `s` takes up on the terminal, going grapheme by grapheme so things
+/// like `e` + combining accent, Hangul syllables made of jamo, flags and ZWJ emoji sequences
+/// come out right. Control characters (including `\n`) count as nothing.
+pub fn display_width(s: &str) -> usize {
+    if s.bytes().all(|b| (b' '..=b'~').contains(&b)) {
+        return s.len();
+    }
+    graphemes(s).map(grapheme_width).sum()
+}
+
+/// Split `s` into grapheme clusters, i.e. what the user thinks of as characters, following
+/// the extended grapheme cluster rules from UAX #29
+pub fn graphemes(s: &str) -> Graphemes<'_> {
+    Graphemes { rest: s }
+}
+
+/// The grapheme clusters in a string, see `graphemes`
+#[derive(Debug, Clone)]
+pub struct Graphemes<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Graphemes<'a> {
+    /// What hasn't been iterated over yet
+    pub fn as_str(&self) -> &'a str {
+        self.rest
+    }
+}
+
+impl<'a> Iterator for Graphemes<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let (grapheme, rest) = self.rest.split_at(first_grapheme_len(self.rest));
+        self.rest = rest;
+        Some(grapheme)
+    }
+}
+
+/// The length in bytes of the first grapheme cluster in `s`
+fn first_grapheme_len(s: &str) -> usize {
+    let mut chars = s.char_indices();
+    let Some((_, first)) = chars.next() else {
+        return 0;
+    };
+    let mut prev = grapheme_cat(first);
+    // we're in `Extended_Pictographic Extend*`
+    let mut emoji = in_table(EXTENDED_PICTOGRAPHIC, first);
+    // we're in `Extended_Pictographic Extend* ZWJ`
+    let mut emoji_zwj = false;
+    let mut regional_indicators = (prev == GraphemeCat::RegionalIndicator) as usize;
+
+    for (i, c) in chars {
+        use GraphemeCat::*;
+
+        let cat = grapheme_cat(c);
+        let pictographic = in_table(EXTENDED_PICTOGRAPHIC, c);
+        let join = match (prev, cat) {
+            // GB3 - GB5
+            (Cr, Lf) => true,
+            (Control | Cr | Lf, _) | (_, Control | Cr | Lf) => false,
+            // GB6 - GB8, Hangul syllables
+            (L, L | V | Lv | Lvt) | (Lv | V, V | T) | (Lvt | T, T) => true,
+            // GB9 - GB9b
+            (_, Extend | Zwj | SpacingMark) | (Prepend, _) => true,
+            // GB11, emoji ZWJ sequences
+            (Zwj, _) => emoji_zwj && pictographic,
+            // GB12 and GB13, regional indicators (flags) come in pairs
+            (RegionalIndicator, RegionalIndicator) => regional_indicators % 2 == 1,
+            _ => false,
+        };
+        if !join {
+            return i;
+        }
+
+        emoji_zwj = emoji && cat == Zwj;
+        emoji = pictographic || (emoji && cat == Extend);
+        regional_indicators = match cat {
+            RegionalIndicator => regional_indicators + 1,
+            _ => 0,
+        };
+        prev = cat;
+    }
+    s.len()
+}
+
+/// The byte index of the grapheme boundary before `index` (or `0`). `index` should be on a
+/// boundary itself.
+pub fn prev_grapheme_boundary(s: &str, index: usize) -> usize {
+    let mut boundary = 0;
+    for grapheme in graphemes(&s[..index]) {
+        if boundary + grapheme.len() >= index {
+            break;
+        }
+        boundary += grapheme.len();
+    }
+    boundary
+}
+
+/// The byte index of the grapheme boundary after `index` (or `s.len()`). `index` should be on
+/// a boundary itself.
+pub fn next_grapheme_boundary(s: &str, index: usize) -> usize {
+    index + first_grapheme_len(&s[index..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(s: &str) -> Vec<&str> {
+        graphemes(s).collect()
+    }
+
+    #[test]
+    fn zwj_sequences() {
+        let family = "👨\u{200D}👩\u{200D}👧";
+        assert_eq!(split(&format!("{family}x")), [family, "x"]);
+        assert_eq!(grapheme_width(family), 2);
+        assert_eq!(display_width(family), 2);
+        // ZWJ only joins emoji
+        assert_eq!(split("a\u{200D}b"), ["a\u{200D}", "b"]);
+        assert_eq!(display_width("a\u{200D}b"), 2);
+    }
+
+    #[test]
+    fn flags_pair_up() {
+        let japan = "🇯🇵";
+        let france = "🇫🇷";
+        assert_eq!(split(&format!("{japan}{france}")), [japan, france]);
+        assert_eq!(grapheme_width(japan), 2);
+        // an odd one out is on its own
+        let odd = "\u{1F1EF}\u{1F1F5}\u{1F1FA}";
+        assert_eq!(split(odd), [japan, "\u{1F1FA}"]);
+        assert_eq!(grapheme_width("\u{1F1FA}"), 1);
+        assert_eq!(display_width(odd), 3);
+    }
+
+    #[test]
+    fn hangul_jamo() {
+        let han = "\u{1112}\u{1161}\u{11AB}";
+        assert_eq!(split(&format!("{han}{han}")), [han, han]);
+        assert_eq!(grapheme_width(han), 2);
+        // precomposed LV + T
+        assert_eq!(split("\u{D558}\u{11AB}"), ["\u{D558}\u{11AB}"]);
+        assert_eq!(display_width("\u{D558}\u{11AB}"), 2);
+        // T can't start a syllable after L
+        assert_eq!(split("\u{1112}\u{11AB}").len(), 2);
+    }
+
+    #[test]
+    fn combining_marks() {
+        assert_eq!(split("e\u{301}\u{302}x"), ["e\u{301}\u{302}", "x"]);
+        assert_eq!(display_width("e\u{301}\u{302}x"), 2);
+        assert_eq!(char_width('\u{301}'), 0);
+        // nothing to combine with
+        assert_eq!(grapheme_width("\u{301}"), 0);
+    }
+
+    #[test]
+    fn crlf() {
+        assert_eq!(split("a\r\nb"), ["a", "\r\n", "b"]);
+        assert_eq!(split("\n\r"), ["\n", "\r"]);
+        assert_eq!(display_width("a\r\nb"), 2);
+        assert_eq!(next_grapheme_boundary("a\r\nb", 1), 3);
+        assert_eq!(prev_grapheme_boundary("a\r\nb", 3), 1);
+    }
+
+    #[test]
+    fn presentation_selectors() {
+        let keycap = "1\u{FE0F}\u{20E3}";
+        assert_eq!(split(&format!("{keycap}2")), [keycap, "2"]);
+        assert_eq!(grapheme_width(keycap), 2);
+        assert_eq!(grapheme_width("#\u{FE0F}\u{20E3}"), 2);
+        assert_eq!(grapheme_width("\u{263A}\u{FE0F}"), 2);
+        assert_eq!(grapheme_width("\u{231A}\u{FE0E}"), 1);
+        // not an emoji, so the selector doesn't change anything
+        assert_eq!(grapheme_width("a\u{FE0F}"), 1);
+    }
+
+    #[test]
+    fn soft_hyphen_is_visible() {
+        assert_eq!(char_width('\u{AD}'), 1);
+        assert_eq!(display_width("co\u{AD}op"), 5);
+        assert_eq!(char_width('\u{200B}'), 0);
+    }
+}
@@ -0,0 +1,2171 @@
+// Generated by scripts/gen_unicode_tables.py from the Unicode 14.0.0
+// data files, don't edit this by hand
+
+use super::GraphemeCat;
+
+/// The version of Unicode the tables come from
+pub const UNICODE_VERSION: (u8, u8, u8) = (14, 0, 0);
+
+/// Characters that take up no columns
+pub(crate) const ZERO_WIDTH: &[(u32, u32)] = &[
+    (0x0300, 0x036F),
+    (0x0483, 0x0489),
+    (0x0591, 0x05BD),
+    (0x05BF, 0x05BF),
+    (0x05C1, 0x05C2),
+    (0x05C4, 0x05C5),
+    (0x05C7, 0x05C7),
+    (0x0600, 0x0605),
+    (0x0610, 0x061A),
+    (0x061C, 0x061C),
+    (0x064B, 0x065F),
+    (0x0670, 0x0670),
+    (0x06D6, 0x06DD),
+    (0x06DF, 0x06E4),
+    (0x06E7, 0x06E8),
+    (0x06EA, 0x06ED),
+    (0x070F, 0x070F),
+    (0x0711, 0x0711),
+    (0x0730, 0x074A),
+    (0x07A6, 0x07B0),
+    (0x07EB, 0x07F3),
+    (0x07FD, 0x07FD),
+    (0x0816, 0x0819),
+    (0x081B, 0x0823),
+    (0x0825, 0x0827),
+    (0x0829, 0x082D),
+    (0x0859, 0x085B),
+    (0x0890, 0x0891),
+    (0x0898, 0x089F),
+    (0x08CA, 0x0902),
+    (0x093A, 0x093A),
+    (0x093C, 0x093C),
+    (0x0941, 0x0948),
+    (0x094D, 0x094D),
+    (0x0951, 0x0957),
+    (0x0962, 0x0963),
+    (0x0981, 0x0981),
+    (0x09BC, 0x09BC),
+    (0x09C1, 0x09C4),
+    (0x09CD, 0x09CD),
+    (0x09E2, 0x09E3),
+    (0x09FE, 0x09FE),
+    (0x0A01, 0x0A02),
+    (0x0A3C, 0x0A3C),
+    (0x0A41, 0x0A42),
+    (0x0A47, 0x0A48),
+    (0x0A4B, 0x0A4D),
+    (0x0A51, 0x0A51),
+    (0x0A70, 0x0A71),
+    (0x0A75, 0x0A75),
+    (0x0A81, 0x0A82),
+    (0x0ABC, 0x0ABC),
+    (0x0AC1, 0x0AC5),
+    (0x0AC7, 0x0AC8),
+    (0x0ACD, 0x0ACD),
+    (0x0AE2, 0x0AE3),
+    (0x0AFA, 0x0AFF),
+    (0x0B01, 0x0B01),
+    (0x0B3C, 0x0B3C),
+    (0x0B3F, 0x0B3F),
+    (0x0B41, 0x0B44),
+    (0x0B4D, 0x0B4D),
+    (0x0B55, 0x0B56),
+    (0x0B62, 0x0B63),
+    (0x0B82, 0x0B82),
+    (0x0BC0, 0x0BC0),
+    (0x0BCD, 0x0BCD),
+    (0x0C00, 0x0C00),
+    (0x0C04, 0x0C04),
+    (0x0C3C, 0x0C3C),
+    (0x0C3E, 0x0C40),
+    (0x0C46, 0x0C48),
+    (0x0C4A, 0x0C4D),
+    (0x0C55, 0x0C56),
+    (0x0C62, 0x0C63),
+    (0x0C81, 0x0C81),
+    (0x0CBC, 0x0CBC),
+    (0x0CBF, 0x0CBF),
+    (0x0CC6, 0x0CC6),
+    (0x0CCC, 0x0CCD),
+    (0x0CE2, 0x0CE3),
+    (0x0D00, 0x0D01),
+    (0x0D3B, 0x0D3C),
+    (0x0D41, 0x0D44),
+    (0x0D4D, 0x0D4D),
+    (0x0D62, 0x0D63),
+    (0x0D81, 0x0D81),
+    (0x0DCA, 0x0DCA),
+    (0x0DD2, 0x0DD4),
+    (0x0DD6, 0x0DD6),
+    (0x0E31, 0x0E31),
+    (0x0E34, 0x0E3A),
+    (0x0E47, 0x0E4E),
+    (0x0EB1, 0x0EB1),
+    (0x0EB4, 0x0EBC),
+    (0x0EC8, 0x0ECD),
+    (0x0F18, 0x0F19),
+    (0x0F35, 0x0F35),
+    (0x0F37, 0x0F37),
+    (0x0F39, 0x0F39),
+    (0x0F71, 0x0F7E),
+    (0x0F80, 0x0F84),
+    (0x0F86, 0x0F87),
+    (0x0F8D, 0x0F97),
+    (0x0F99, 0x0FBC),
+    (0x0FC6, 0x0FC6),
+    (0x102D, 0x1030),
+    (0x1032, 0x1037),
+    (0x1039, 0x103A),
+    (0x103D, 0x103E),
+    (0x1058, 0x1059),
+    (0x105E, 0x1060),
+    (0x1071, 0x1074),
+    (0x1082, 0x1082),
+    (0x1085, 0x1086),
+    (0x108D, 0x108D),
+    (0x109D, 0x109D),
+    (0x1160, 0x11FF),
+    (0x135D, 0x135F),
+    (0x1712, 0x1714),
+    (0x1732, 0x1733),
+    (0x1752, 0x1753),
+    (0x1772, 0x1773),
+    (0x17B4, 0x17B5),
+    (0x17B7, 0x17BD),
+    (0x17C6, 0x17C6),
+    (0x17C9, 0x17D3),
+    (0x17DD, 0x17DD),
+    (0x180B, 0x180F),
+    (0x1885, 0x1886),
+    (0x18A9, 0x18A9),
+    (0x1920, 0x1922),
+    (0x1927, 0x1928),
+    (0x1932, 0x1932),
+    (0x1939, 0x193B),
+    (0x1A17, 0x1A18),
+    (0x1A1B, 0x1A1B),
+    (0x1A56, 0x1A56),
+    (0x1A58, 0x1A5E),
+    (0x1A60, 0x1A60),
+    (0x1A62, 0x1A62),
+    (0x1A65, 0x1A6C),
+    (0x1A73, 0x1A7C),
+    (0x1A7F, 0x1A7F),
+    (0x1AB0, 0x1ACE),
+    (0x1B00, 0x1B03),
+    (0x1B34, 0x1B34),
+    (0x1B36, 0x1B3A),
+    (0x1B3C, 0x1B3C),
+    (0x1B42, 0x1B42),
+    (0x1B6B, 0x1B73),
+    (0x1B80, 0x1B81),
+    (0x1BA2, 0x1BA5),
+    (0x1BA8, 0x1BA9),
+    (0x1BAB, 0x1BAD),
+    (0x1BE6, 0x1BE6),
+    (0x1BE8, 0x1BE9),
+    (0x1BED, 0x1BED),
+    (0x1BEF, 0x1BF1),
+    (0x1C2C, 0x1C33),
+    (0x1C36, 0x1C37),
+    (0x1CD0, 0x1CD2),
+    (0x1CD4, 0x1CE0),
+    (0x1CE2, 0x1CE8),
+    (0x1CED, 0x1CED),
+    (0x1CF4, 0x1CF4),
+    (0x1CF8, 0x1CF9),
+    (0x1DC0, 0x1DFF),
+    (0x200B, 0x200F),
+    (0x202A, 0x202E),
+    (0x2060, 0x2064),
+    (0x2066, 0x206F),
+    (0x20D0, 0x20F0),
+    (0x2CEF, 0x2CF1),
+    (0x2D7F, 0x2D7F),
+    (0x2DE0, 0x2DFF),
+    (0x302A, 0x302D),
+    (0x3099, 0x309A),
+    (0xA66F, 0xA672),
+    (0xA674, 0xA67D),
+    (0xA69E, 0xA69F),
+    (0xA6F0, 0xA6F1),
+    (0xA802, 0xA802),
+    (0xA806, 0xA806),
+    (0xA80B, 0xA80B),
+    (0xA825, 0xA826),
+    (0xA82C, 0xA82C),
+    (0xA8C4, 0xA8C5),
+    (0xA8E0, 0xA8F1),
+    (0xA8FF, 0xA8FF),
+    (0xA926, 0xA92D),
+    (0xA947, 0xA951),
+    (0xA980, 0xA982),
+    (0xA9B3, 0xA9B3),
+    (0xA9B6, 0xA9B9),
+    (0xA9BC, 0xA9BD),
+    (0xA9E5, 0xA9E5),
+    (0xAA29, 0xAA2E),
+    (0xAA31, 0xAA32),
+    (0xAA35, 0xAA36),
+    (0xAA43, 0xAA43),
+    (0xAA4C, 0xAA4C),
+    (0xAA7C, 0xAA7C),
+    (0xAAB0, 0xAAB0),
+    (0xAAB2, 0xAAB4),
+    (0xAAB7, 0xAAB8),
+    (0xAABE, 0xAABF),
+    (0xAAC1, 0xAAC1),
+    (0xAAEC, 0xAAED),
+    (0xAAF6, 0xAAF6),
+    (0xABE5, 0xABE5),
+    (0xABE8, 0xABE8),
+    (0xABED, 0xABED),
+    (0xD7B0, 0xD7C6),
+    (0xD7CB, 0xD7FB),
+    (0xFB1E, 0xFB1E),
+    (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F),
+    (0xFEFF, 0xFEFF),
+    (0xFFF9, 0xFFFB),
+    (0x101FD, 0x101FD),
+    (0x102E0, 0x102E0),
+    (0x10376, 0x1037A),
+    (0x10A01, 0x10A03),
+    (0x10A05, 0x10A06),
+    (0x10A0C, 0x10A0F),
+    (0x10A38, 0x10A3A),
+    (0x10A3F, 0x10A3F),
+    (0x10AE5, 0x10AE6),
+    (0x10D24, 0x10D27),
+    (0x10EAB, 0x10EAC),
+    (0x10F46, 0x10F50),
+    (0x10F82, 0x10F85),
+    (0x11001, 0x11001),
+    (0x11038, 0x11046),
+    (0x11070, 0x11070),
+    (0x11073, 0x11074),
+    (0x1107F, 0x11081),
+    (0x110B3, 0x110B6),
+    (0x110B9, 0x110BA),
+    (0x110BD, 0x110BD),
+    (0x110C2, 0x110C2),
+    (0x110CD, 0x110CD),
+    (0x11100, 0x11102),
+    (0x11127, 0x1112B),
+    (0x1112D, 0x11134),
+    (0x11173, 0x11173),
+    (0x11180, 0x11181),
+    (0x111B6, 0x111BE),
+    (0x111C9, 0x111CC),
+    (0x111CF, 0x111CF),
+    (0x1122F, 0x11231),
+    (0x11234, 0x11234),
+    (0x11236, 0x11237),
+    (0x1123E, 0x1123E),
+    (0x112DF, 0x112DF),
+    (0x112E3, 0x112EA),
+    (0x11300, 0x11301),
+    (0x1133B, 0x1133C),
+    (0x11340, 0x11340),
+    (0x11366, 0x1136C),
+    (0x11370, 0x11374),
+    (0x11438, 0x1143F),
+    (0x11442, 0x11444),
+    (0x11446, 0x11446),
+    (0x1145E, 0x1145E),
+    (0x114B3, 0x114B8),
+    (0x114BA, 0x114BA),
+    (0x114BF, 0x114C0),
+    (0x114C2, 0x114C3),
+    (0x115B2, 0x115B5),
+    (0x115BC, 0x115BD),
+    (0x115BF, 0x115C0),
+    (0x115DC, 0x115DD),
+    (0x11633, 0x1163A),
+    (0x1163D, 0x1163D),
+    (0x1163F, 0x11640),
+    (0x116AB, 0x116AB),
+    (0x116AD, 0x116AD),
+    (0x116B0, 0x116B5),
+    (0x116B7, 0x116B7),
+    (0x1171D, 0x1171F),
+    (0x11722, 0x11725),
+    (0x11727, 0x1172B),
+    (0x1182F, 0x11837),
+    (0x11839, 0x1183A),
+    (0x1193B, 0x1193C),
+    (0x1193E, 0x1193E),
+    (0x11943, 0x11943),
+    (0x119D4, 0x119D7),
+    (0x119DA, 0x119DB),
+    (0x119E0, 0x119E0),
+    (0x11A01, 0x11A0A),
+    (0x11A33, 0x11A38),
+    (0x11A3B, 0x11A3E),
+    (0x11A47, 0x11A47),
+    (0x11A51, 0x11A56),
+    (0x11A59, 0x11A5B),
+    (0x11A8A, 0x11A96),
+    (0x11A98, 0x11A99),
+    (0x11C30, 0x11C36),
+    (0x11C38, 0x11C3D),
+    (0x11C3F, 0x11C3F),
+    (0x11C92, 0x11CA7),
+    (0x11CAA, 0x11CB0),
+    (0x11CB2, 0x11CB3),
+    (0x11CB5, 0x11CB6),
+    (0x11D31, 0x11D36),
+    (0x11D3A, 0x11D3A),
+    (0x11D3C, 0x11D3D),
+    (0x11D3F, 0x11D45),
+    (0x11D47, 0x11D47),
+    (0x11D90, 0x11D91),
+    (0x11D95, 0x11D95),
+    (0x11D97, 0x11D97),
+    (0x11EF3, 0x11EF4),
+    (0x13430, 0x13438),
+    (0x16AF0, 0x16AF4),
+    (0x16B30, 0x16B36),
+    (0x16F4F, 0x16F4F),
+    (0x16F8F, 0x16F92),
+    (0x16FE4, 0x16FE4),
+    (0x1BC9D, 0x1BC9E),
+    (0x1BCA0, 0x1BCA3),
+    (0x1CF00, 0x1CF2D),
+    (0x1CF30, 0x1CF46),
+    (0x1D167, 0x1D169),
+    (0x1D173, 0x1D182),
+    (0x1D185, 0x1D18B),
+    (0x1D1AA, 0x1D1AD),
+    (0x1D242, 0x1D244),
+    (0x1DA00, 0x1DA36),
+    (0x1DA3B, 0x1DA6C),
+    (0x1DA75, 0x1DA75),
+    (0x1DA84, 0x1DA84),
+    (0x1DA9B, 0x1DA9F),
+    (0x1DAA1, 0x1DAAF),
+    (0x1E000, 0x1E006),
+    (0x1E008, 0x1E018),
+    (0x1E01B, 0x1E021),
+    (0x1E023, 0x1E024),
+    (0x1E026, 0x1E02A),
+    (0x1E130, 0x1E136),
+    (0x1E2AE, 0x1E2AE),
+    (0x1E2EC, 0x1E2EF),
+    (0x1E8D0, 0x1E8D6),
+    (0x1E944, 0x1E94A),
+    (0xE0001, 0xE0001),
+    (0xE0020, 0xE007F),
+    (0xE0100, 0xE01EF),
+];
+
+/// East Asian Wide and Fullwidth characters, which take up two columns
+pub(crate) const WIDE: &[(u32, u32)] = &[
+    (0x1100, 0x115F),
+    (0x231A, 0x231B),
+    (0x2329, 0x232A),
+    (0x23E9, 0x23EC),
+    (0x23F0, 0x23F0),
+    (0x23F3, 0x23F3),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x267F, 0x267F),
+    (0x2693, 0x2693),
+    (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB),
+    (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5),
+    (0x26CE, 0x26CE),
+    (0x26D4, 0x26D4),
+    (0x26EA, 0x26EA),
+    (0x26F2, 0x26F3),
+    (0x26F5, 0x26F5),
+    (0x26FA, 0x26FA),
+    (0x26FD, 0x26FD),
+    (0x2705, 0x2705),
+    (0x270A, 0x270B),
+    (0x2728, 0x2728),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x2E80, 0x2E99),
+    (0x2E9B, 0x2EF3),
+    (0x2F00, 0x2FD5),
+    (0x2FF0, 0x2FFB),
+    (0x3000, 0x303E),
+    (0x3041, 0x3096),
+    (0x3099, 0x30FF),
+    (0x3105, 0x312F),
+    (0x3131, 0x318E),
+    (0x3190, 0x31E3),
+    (0x31F0, 0x321E),
+    (0x3220, 0x3247),
+    (0x3250, 0x4DBF),
+    (0x4E00, 0xA48C),
+    (0xA490, 0xA4C6),
+    (0xA960, 0xA97C),
+    (0xAC00, 0xD7A3),
+    (0xF900, 0xFAFF),
+    (0xFE10, 0xFE19),
+    (0xFE30, 0xFE52),
+    (0xFE54, 0xFE66),
+    (0xFE68, 0xFE6B),
+    (0xFF01, 0xFF60),
+    (0xFFE0, 0xFFE6),
+    (0x16FE0, 0x16FE4),
+    (0x16FF0, 0x16FF1),
+    (0x17000, 0x187F7),
+    (0x18800, 0x18CD5),
+    (0x18D00, 0x18D08),
+    (0x1AFF0, 0x1AFF3),
+    (0x1AFF5, 0x1AFFB),
+    (0x1AFFD, 0x1AFFE),
+    (0x1B000, 0x1B122),
+    (0x1B150, 0x1B152),
+    (0x1B164, 0x1B167),
+    (0x1B170, 0x1B2FB),
+    (0x1F004, 0x1F004),
+    (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F200, 0x1F202),
+    (0x1F210, 0x1F23B),
+    (0x1F240, 0x1F248),
+    (0x1F250, 0x1F251),
+    (0x1F260, 0x1F265),
+    (0x1F300, 0x1F320),
+    (0x1F32D, 0x1F335),
+    (0x1F337, 0x1F37C),
+    (0x1F37E, 0x1F393),
+    (0x1F3A0, 0x1F3CA),
+    (0x1F3CF, 0x1F3D3),
+    (0x1F3E0, 0x1F3F0),
+    (0x1F3F4, 0x1F3F4),
+    (0x1F3F8, 0x1F43E),
+    (0x1F440, 0x1F440),
+    (0x1F442, 0x1F4FC),
+    (0x1F4FF, 0x1F53D),
+    (0x1F54B, 0x1F54E),
+    (0x1F550, 0x1F567),
+    (0x1F57A, 0x1F57A),
+    (0x1F595, 0x1F596),
+    (0x1F5A4, 0x1F5A4),
+    (0x1F5FB, 0x1F64F),
+    (0x1F680, 0x1F6C5),
+    (0x1F6CC, 0x1F6CC),
+    (0x1F6D0, 0x1F6D2),
+    (0x1F6D5, 0x1F6D7),
+    (0x1F6DD, 0x1F6DF),
+    (0x1F6EB, 0x1F6EC),
+    (0x1F6F4, 0x1F6FC),
+    (0x1F7E0, 0x1F7EB),
+    (0x1F7F0, 0x1F7F0),
+    (0x1F90C, 0x1F93A),
+    (0x1F93C, 0x1F945),
+    (0x1F947, 0x1F9FF),
+    (0x1FA70, 0x1FA74),
+    (0x1FA78, 0x1FA7C),
+    (0x1FA80, 0x1FA86),
+    (0x1FA90, 0x1FAAC),
+    (0x1FAB0, 0x1FABA),
+    (0x1FAC0, 0x1FAC5),
+    (0x1FAD0, 0x1FAD9),
+    (0x1FAE0, 0x1FAE7),
+    (0x1FAF0, 0x1FAF6),
+    (0x20000, 0x2FFFD),
+    (0x30000, 0x3FFFD),
+];
+
+/// Characters that can be drawn as emoji
+pub(crate) const EMOJI: &[(u32, u32)] = &[
+    (0x0023, 0x0023),
+    (0x002A, 0x002A),
+    (0x0030, 0x0039),
+    (0x00A9, 0x00A9),
+    (0x00AE, 0x00AE),
+    (0x203C, 0x203C),
+    (0x2049, 0x2049),
+    (0x2122, 0x2122),
+    (0x2139, 0x2139),
+    (0x2194, 0x2199),
+    (0x21A9, 0x21AA),
+    (0x231A, 0x231B),
+    (0x2328, 0x2328),
+    (0x23CF, 0x23CF),
+    (0x23E9, 0x23F3),
+    (0x23F8, 0x23FA),
+    (0x24C2, 0x24C2),
+    (0x25AA, 0x25AB),
+    (0x25B6, 0x25B6),
+    (0x25C0, 0x25C0),
+    (0x25FB, 0x25FE),
+    (0x2600, 0x2604),
+    (0x260E, 0x260E),
+    (0x2611, 0x2611),
+    (0x2614, 0x2615),
+    (0x2618, 0x2618),
+    (0x261D, 0x261D),
+    (0x2620, 0x2620),
+    (0x2622, 0x2623),
+    (0x2626, 0x2626),
+    (0x262A, 0x262A),
+    (0x262E, 0x262F),
+    (0x2638, 0x263A),
+    (0x2640, 0x2640),
+    (0x2642, 0x2642),
+    (0x2648, 0x2653),
+    (0x265F, 0x2660),
+    (0x2663, 0x2663),
+    (0x2665, 0x2666),
+    (0x2668, 0x2668),
+    (0x267B, 0x267B),
+    (0x267E, 0x267F),
+    (0x2692, 0x2697),
+    (0x2699, 0x2699),
+    (0x269B, 0x269C),
+    (0x26A0, 0x26A1),
+    (0x26A7, 0x26A7),
+    (0x26AA, 0x26AB),
+    (0x26B0, 0x26B1),
+    (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5),
+    (0x26C8, 0x26C8),
+    (0x26CE, 0x26CF),
+    (0x26D1, 0x26D1),
+    (0x26D3, 0x26D4),
+    (0x26E9, 0x26EA),
+    (0x26F0, 0x26F5),
+    (0x26F7, 0x26FA),
+    (0x26FD, 0x26FD),
+    (0x2702, 0x2702),
+    (0x2705, 0x2705),
+    (0x2708, 0x270D),
+    (0x270F, 0x270F),
+    (0x2712, 0x2712),
+    (0x2714, 0x2714),
+    (0x2716, 0x2716),
+    (0x271D, 0x271D),
+    (0x2721, 0x2721),
+    (0x2728, 0x2728),
+    (0x2733, 0x2734),
+    (0x2744, 0x2744),
+    (0x2747, 0x2747),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2763, 0x2764),
+    (0x2795, 0x2797),
+    (0x27A1, 0x27A1),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2934, 0x2935),
+    (0x2B05, 0x2B07),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x3030, 0x3030),
+    (0x303D, 0x303D),
+    (0x3297, 0x3297),
+    (0x3299, 0x3299),
+    (0x1F004, 0x1F004),
+    (0x1F0CF, 0x1F0CF),
+    (0x1F170, 0x1F171),
+    (0x1F17E, 0x1F17F),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F1E6, 0x1F1FF),
+    (0x1F201, 0x1F202),
+    (0x1F21A, 0x1F21A),
+    (0x1F22F, 0x1F22F),
+    (0x1F232, 0x1F23A),
+    (0x1F250, 0x1F251),
+    (0x1F300, 0x1F321),
+    (0x1F324, 0x1F393),
+    (0x1F396, 0x1F397),
+    (0x1F399, 0x1F39B),
+    (0x1F39E, 0x1F3F0),
+    (0x1F3F3, 0x1F3F5),
+    (0x1F3F7, 0x1F4FD),
+    (0x1F4FF, 0x1F53D),
+    (0x1F549, 0x1F54E),
+    (0x1F550, 0x1F567),
+    (0x1F56F, 0x1F570),
+    (0x1F573, 0x1F57A),
+    (0x1F587, 0x1F587),
+    (0x1F58A, 0x1F58D),
+    (0x1F590, 0x1F590),
+    (0x1F595, 0x1F596),
+    (0x1F5A4, 0x1F5A5),
+    (0x1F5A8, 0x1F5A8),
+    (0x1F5B1, 0x1F5B2),
+    (0x1F5BC, 0x1F5BC),
+    (0x1F5C2, 0x1F5C4),
+    (0x1F5D1, 0x1F5D3),
+    (0x1F5DC, 0x1F5DE),
+    (0x1F5E1, 0x1F5E1),
+    (0x1F5E3, 0x1F5E3),
+    (0x1F5E8, 0x1F5E8),
+    (0x1F5EF, 0x1F5EF),
+    (0x1F5F3, 0x1F5F3),
+    (0x1F5FA, 0x1F64F),
+    (0x1F680, 0x1F6C5),
+    (0x1F6CB, 0x1F6D2),
+    (0x1F6D5, 0x1F6D7),
+    (0x1F6DD, 0x1F6E5),
+    (0x1F6E9, 0x1F6E9),
+    (0x1F6EB, 0x1F6EC),
+    (0x1F6F0, 0x1F6F0),
+    (0x1F6F3, 0x1F6FC),
+    (0x1F7E0, 0x1F7EB),
+    (0x1F7F0, 0x1F7F0),
+    (0x1F90C, 0x1F93A),
+    (0x1F93C, 0x1F945),
+    (0x1F947, 0x1F9FF),
+    (0x1FA70, 0x1FA74),
+    (0x1FA78, 0x1FA7C),
+    (0x1FA80, 0x1FA86),
+    (0x1FA90, 0x1FAAC),
+    (0x1FAB0, 0x1FABA),
+    (0x1FAC0, 0x1FAC5),
+    (0x1FAD0, 0x1FAD9),
+    (0x1FAE0, 0x1FAE7),
+    (0x1FAF0, 0x1FAF6),
+];
+
+/// Emoji that are drawn as emoji (not text) by default
+pub(crate) const EMOJI_PRESENTATION: &[(u32, u32)] = &[
+    (0x231A, 0x231B),
+    (0x23E9, 0x23EC),
+    (0x23F0, 0x23F0),
+    (0x23F3, 0x23F3),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x267F, 0x267F),
+    (0x2693, 0x2693),
+    (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB),
+    (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5),
+    (0x26CE, 0x26CE),
+    (0x26D4, 0x26D4),
+    (0x26EA, 0x26EA),
+    (0x26F2, 0x26F3),
+    (0x26F5, 0x26F5),
+    (0x26FA, 0x26FA),
+    (0x26FD, 0x26FD),
+    (0x2705, 0x2705),
+    (0x270A, 0x270B),
+    (0x2728, 0x2728),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x1F004, 0x1F004),
+    (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F1E6, 0x1F1FF),
+    (0x1F201, 0x1F201),
+    (0x1F21A, 0x1F21A),
+    (0x1F22F, 0x1F22F),
+    (0x1F232, 0x1F236),
+    (0x1F238, 0x1F23A),
+    (0x1F250, 0x1F251),
+    (0x1F300, 0x1F320),
+    (0x1F32D, 0x1F335),
+    (0x1F337, 0x1F37C),
+    (0x1F37E, 0x1F393),
+    (0x1F3A0, 0x1F3CA),
+    (0x1F3CF, 0x1F3D3),
+    (0x1F3E0, 0x1F3F0),
+    (0x1F3F4, 0x1F3F4),
+    (0x1F3F8, 0x1F43E),
+    (0x1F440, 0x1F440),
+    (0x1F442, 0x1F4FC),
+    (0x1F4FF, 0x1F53D),
+    (0x1F54B, 0x1F54E),
+    (0x1F550, 0x1F567),
+    (0x1F57A, 0x1F57A),
+    (0x1F595, 0x1F596),
+    (0x1F5A4, 0x1F5A4),
+    (0x1F5FB, 0x1F64F),
+    (0x1F680, 0x1F6C5),
+    (0x1F6CC, 0x1F6CC),
+    (0x1F6D0, 0x1F6D2),
+    (0x1F6D5, 0x1F6D7),
+    (0x1F6DD, 0x1F6DF),
+    (0x1F6EB, 0x1F6EC),
+    (0x1F6F4, 0x1F6FC),
+    (0x1F7E0, 0x1F7EB),
+    (0x1F7F0, 0x1F7F0),
+    (0x1F90C, 0x1F93A),
+    (0x1F93C, 0x1F945),
+    (0x1F947, 0x1F9FF),
+    (0x1FA70, 0x1FA74),
+    (0x1FA78, 0x1FA7C),
+    (0x1FA80, 0x1FA86),
+    (0x1FA90, 0x1FAAC),
+    (0x1FAB0, 0x1FABA),
+    (0x1FAC0, 0x1FAC5),
+    (0x1FAD0, 0x1FAD9),
+    (0x1FAE0, 0x1FAE7),
+    (0x1FAF0, 0x1FAF6),
+];
+
+/// Emoji, and code points reserved for future emoji
+pub(crate) const EXTENDED_PICTOGRAPHIC: &[(u32, u32)] = &[
+    (0x00A9, 0x00A9),
+    (0x00AE, 0x00AE),
+    (0x203C, 0x203C),
+    (0x2049, 0x2049),
+    (0x2122, 0x2122),
+    (0x2139, 0x2139),
+    (0x2194, 0x2199),
+    (0x21A9, 0x21AA),
+    (0x231A, 0x231B),
+    (0x2328, 0x2328),
+    (0x2388, 0x2388),
+    (0x23CF, 0x23CF),
+    (0x23E9, 0x23F3),
+    (0x23F8, 0x23FA),
+    (0x24C2, 0x24C2),
+    (0x25AA, 0x25AB),
+    (0x25B6, 0x25B6),
+    (0x25C0, 0x25C0),
+    (0x25FB, 0x25FE),
+    (0x2600, 0x2605),
+    (0x2607, 0x2612),
+    (0x2614, 0x2685),
+    (0x2690, 0x2705),
+    (0x2708, 0x2712),
+    (0x2714, 0x2714),
+    (0x2716, 0x2716),
+    (0x271D, 0x271D),
+    (0x2721, 0x2721),
+    (0x2728, 0x2728),
+    (0x2733, 0x2734),
+    (0x2744, 0x2744),
+    (0x2747, 0x2747),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2763, 0x2767),
+    (0x2795, 0x2797),
+    (0x27A1, 0x27A1),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2934, 0x2935),
+    (0x2B05, 0x2B07),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x3030, 0x3030),
+    (0x303D, 0x303D),
+    (0x3297, 0x3297),
+    (0x3299, 0x3299),
+    (0x1F000, 0x1F0FF),
+    (0x1F10D, 0x1F10F),
+    (0x1F12F, 0x1F12F),
+    (0x1F16C, 0x1F171),
+    (0x1F17E, 0x1F17F),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F1AD, 0x1F1E5),
+    (0x1F201, 0x1F20F),
+    (0x1F21A, 0x1F21A),
+    (0x1F22F, 0x1F22F),
+    (0x1F232, 0x1F23A),
+    (0x1F23C, 0x1F23F),
+    (0x1F249, 0x1F3FA),
+    (0x1F400, 0x1F53D),
+    (0x1F546, 0x1F64F),
+    (0x1F680, 0x1F6FF),
+    (0x1F774, 0x1F77F),
+    (0x1F7D5, 0x1F7FF),
+    (0x1F80C, 0x1F80F),
+    (0x1F848, 0x1F84F),
+    (0x1F85A, 0x1F85F),
+    (0x1F888, 0x1F88F),
+    (0x1F8AE, 0x1F8FF),
+    (0x1F90C, 0x1F93A),
+    (0x1F93C, 0x1F945),
+    (0x1F947, 0x1FAFF),
+    (0x1FC00, 0x1FFFD),
+];
+
+/// The Grapheme_Cluster_Break property, anything else is `Other`
+pub(crate) const GRAPHEME_BREAK: &[(u32, u32, GraphemeCat)] = &[
+    (0x0000, 0x0009, GraphemeCat::Control),
+    (0x000A, 0x000A, GraphemeCat::Lf),
+    (0x000B, 0x000C, GraphemeCat::Control),
+    (0x000D, 0x000D, GraphemeCat::Cr),
+    (0x000E, 0x001F, GraphemeCat::Control),
+    (0x007F, 0x009F, GraphemeCat::Control),
+    (0x00AD, 0x00AD, GraphemeCat::Control),
+    (0x0300, 0x036F, GraphemeCat::Extend),
+    (0x0483, 0x0489, GraphemeCat::Extend),
+    (0x0591, 0x05BD, GraphemeCat::Extend),
+    (0x05BF, 0x05BF, GraphemeCat::Extend),
+    (0x05C1, 0x05C2, GraphemeCat::Extend),
+    (0x05C4, 0x05C5, GraphemeCat::Extend),
+    (0x05C7, 0x05C7, GraphemeCat::Extend),
+    (0x0600, 0x0605, GraphemeCat::Prepend),
+    (0x0610, 0x061A, GraphemeCat::Extend),
+    (0x061C, 0x061C, GraphemeCat::Control),
+    (0x064B, 0x065F, GraphemeCat::Extend),
+    (0x0670, 0x0670, GraphemeCat::Extend),
+    (0x06D6, 0x06DC, GraphemeCat::Extend),
+    (0x06DD, 0x06DD, GraphemeCat::Prepend),
+    (0x06DF, 0x06E4, GraphemeCat::Extend),
+    (0x06E7, 0x06E8, GraphemeCat::Extend),
+    (0x06EA, 0x06ED, GraphemeCat::Extend),
+    (0x070F, 0x070F, GraphemeCat::Prepend),
+    (0x0711, 0x0711, GraphemeCat::Extend),
+    (0x0730, 0x074A, GraphemeCat::Extend),
+    (0x07A6, 0x07B0, GraphemeCat::Extend),
+    (0x07EB, 0x07F3, GraphemeCat::Extend),
+    (0x07FD, 0x07FD, GraphemeCat::Extend),
+    (0x0816, 0x0819, GraphemeCat::Extend),
+    (0x081B, 0x0823, GraphemeCat::Extend),
+    (0x0825, 0x0827, GraphemeCat::Extend),
+    (0x0829, 0x082D, GraphemeCat::Extend),
+    (0x0859, 0x085B, GraphemeCat::Extend),
+    (0x0890, 0x0891, GraphemeCat::Prepend),
+    (0x0898, 0x089F, GraphemeCat::Extend),
+    (0x08CA, 0x08E1, GraphemeCat::Extend),
+    (0x08E2, 0x08E2, GraphemeCat::Prepend),
+    (0x08E3, 0x0902, GraphemeCat::Extend),
+    (0x0903, 0x0903, GraphemeCat::SpacingMark),
+    (0x093A, 0x093A, GraphemeCat::Extend),
+    (0x093B, 0x093B, GraphemeCat::SpacingMark),
+    (0x093C, 0x093C, GraphemeCat::Extend),
+    (0x093E, 0x0940, GraphemeCat::SpacingMark),
+    (0x0941, 0x0948, GraphemeCat::Extend),
+    (0x0949, 0x094C, GraphemeCat::SpacingMark),
+    (0x094D, 0x094D, GraphemeCat::Extend),
+    (0x094E, 0x094F, GraphemeCat::SpacingMark),
+    (0x0951, 0x0957, GraphemeCat::Extend),
+    (0x0962, 0x0963, GraphemeCat::Extend),
+    (0x0981, 0x0981, GraphemeCat::Extend),
+    (0x0982, 0x0983, GraphemeCat::SpacingMark),
+    (0x09BC, 0x09BC, GraphemeCat::Extend),
+    (0x09BE, 0x09BE, GraphemeCat::Extend),
+    (0x09BF, 0x09C0, GraphemeCat::SpacingMark),
+    (0x09C1, 0x09C4, GraphemeCat::Extend),
+    (0x09C7, 0x09C8, GraphemeCat::SpacingMark),
+    (0x09CB, 0x09CC, GraphemeCat::SpacingMark),
+    (0x09CD, 0x09CD, GraphemeCat::Extend),
+    (0x09D7, 0x09D7, GraphemeCat::Extend),
+    (0x09E2, 0x09E3, GraphemeCat::Extend),
+    (0x09FE, 0x09FE, GraphemeCat::Extend),
+    (0x0A01, 0x0A02, GraphemeCat::Extend),
+    (0x0A03, 0x0A03, GraphemeCat::SpacingMark),
+    (0x0A3C, 0x0A3C, GraphemeCat::Extend),
+    (0x0A3E, 0x0A40, GraphemeCat::SpacingMark),
+    (0x0A41, 0x0A42, GraphemeCat::Extend),
+    (0x0A47, 0x0A48, GraphemeCat::Extend),
+    (0x0A4B, 0x0A4D, GraphemeCat::Extend),
+    (0x0A51, 0x0A51, GraphemeCat::Extend),
+    (0x0A70, 0x0A71, GraphemeCat::Extend),
+    (0x0A75, 0x0A75, GraphemeCat::Extend),
+    (0x0A81, 0x0A82, GraphemeCat::Extend),
+    (0x0A83, 0x0A83, GraphemeCat::SpacingMark),
+    (0x0ABC, 0x0ABC, GraphemeCat::Extend),
+    (0x0ABE, 0x0AC0, GraphemeCat::SpacingMark),
+    (0x0AC1, 0x0AC5, GraphemeCat::Extend),
+    (0x0AC7, 0x0AC8, GraphemeCat::Extend),
+    (0x0AC9, 0x0AC9, GraphemeCat::SpacingMark),
+    (0x0ACB, 0x0ACC, GraphemeCat::SpacingMark),
+    (0x0ACD, 0x0ACD, GraphemeCat::Extend),
+    (0x0AE2, 0x0AE3, GraphemeCat::Extend),
+    (0x0AFA, 0x0AFF, GraphemeCat::Extend),
+    (0x0B01, 0x0B01, GraphemeCat::Extend),
+    (0x0B02, 0x0B03, GraphemeCat::SpacingMark),
+    (0x0B3C, 0x0B3C, GraphemeCat::Extend),
+    (0x0B3E, 0x0B3F, GraphemeCat::Extend),
+    (0x0B40, 0x0B40, GraphemeCat::SpacingMark),
+    (0x0B41, 0x0B44, GraphemeCat::Extend),
+    (0x0B47, 0x0B48, GraphemeCat::SpacingMark),
+    (0x0B4B, 0x0B4C, GraphemeCat::SpacingMark),
+    (0x0B4D, 0x0B4D, GraphemeCat::Extend),
+    (0x0B55, 0x0B57, GraphemeCat::Extend),
+    (0x0B62, 0x0B63, GraphemeCat::Extend),
+    (0x0B82, 0x0B82, GraphemeCat::Extend),
+    (0x0BBE, 0x0BBE, GraphemeCat::Extend),
+    (0x0BBF, 0x0BBF, GraphemeCat::SpacingMark),
+    (0x0BC0, 0x0BC0, GraphemeCat::Extend),
+    (0x0BC1, 0x0BC2, GraphemeCat::SpacingMark),
+    (0x0BC6, 0x0BC8, GraphemeCat::SpacingMark),
+    (0x0BCA, 0x0BCC, GraphemeCat::SpacingMark),
+    (0x0BCD, 0x0BCD, GraphemeCat::Extend),
+    (0x0BD7, 0x0BD7, GraphemeCat::Extend),
+    (0x0C00, 0x0C00, GraphemeCat::Extend),
+    (0x0C01, 0x0C03, GraphemeCat::SpacingMark),
+    (0x0C04, 0x0C04, GraphemeCat::Extend),
+    (0x0C3C, 0x0C3C, GraphemeCat::Extend),
+    (0x0C3E, 0x0C40, GraphemeCat::Extend),
+    (0x0C41, 0x0C44, GraphemeCat::SpacingMark),
+    (0x0C46, 0x0C48, GraphemeCat::Extend),
+    (0x0C4A, 0x0C4D, GraphemeCat::Extend),
+    (0x0C55, 0x0C56, GraphemeCat::Extend),
+    (0x0C62, 0x0C63, GraphemeCat::Extend),
+    (0x0C81, 0x0C81, GraphemeCat::Extend),
+    (0x0C82, 0x0C83, GraphemeCat::SpacingMark),
+    (0x0CBC, 0x0CBC, GraphemeCat::Extend),
+    (0x0CBE, 0x0CBE, GraphemeCat::SpacingMark),
+    (0x0CBF, 0x0CBF, GraphemeCat::Extend),
+    (0x0CC0, 0x0CC1, GraphemeCat::SpacingMark),
+    (0x0CC2, 0x0CC2, GraphemeCat::Extend),
+    (0x0CC3, 0x0CC4, GraphemeCat::SpacingMark),
+    (0x0CC6, 0x0CC6, GraphemeCat::Extend),
+    (0x0CC7, 0x0CC8, GraphemeCat::SpacingMark),
+    (0x0CCA, 0x0CCB, GraphemeCat::SpacingMark),
+    (0x0CCC, 0x0CCD, GraphemeCat::Extend),
+    (0x0CD5, 0x0CD6, GraphemeCat::Extend),
+    (0x0CE2, 0x0CE3, GraphemeCat::Extend),
+    (0x0D00, 0x0D01, GraphemeCat::Extend),
+    (0x0D02, 0x0D03, GraphemeCat::SpacingMark),
+    (0x0D3B, 0x0D3C, GraphemeCat::Extend),
+    (0x0D3E, 0x0D3E, GraphemeCat::Extend),
+    (0x0D3F, 0x0D40, GraphemeCat::SpacingMark),
+    (0x0D41, 0x0D44, GraphemeCat::Extend),
+    (0x0D46, 0x0D48, GraphemeCat::SpacingMark),
+    (0x0D4A, 0x0D4C, GraphemeCat::SpacingMark),
+    (0x0D4D, 0x0D4D, GraphemeCat::Extend),
+    (0x0D4E, 0x0D4E, GraphemeCat::Prepend),
+    (0x0D57, 0x0D57, GraphemeCat::Extend),
+    (0x0D62, 0x0D63, GraphemeCat::Extend),
+    (0x0D81, 0x0D81, GraphemeCat::Extend),
+    (0x0D82, 0x0D83, GraphemeCat::SpacingMark),
+    (0x0DCA, 0x0DCA, GraphemeCat::Extend),
+    (0x0DCF, 0x0DCF, GraphemeCat::Extend),
+    (0x0DD0, 0x0DD1, GraphemeCat::SpacingMark),
+    (0x0DD2, 0x0DD4, GraphemeCat::Extend),
+    (0x0DD6, 0x0DD6, GraphemeCat::Extend),
+    (0x0DD8, 0x0DDE, GraphemeCat::SpacingMark),
+    (0x0DDF, 0x0DDF, GraphemeCat::Extend),
+    (0x0DF2, 0x0DF3, GraphemeCat::SpacingMark),
+    (0x0E31, 0x0E31, GraphemeCat::Extend),
+    (0x0E33, 0x0E33, GraphemeCat::SpacingMark),
+    (0x0E34, 0x0E3A, GraphemeCat::Extend),
+    (0x0E47, 0x0E4E, GraphemeCat::Extend),
+    (0x0EB1, 0x0EB1, GraphemeCat::Extend),
+    (0x0EB3, 0x0EB3, GraphemeCat::SpacingMark),
+    (0x0EB4, 0x0EBC, GraphemeCat::Extend),
+    (0x0EC8, 0x0ECD, GraphemeCat::Extend),
+    (0x0F18, 0x0F19, GraphemeCat::Extend),
+    (0x0F35, 0x0F35, GraphemeCat::Extend),
+    (0x0F37, 0x0F37, GraphemeCat::Extend),
+    (0x0F39, 0x0F39, GraphemeCat::Extend),
+    (0x0F3E, 0x0F3F, GraphemeCat::SpacingMark),
+    (0x0F71, 0x0F7E, GraphemeCat::Extend),
+    (0x0F7F, 0x0F7F, GraphemeCat::SpacingMark),
+    (0x0F80, 0x0F84, GraphemeCat::Extend),
+    (0x0F86, 0x0F87, GraphemeCat::Extend),
+    (0x0F8D, 0x0F97, GraphemeCat::Extend),
+    (0x0F99, 0x0FBC, GraphemeCat::Extend),
+    (0x0FC6, 0x0FC6, GraphemeCat::Extend),
+    (0x102D, 0x1030, GraphemeCat::Extend),
+    (0x1031, 0x1031, GraphemeCat::SpacingMark),
+    (0x1032, 0x1037, GraphemeCat::Extend),
+    (0x1039, 0x103A, GraphemeCat::Extend),
+    (0x103B, 0x103C, GraphemeCat::SpacingMark),
+    (0x103D, 0x103E, GraphemeCat::Extend),
+    (0x1056, 0x1057, GraphemeCat::SpacingMark),
+    (0x1058, 0x1059, GraphemeCat::Extend),
+    (0x105E, 0x1060, GraphemeCat::Extend),
+    (0x1071, 0x1074, GraphemeCat::Extend),
+    (0x1082, 0x1082, GraphemeCat::Extend),
+    (0x1084, 0x1084, GraphemeCat::SpacingMark),
+    (0x1085, 0x1086, GraphemeCat::Extend),
+    (0x108D, 0x108D, GraphemeCat::Extend),
+    (0x109D, 0x109D, GraphemeCat::Extend),
+    (0x1100, 0x115F, GraphemeCat::L),
+    (0x1160, 0x11A7, GraphemeCat::V),
+    (0x11A8, 0x11FF, GraphemeCat::T),
+    (0x135D, 0x135F, GraphemeCat::Extend),
+    (0x1712, 0x1714, GraphemeCat::Extend),
+    (0x1715, 0x1715, GraphemeCat::SpacingMark),
+    (0x1732, 0x1733, GraphemeCat::Extend),
+    (0x1734, 0x1734, GraphemeCat::SpacingMark),
+    (0x1752, 0x1753, GraphemeCat::Extend),
+    (0x1772, 0x1773, GraphemeCat::Extend),
+    (0x17B4, 0x17B5, GraphemeCat::Extend),
+    (0x17B6, 0x17B6, GraphemeCat::SpacingMark),
+    (0x17B7, 0x17BD, GraphemeCat::Extend),
+    (0x17BE, 0x17C5, GraphemeCat::SpacingMark),
+    (0x17C6, 0x17C6, GraphemeCat::Extend),
+    (0x17C7, 0x17C8, GraphemeCat::SpacingMark),
+    (0x17C9, 0x17D3, GraphemeCat::Extend),
+    (0x17DD, 0x17DD, GraphemeCat::Extend),
+    (0x180B, 0x180D, GraphemeCat::Extend),
+    (0x180E, 0x180E, GraphemeCat::Control),
+    (0x180F, 0x180F, GraphemeCat::Extend),
+    (0x1885, 0x1886, GraphemeCat::Extend),
+    (0x18A9, 0x18A9, GraphemeCat::Extend),
+    (0x1920, 0x1922, GraphemeCat::Extend),
+    (0x1923, 0x1926, GraphemeCat::SpacingMark),
+    (0x1927, 0x1928, GraphemeCat::Extend),
+    (0x1929, 0x192B, GraphemeCat::SpacingMark),
+    (0x1930, 0x1931, GraphemeCat::SpacingMark),
+    (0x1932, 0x1932, GraphemeCat::Extend),
+    (0x1933, 0x1938, GraphemeCat::SpacingMark),
+    (0x1939, 0x193B, GraphemeCat::Extend),
+    (0x1A17, 0x1A18, GraphemeCat::Extend),
+    (0x1A19, 0x1A1A, GraphemeCat::SpacingMark),
+    (0x1A1B, 0x1A1B, GraphemeCat::Extend),
+    (0x1A55, 0x1A55, GraphemeCat::SpacingMark),
+    (0x1A56, 0x1A56, GraphemeCat::Extend),
+    (0x1A57, 0x1A57, GraphemeCat::SpacingMark),
+    (0x1A58, 0x1A5E, GraphemeCat::Extend),
+    (0x1A60, 0x1A60, GraphemeCat::Extend),
+    (0x1A62, 0x1A62, GraphemeCat::Extend),
+    (0x1A65, 0x1A6C, GraphemeCat::Extend),
+    (0x1A6D, 0x1A72, GraphemeCat::SpacingMark),
+    (0x1A73, 0x1A7C, GraphemeCat::Extend),
+    (0x1A7F, 0x1A7F, GraphemeCat::Extend),
+    (0x1AB0, 0x1ACE, GraphemeCat::Extend),
+    (0x1B00, 0x1B03, GraphemeCat::Extend),
+    (0x1B04, 0x1B04, GraphemeCat::SpacingMark),
+    (0x1B34, 0x1B3A, GraphemeCat::Extend),
+    (0x1B3B, 0x1B3B, GraphemeCat::SpacingMark),
+    (0x1B3C, 0x1B3C, GraphemeCat::Extend),
+    (0x1B3D, 0x1B41, GraphemeCat::SpacingMark),
+    (0x1B42, 0x1B42, GraphemeCat::Extend),
+    (0x1B43, 0x1B44, GraphemeCat::SpacingMark),
+    (0x1B6B, 0x1B73, GraphemeCat::Extend),
+    (0x1B80, 0x1B81, GraphemeCat::Extend),
+    (0x1B82, 0x1B82, GraphemeCat::SpacingMark),
+    (0x1BA1, 0x1BA1, GraphemeCat::SpacingMark),
+    (0x1BA2, 0x1BA5, GraphemeCat::Extend),
+    (0x1BA6, 0x1BA7, GraphemeCat::SpacingMark),
+    (0x1BA8, 0x1BA9, GraphemeCat::Extend),
+    (0x1BAA, 0x1BAA, GraphemeCat::SpacingMark),
+    (0x1BAB, 0x1BAD, GraphemeCat::Extend),
+    (0x1BE6, 0x1BE6, GraphemeCat::Extend),
+    (0x1BE7, 0x1BE7, GraphemeCat::SpacingMark),
+    (0x1BE8, 0x1BE9, GraphemeCat::Extend),
+    (0x1BEA, 0x1BEC, GraphemeCat::SpacingMark),
+    (0x1BED, 0x1BED, GraphemeCat::Extend),
+    (0x1BEE, 0x1BEE, GraphemeCat::SpacingMark),
+    (0x1BEF, 0x1BF1, GraphemeCat::Extend),
+    (0x1BF2, 0x1BF3, GraphemeCat::SpacingMark),
+    (0x1C24, 0x1C2B, GraphemeCat::SpacingMark),
+    (0x1C2C, 0x1C33, GraphemeCat::Extend),
+    (0x1C34, 0x1C35, GraphemeCat::SpacingMark),
+    (0x1C36, 0x1C37, GraphemeCat::Extend),
+    (0x1CD0, 0x1CD2, GraphemeCat::Extend),
+    (0x1CD4, 0x1CE0, GraphemeCat::Extend),
+    (0x1CE1, 0x1CE1, GraphemeCat::SpacingMark),
+    (0x1CE2, 0x1CE8, GraphemeCat::Extend),
+    (0x1CED, 0x1CED, GraphemeCat::Extend),
+    (0x1CF4, 0x1CF4, GraphemeCat::Extend),
+    (0x1CF7, 0x1CF7, GraphemeCat::SpacingMark),
+    (0x1CF8, 0x1CF9, GraphemeCat::Extend),
+    (0x1DC0, 0x1DFF, GraphemeCat::Extend),
+    (0x200B, 0x200B, GraphemeCat::Control),
+    (0x200C, 0x200C, GraphemeCat::Extend),
+    (0x200D, 0x200D, GraphemeCat::Zwj),
+    (0x200E, 0x200F, GraphemeCat::Control),
+    (0x2028, 0x202E, GraphemeCat::Control),
+    (0x2060, 0x206F, GraphemeCat::Control),
+    (0x20D0, 0x20F0, GraphemeCat::Extend),
+    (0x2CEF, 0x2CF1, GraphemeCat::Extend),
+    (0x2D7F, 0x2D7F, GraphemeCat::Extend),
+    (0x2DE0, 0x2DFF, GraphemeCat::Extend),
+    (0x302A, 0x302F, GraphemeCat::Extend),
+    (0x3099, 0x309A, GraphemeCat::Extend),
+    (0xA66F, 0xA672, GraphemeCat::Extend),
+    (0xA674, 0xA67D, GraphemeCat::Extend),
+    (0xA69E, 0xA69F, GraphemeCat::Extend),
+    (0xA6F0, 0xA6F1, GraphemeCat::Extend),
+    (0xA802, 0xA802, GraphemeCat::Extend),
+    (0xA806, 0xA806, GraphemeCat::Extend),
+    (0xA80B, 0xA80B, GraphemeCat::Extend),
+    (0xA823, 0xA824, GraphemeCat::SpacingMark),
+    (0xA825, 0xA826, GraphemeCat::Extend),
+    (0xA827, 0xA827, GraphemeCat::SpacingMark),
+    (0xA82C, 0xA82C, GraphemeCat::Extend),
+    (0xA880, 0xA881, GraphemeCat::SpacingMark),
+    (0xA8B4, 0xA8C3, GraphemeCat::SpacingMark),
+    (0xA8C4, 0xA8C5, GraphemeCat::Extend),
+    (0xA8E0, 0xA8F1, GraphemeCat::Extend),
+    (0xA8FF, 0xA8FF, GraphemeCat::Extend),
+    (0xA926, 0xA92D, GraphemeCat::Extend),
+    (0xA947, 0xA951, GraphemeCat::Extend),
+    (0xA952, 0xA953, GraphemeCat::SpacingMark),
+    (0xA960, 0xA97C, GraphemeCat::L),
+    (0xA980, 0xA982, GraphemeCat::Extend),
+    (0xA983, 0xA983, GraphemeCat::SpacingMark),
+    (0xA9B3, 0xA9B3, GraphemeCat::Extend),
+    (0xA9B4, 0xA9B5, GraphemeCat::SpacingMark),
+    (0xA9B6, 0xA9B9, GraphemeCat::Extend),
+    (0xA9BA, 0xA9BB, GraphemeCat::SpacingMark),
+    (0xA9BC, 0xA9BD, GraphemeCat::Extend),
+    (0xA9BE, 0xA9C0, GraphemeCat::SpacingMark),
+    (0xA9E5, 0xA9E5, GraphemeCat::Extend),
+    (0xAA29, 0xAA2E, GraphemeCat::Extend),
+    (0xAA2F, 0xAA30, GraphemeCat::SpacingMark),
+    (0xAA31, 0xAA32, GraphemeCat::Extend),
+    (0xAA33, 0xAA34, GraphemeCat::SpacingMark),
+    (0xAA35, 0xAA36, GraphemeCat::Extend),
+    (0xAA43, 0xAA43, GraphemeCat::Extend),
+    (0xAA4C, 0xAA4C, GraphemeCat::Extend),
+    (0xAA4D, 0xAA4D, GraphemeCat::SpacingMark),
+    (0xAA7C, 0xAA7C, GraphemeCat::Extend),
+    (0xAAB0, 0xAAB0, GraphemeCat::Extend),
+    (0xAAB2, 0xAAB4, GraphemeCat::Extend),
+    (0xAAB7, 0xAAB8, GraphemeCat::Extend),
+    (0xAABE, 0xAABF, GraphemeCat::Extend),
+    (0xAAC1, 0xAAC1, GraphemeCat::Extend),
+    (0xAAEB, 0xAAEB, GraphemeCat::SpacingMark),
+    (0xAAEC, 0xAAED, GraphemeCat::Extend),
+    (0xAAEE, 0xAAEF, GraphemeCat::SpacingMark),
+    (0xAAF5, 0xAAF5, GraphemeCat::SpacingMark),
+    (0xAAF6, 0xAAF6, GraphemeCat::Extend),
+    (0xABE3, 0xABE4, GraphemeCat::SpacingMark),
+    (0xABE5, 0xABE5, GraphemeCat::Extend),
+    (0xABE6, 0xABE7, GraphemeCat::SpacingMark),
+    (0xABE8, 0xABE8, GraphemeCat::Extend),
+    (0xABE9, 0xABEA, GraphemeCat::SpacingMark),
+    (0xABEC, 0xABEC, GraphemeCat::SpacingMark),
+    (0xABED, 0xABED, GraphemeCat::Extend),
+    (0xAC00, 0xAC00, GraphemeCat::Lv),
+    (0xAC01, 0xAC1B, GraphemeCat::Lvt),
+    (0xAC1C, 0xAC1C, GraphemeCat::Lv),
+    (0xAC1D, 0xAC37, GraphemeCat::Lvt),
+    (0xAC38, 0xAC38, GraphemeCat::Lv),
+    (0xAC39, 0xAC53, GraphemeCat::Lvt),
+    (0xAC54, 0xAC54, GraphemeCat::Lv),
+    (0xAC55, 0xAC6F, GraphemeCat::Lvt),
+    (0xAC70, 0xAC70, GraphemeCat::Lv),
+    (0xAC71, 0xAC8B, GraphemeCat::Lvt),
+    (0xAC8C, 0xAC8C, GraphemeCat::Lv),
+    (0xAC8D, 0xACA7, GraphemeCat::Lvt),
+    (0xACA8, 0xACA8, GraphemeCat::Lv),
+    (0xACA9, 0xACC3, GraphemeCat::Lvt),
+    (0xACC4, 0xACC4, GraphemeCat::Lv),
+    (0xACC5, 0xACDF, GraphemeCat::Lvt),
+    (0xACE0, 0xACE0, GraphemeCat::Lv),
+    (0xACE1, 0xACFB, GraphemeCat::Lvt),
+    (0xACFC, 0xACFC, GraphemeCat::Lv),
+    (0xACFD, 0xAD17, GraphemeCat::Lvt),
+    (0xAD18, 0xAD18, GraphemeCat::Lv),
+    (0xAD19, 0xAD33, GraphemeCat::Lvt),
+    (0xAD34, 0xAD34, GraphemeCat::Lv),
+    (0xAD35, 0xAD4F, GraphemeCat::Lvt),
+    (0xAD50, 0xAD50, GraphemeCat::Lv),
+    (0xAD51, 0xAD6B, GraphemeCat::Lvt),
+    (0xAD6C, 0xAD6C, GraphemeCat::Lv),
+    (0xAD6D, 0xAD87, GraphemeCat::Lvt),
+    (0xAD88, 0xAD88, GraphemeCat::Lv),
+    (0xAD89, 0xADA3, GraphemeCat::Lvt),
+    (0xADA4, 0xADA4, GraphemeCat::Lv),
+    (0xADA5, 0xADBF, GraphemeCat::Lvt),
+    (0xADC0, 0xADC0, GraphemeCat::Lv),
+    (0xADC1, 0xADDB, GraphemeCat::Lvt),
+    (0xADDC, 0xADDC, GraphemeCat::Lv),
+    (0xADDD, 0xADF7, GraphemeCat::Lvt),
+    (0xADF8, 0xADF8, GraphemeCat::Lv),
+    (0xADF9, 0xAE13, GraphemeCat::Lvt),
+    (0xAE14, 0xAE14, GraphemeCat::Lv),
+    (0xAE15, 0xAE2F, GraphemeCat::Lvt),
+    (0xAE30, 0xAE30, GraphemeCat::Lv),
+    (0xAE31, 0xAE4B, GraphemeCat::Lvt),
+    (0xAE4C, 0xAE4C, GraphemeCat::Lv),
+    (0xAE4D, 0xAE67, GraphemeCat::Lvt),
+    (0xAE68, 0xAE68, GraphemeCat::Lv),
+    (0xAE69, 0xAE83, GraphemeCat::Lvt),
+    (0xAE84, 0xAE84, GraphemeCat::Lv),
+    (0xAE85, 0xAE9F, GraphemeCat::Lvt),
+    (0xAEA0, 0xAEA0, GraphemeCat::Lv),
+    (0xAEA1, 0xAEBB, GraphemeCat::Lvt),
+    (0xAEBC, 0xAEBC, GraphemeCat::Lv),
+    (0xAEBD, 0xAED7, GraphemeCat::Lvt),
+    (0xAED8, 0xAED8, GraphemeCat::Lv),
+    (0xAED9, 0xAEF3, GraphemeCat::Lvt),
+    (0xAEF4, 0xAEF4, GraphemeCat::Lv),
+    (0xAEF5, 0xAF0F, GraphemeCat::Lvt),
+    (0xAF10, 0xAF10, GraphemeCat::Lv),
+    (0xAF11, 0xAF2B, GraphemeCat::Lvt),
+    (0xAF2C, 0xAF2C, GraphemeCat::Lv),
+    (0xAF2D, 0xAF47, GraphemeCat::Lvt),
+    (0xAF48, 0xAF48, GraphemeCat::Lv),
+    (0xAF49, 0xAF63, GraphemeCat::Lvt),
+    (0xAF64, 0xAF64, GraphemeCat::Lv),
+    (0xAF65, 0xAF7F, GraphemeCat::Lvt),
+    (0xAF80, 0xAF80, GraphemeCat::Lv),
+    (0xAF81, 0xAF9B, GraphemeCat::Lvt),
+    (0xAF9C, 0xAF9C, GraphemeCat::Lv),
+    (0xAF9D, 0xAFB7, GraphemeCat::Lvt),
+    (0xAFB8, 0xAFB8, GraphemeCat::Lv),
+    (0xAFB9, 0xAFD3, GraphemeCat::Lvt),
+    (0xAFD4, 0xAFD4, GraphemeCat::Lv),
+    (0xAFD5, 0xAFEF, GraphemeCat::Lvt),
+    (0xAFF0, 0xAFF0, GraphemeCat::Lv),
+    (0xAFF1, 0xB00B, GraphemeCat::Lvt),
+    (0xB00C, 0xB00C, GraphemeCat::Lv),
+    (0xB00D, 0xB027, GraphemeCat::Lvt),
+    (0xB028, 0xB028, GraphemeCat::Lv),
+    (0xB029, 0xB043, GraphemeCat::Lvt),
+    (0xB044, 0xB044, GraphemeCat::Lv),
+    (0xB045, 0xB05F, GraphemeCat::Lvt),
+    (0xB060, 0xB060, GraphemeCat::Lv),
+    (0xB061, 0xB07B, GraphemeCat::Lvt),
+    (0xB07C, 0xB07C, GraphemeCat::Lv),
+    (0xB07D, 0xB097, GraphemeCat::Lvt),
+    (0xB098, 0xB098, GraphemeCat::Lv),
+    (0xB099, 0xB0B3, GraphemeCat::Lvt),
+    (0xB0B4, 0xB0B4, GraphemeCat::Lv),
+    (0xB0B5, 0xB0CF, GraphemeCat::Lvt),
+    (0xB0D0, 0xB0D0, GraphemeCat::Lv),
+    (0xB0D1, 0xB0EB, GraphemeCat::Lvt),
+    (0xB0EC, 0xB0EC, GraphemeCat::Lv),
+    (0xB0ED, 0xB107, GraphemeCat::Lvt),
+    (0xB108, 0xB108, GraphemeCat::Lv),
+    (0xB109, 0xB123, GraphemeCat::Lvt),
+    (0xB124, 0xB124, GraphemeCat::Lv),
+    (0xB125, 0xB13F, GraphemeCat::Lvt),
+    (0xB140, 0xB140, GraphemeCat::Lv),
+    (0xB141, 0xB15B, GraphemeCat::Lvt),
+    (0xB15C, 0xB15C, GraphemeCat::Lv),
+    (0xB15D, 0xB177, GraphemeCat::Lvt),
+    (0xB178, 0xB178, GraphemeCat::Lv),
+    (0xB179, 0xB193, GraphemeCat::Lvt),
+    (0xB194, 0xB194, GraphemeCat::Lv),
+    (0xB195, 0xB1AF, GraphemeCat::Lvt),
+    (0xB1B0, 0xB1B0, GraphemeCat::Lv),
+    (0xB1B1, 0xB1CB, GraphemeCat::Lvt),
+    (0xB1CC, 0xB1CC, GraphemeCat::Lv),
+    (0xB1CD, 0xB1E7, GraphemeCat::Lvt),
+    (0xB1E8, 0xB1E8, GraphemeCat::Lv),
+    (0xB1E9, 0xB203, GraphemeCat::Lvt),
+    (0xB204, 0xB204, GraphemeCat::Lv),
+    (0xB205, 0xB21F, GraphemeCat::Lvt),
+    (0xB220, 0xB220, GraphemeCat::Lv),
+    (0xB221, 0xB23B, GraphemeCat::Lvt),
+    (0xB23C, 0xB23C, GraphemeCat::Lv),
+    (0xB23D, 0xB257, GraphemeCat::Lvt),
+    (0xB258, 0xB258, GraphemeCat::Lv),
+    (0xB259, 0xB273, GraphemeCat::Lvt),
+    (0xB274, 0xB274, GraphemeCat::Lv),
+    (0xB275, 0xB28F, GraphemeCat::Lvt),
+    (0xB290, 0xB290, GraphemeCat::Lv),
+    (0xB291, 0xB2AB, GraphemeCat::Lvt),
+    (0xB2AC, 0xB2AC, GraphemeCat::Lv),
+    (0xB2AD, 0xB2C7, GraphemeCat::Lvt),
+    (0xB2C8, 0xB2C8, GraphemeCat::Lv),
+    (0xB2C9, 0xB2E3, GraphemeCat::Lvt),
+    (0xB2E4, 0xB2E4, GraphemeCat::Lv),
+    (0xB2E5, 0xB2FF, GraphemeCat::Lvt),
+    (0xB300, 0xB300, GraphemeCat::Lv),
+    (0xB301, 0xB31B, GraphemeCat::Lvt),
+    (0xB31C, 0xB31C, GraphemeCat::Lv),
+    (0xB31D, 0xB337, GraphemeCat::Lvt),
+    (0xB338, 0xB338, GraphemeCat::Lv),
+    (0xB339, 0xB353, GraphemeCat::Lvt),
+    (0xB354, 0xB354, GraphemeCat::Lv),
+    (0xB355, 0xB36F, GraphemeCat::Lvt),
+    (0xB370, 0xB370, GraphemeCat::Lv),
+    (0xB371, 0xB38B, GraphemeCat::Lvt),
+    (0xB38C, 0xB38C, GraphemeCat::Lv),
+    (0xB38D, 0xB3A7, GraphemeCat::Lvt),
+    (0xB3A8, 0xB3A8, GraphemeCat::Lv),
+    (0xB3A9, 0xB3C3, GraphemeCat::Lvt),
+    (0xB3C4, 0xB3C4, GraphemeCat::Lv),
+    (0xB3C5, 0xB3DF, GraphemeCat::Lvt),
+    (0xB3E0, 0xB3E0, GraphemeCat::Lv),
+    (0xB3E1, 0xB3FB, GraphemeCat::Lvt),
+    (0xB3FC, 0xB3FC, GraphemeCat::Lv),
+    (0xB3FD, 0xB417, GraphemeCat::Lvt),
+    (0xB418, 0xB418, GraphemeCat::Lv),
+    (0xB419, 0xB433, GraphemeCat::Lvt),
+    (0xB434, 0xB434, GraphemeCat::Lv),
+    (0xB435, 0xB44F, GraphemeCat::Lvt),
+    (0xB450, 0xB450, GraphemeCat::Lv),
+    (0xB451, 0xB46B, GraphemeCat::Lvt),
+    (0xB46C, 0xB46C, GraphemeCat::Lv),
+    (0xB46D, 0xB487, GraphemeCat::Lvt),
+    (0xB488, 0xB488, GraphemeCat::Lv),
+    (0xB489, 0xB4A3, GraphemeCat::Lvt),
+    (0xB4A4, 0xB4A4, GraphemeCat::Lv),
+    (0xB4A5, 0xB4BF, GraphemeCat::Lvt),
+    (0xB4C0, 0xB4C0, GraphemeCat::Lv),
+    (0xB4C1, 0xB4DB, GraphemeCat::Lvt),
+    (0xB4DC, 0xB4DC, GraphemeCat::Lv),
+    (0xB4DD, 0xB4F7, GraphemeCat::Lvt),
+    (0xB4F8, 0xB4F8, GraphemeCat::Lv),
+    (0xB4F9, 0xB513, GraphemeCat::Lvt),
+    (0xB514, 0xB514, GraphemeCat::Lv),
+    (0xB515, 0xB52F, GraphemeCat::Lvt),
+    (0xB530, 0xB530, GraphemeCat::Lv),
+    (0xB531, 0xB54B, GraphemeCat::Lvt),
+    (0xB54C, 0xB54C, GraphemeCat::Lv),
+    (0xB54D, 0xB567, GraphemeCat::Lvt),
+    (0xB568, 0xB568, GraphemeCat::Lv),
+    (0xB569, 0xB583, GraphemeCat::Lvt),
+    (0xB584, 0xB584, GraphemeCat::Lv),
+    (0xB585, 0xB59F, GraphemeCat::Lvt),
+    (0xB5A0, 0xB5A0, GraphemeCat::Lv),
+    (0xB5A1, 0xB5BB, GraphemeCat::Lvt),
+    (0xB5BC, 0xB5BC, GraphemeCat::Lv),
+    (0xB5BD, 0xB5D7, GraphemeCat::Lvt),
+    (0xB5D8, 0xB5D8, GraphemeCat::Lv),
+    (0xB5D9, 0xB5F3, GraphemeCat::Lvt),
+    (0xB5F4, 0xB5F4, GraphemeCat::Lv),
+    (0xB5F5, 0xB60F, GraphemeCat::Lvt),
+    (0xB610, 0xB610, GraphemeCat::Lv),
+    (0xB611, 0xB62B, GraphemeCat::Lvt),
+    (0xB62C, 0xB62C, GraphemeCat::Lv),
+    (0xB62D, 0xB647, GraphemeCat::Lvt),
+    (0xB648, 0xB648, GraphemeCat::Lv),
+    (0xB649, 0xB663, GraphemeCat::Lvt),
+    (0xB664, 0xB664, GraphemeCat::Lv),
+    (0xB665, 0xB67F, GraphemeCat::Lvt),
+    (0xB680, 0xB680, GraphemeCat::Lv),
+    (0xB681, 0xB69B, GraphemeCat::Lvt),
+    (0xB69C, 0xB69C, GraphemeCat::Lv),
+    (0xB69D, 0xB6B7, GraphemeCat::Lvt),
+    (0xB6B8, 0xB6B8, GraphemeCat::Lv),
+    (0xB6B9, 0xB6D3, GraphemeCat::Lvt),
+    (0xB6D4, 0xB6D4, GraphemeCat::Lv),
+    (0xB6D5, 0xB6EF, GraphemeCat::Lvt),
+    (0xB6F0, 0xB6F0, GraphemeCat::Lv),
+    (0xB6F1, 0xB70B, GraphemeCat::Lvt),
+    (0xB70C, 0xB70C, GraphemeCat::Lv),
+    (0xB70D, 0xB727, GraphemeCat::Lvt),
+    (0xB728, 0xB728, GraphemeCat::Lv),
+    (0xB729, 0xB743, GraphemeCat::Lvt),
+    (0xB744, 0xB744, GraphemeCat::Lv),
+    (0xB745, 0xB75F, GraphemeCat::Lvt),
+    (0xB760, 0xB760, GraphemeCat::Lv),
+    (0xB761, 0xB77B, GraphemeCat::Lvt),
+    (0xB77C, 0xB77C, GraphemeCat::Lv),
+    (0xB77D, 0xB797, GraphemeCat::Lvt),
+    (0xB798, 0xB798, GraphemeCat::Lv),
+    (0xB799, 0xB7B3, GraphemeCat::Lvt),
+    (0xB7B4, 0xB7B4, GraphemeCat::Lv),
+    (0xB7B5, 0xB7CF, GraphemeCat::Lvt),
+    (0xB7D0, 0xB7D0, GraphemeCat::Lv),
+    (0xB7D1, 0xB7EB, GraphemeCat::Lvt),
+    (0xB7EC, 0xB7EC, GraphemeCat::Lv),
+    (0xB7ED, 0xB807, GraphemeCat::Lvt),
+    (0xB808, 0xB808, GraphemeCat::Lv),
+    (0xB809, 0xB823, GraphemeCat::Lvt),
+    (0xB824, 0xB824, GraphemeCat::Lv),
+    (0xB825, 0xB83F, GraphemeCat::Lvt),
+    (0xB840, 0xB840, GraphemeCat::Lv),
+    (0xB841, 0xB85B, GraphemeCat::Lvt),
+    (0xB85C, 0xB85C, GraphemeCat::Lv),
+    (0xB85D, 0xB877, GraphemeCat::Lvt),
+    (0xB878, 0xB878, GraphemeCat::Lv),
+    (0xB879, 0xB893, GraphemeCat::Lvt),
+    (0xB894, 0xB894, GraphemeCat::Lv),
+    (0xB895, 0xB8AF, GraphemeCat::Lvt),
+    (0xB8B0, 0xB8B0, GraphemeCat::Lv),
+    (0xB8B1, 0xB8CB, GraphemeCat::Lvt),
+    (0xB8CC, 0xB8CC, GraphemeCat::Lv),
+    (0xB8CD, 0xB8E7, GraphemeCat::Lvt),
+    (0xB8E8, 0xB8E8, GraphemeCat::Lv),
+    (0xB8E9, 0xB903, GraphemeCat::Lvt),
+    (0xB904, 0xB904, GraphemeCat::Lv),
+    (0xB905, 0xB91F, GraphemeCat::Lvt),
+    (0xB920, 0xB920, GraphemeCat::Lv),
+    (0xB921, 0xB93B, GraphemeCat::Lvt),
+    (0xB93C, 0xB93C, GraphemeCat::Lv),
+    (0xB93D, 0xB957, GraphemeCat::Lvt),
+    (0xB958, 0xB958, GraphemeCat::Lv),
+    (0xB959, 0xB973, GraphemeCat::Lvt),
+    (0xB974, 0xB974, GraphemeCat::Lv),
+    (0xB975, 0xB98F, GraphemeCat::Lvt),
+    (0xB990, 0xB990, GraphemeCat::Lv),
+    (0xB991, 0xB9AB, GraphemeCat::Lvt),
+    (0xB9AC, 0xB9AC, GraphemeCat::Lv),
+    (0xB9AD, 0xB9C7, GraphemeCat::Lvt),
+    (0xB9C8, 0xB9C8, GraphemeCat::Lv),
+    (0xB9C9, 0xB9E3, GraphemeCat::Lvt),
+    (0xB9E4, 0xB9E4, GraphemeCat::Lv),
+    (0xB9E5, 0xB9FF, GraphemeCat::Lvt),
+    (0xBA00, 0xBA00, GraphemeCat::Lv),
+    (0xBA01, 0xBA1B, GraphemeCat::Lvt),
+    (0xBA1C, 0xBA1C, GraphemeCat::Lv),
+    (0xBA1D, 0xBA37, GraphemeCat::Lvt),
+    (0xBA38, 0xBA38, GraphemeCat::Lv),
+    (0xBA39, 0xBA53, GraphemeCat::Lvt),
+    (0xBA54, 0xBA54, GraphemeCat::Lv),
+    (0xBA55, 0xBA6F, GraphemeCat::Lvt),
+    (0xBA70, 0xBA70, GraphemeCat::Lv),
+    (0xBA71, 0xBA8B, GraphemeCat::Lvt),
+    (0xBA8C, 0xBA8C, GraphemeCat::Lv),
+    (0xBA8D, 0xBAA7, GraphemeCat::Lvt),
+    (0xBAA8, 0xBAA8, GraphemeCat::Lv),
+    (0xBAA9, 0xBAC3, GraphemeCat::Lvt),
+    (0xBAC4, 0xBAC4, GraphemeCat::Lv),
+    (0xBAC5, 0xBADF, GraphemeCat::Lvt),
+    (0xBAE0, 0xBAE0, GraphemeCat::Lv),
+    (0xBAE1, 0xBAFB, GraphemeCat::Lvt),
+    (0xBAFC, 0xBAFC, GraphemeCat::Lv),
+    (0xBAFD, 0xBB17, GraphemeCat::Lvt),
+    (0xBB18, 0xBB18, GraphemeCat::Lv),
+    (0xBB19, 0xBB33, GraphemeCat::Lvt),
+    (0xBB34, 0xBB34, GraphemeCat::Lv),
+    (0xBB35, 0xBB4F, GraphemeCat::Lvt),
+    (0xBB50, 0xBB50, GraphemeCat::Lv),
+    (0xBB51, 0xBB6B, GraphemeCat::Lvt),
+    (0xBB6C, 0xBB6C, GraphemeCat::Lv),
+    (0xBB6D, 0xBB87, GraphemeCat::Lvt),
+    (0xBB88, 0xBB88, GraphemeCat::Lv),
+    (0xBB89, 0xBBA3, GraphemeCat::Lvt),
+    (0xBBA4, 0xBBA4, GraphemeCat::Lv),
+    (0xBBA5, 0xBBBF, GraphemeCat::Lvt),
+    (0xBBC0, 0xBBC0, GraphemeCat::Lv),
+    (0xBBC1, 0xBBDB, GraphemeCat::Lvt),
+    (0xBBDC, 0xBBDC, GraphemeCat::Lv),
+    (0xBBDD, 0xBBF7, GraphemeCat::Lvt),
+    (0xBBF8, 0xBBF8, GraphemeCat::Lv),
+    (0xBBF9, 0xBC13, GraphemeCat::Lvt),
+    (0xBC14, 0xBC14, GraphemeCat::Lv),
+    (0xBC15, 0xBC2F, GraphemeCat::Lvt),
+    (0xBC30, 0xBC30, GraphemeCat::Lv),
+    (0xBC31, 0xBC4B, GraphemeCat::Lvt),
+    (0xBC4C, 0xBC4C, GraphemeCat::Lv),
+    (0xBC4D, 0xBC67, GraphemeCat::Lvt),
+    (0xBC68, 0xBC68, GraphemeCat::Lv),
+    (0xBC69, 0xBC83, GraphemeCat::Lvt),
+    (0xBC84, 0xBC84, GraphemeCat::Lv),
+    (0xBC85, 0xBC9F, GraphemeCat::Lvt),
+    (0xBCA0, 0xBCA0, GraphemeCat::Lv),
+    (0xBCA1, 0xBCBB, GraphemeCat::Lvt),
+    (0xBCBC, 0xBCBC, GraphemeCat::Lv),
+    (0xBCBD, 0xBCD7, GraphemeCat::Lvt),
+    (0xBCD8, 0xBCD8, GraphemeCat::Lv),
+    (0xBCD9, 0xBCF3, GraphemeCat::Lvt),
+    (0xBCF4, 0xBCF4, GraphemeCat::Lv),
+    (0xBCF5, 0xBD0F, GraphemeCat::Lvt),
+    (0xBD10, 0xBD10, GraphemeCat::Lv),
+    (0xBD11, 0xBD2B, GraphemeCat::Lvt),
+    (0xBD2C, 0xBD2C, GraphemeCat::Lv),
+    (0xBD2D, 0xBD47, GraphemeCat::Lvt),
+    (0xBD48, 0xBD48, GraphemeCat::Lv),
+    (0xBD49, 0xBD63, GraphemeCat::Lvt),
+    (0xBD64, 0xBD64, GraphemeCat::Lv),
+    (0xBD65, 0xBD7F, GraphemeCat::Lvt),
+    (0xBD80, 0xBD80, GraphemeCat::Lv),
+    (0xBD81, 0xBD9B, GraphemeCat::Lvt),
+    (0xBD9C, 0xBD9C, GraphemeCat::Lv),
+    (0xBD9D, 0xBDB7, GraphemeCat::Lvt),
+    (0xBDB8, 0xBDB8, GraphemeCat::Lv),
+    (0xBDB9, 0xBDD3, GraphemeCat::Lvt),
+    (0xBDD4, 0xBDD4, GraphemeCat::Lv),
+    (0xBDD5, 0xBDEF, GraphemeCat::Lvt),
+    (0xBDF0, 0xBDF0, GraphemeCat::Lv),
+    (0xBDF1, 0xBE0B, GraphemeCat::Lvt),
+    (0xBE0C, 0xBE0C, GraphemeCat::Lv),
+    (0xBE0D, 0xBE27, GraphemeCat::Lvt),
+    (0xBE28, 0xBE28, GraphemeCat::Lv),
+    (0xBE29, 0xBE43, GraphemeCat::Lvt),
+    (0xBE44, 0xBE44, GraphemeCat::Lv),
+    (0xBE45, 0xBE5F, GraphemeCat::Lvt),
+    (0xBE60, 0xBE60, GraphemeCat::Lv),
+    (0xBE61, 0xBE7B, GraphemeCat::Lvt),
+    (0xBE7C, 0xBE7C, GraphemeCat::Lv),
+    (0xBE7D, 0xBE97, GraphemeCat::Lvt),
+    (0xBE98, 0xBE98, GraphemeCat::Lv),
+    (0xBE99, 0xBEB3, GraphemeCat::Lvt),
+    (0xBEB4, 0xBEB4, GraphemeCat::Lv),
+    (0xBEB5, 0xBECF, GraphemeCat::Lvt),
+    (0xBED0, 0xBED0, GraphemeCat::Lv),
+    (0xBED1, 0xBEEB, GraphemeCat::Lvt),
+    (0xBEEC, 0xBEEC, GraphemeCat::Lv),
+    (0xBEED, 0xBF07, GraphemeCat::Lvt),
+    (0xBF08, 0xBF08, GraphemeCat::Lv),
+    (0xBF09, 0xBF23, GraphemeCat::Lvt),
+    (0xBF24, 0xBF24, GraphemeCat::Lv),
+    (0xBF25, 0xBF3F, GraphemeCat::Lvt),
+    (0xBF40, 0xBF40, GraphemeCat::Lv),
+    (0xBF41, 0xBF5B, GraphemeCat::Lvt),
+    (0xBF5C, 0xBF5C, GraphemeCat::Lv),
+    (0xBF5D, 0xBF77, GraphemeCat::Lvt),
+    (0xBF78, 0xBF78, GraphemeCat::Lv),
+    (0xBF79, 0xBF93, GraphemeCat::Lvt),
+    (0xBF94, 0xBF94, GraphemeCat::Lv),
+    (0xBF95, 0xBFAF, GraphemeCat::Lvt),
+    (0xBFB0, 0xBFB0, GraphemeCat::Lv),
+    (0xBFB1, 0xBFCB, GraphemeCat::Lvt),
+    (0xBFCC, 0xBFCC, GraphemeCat::Lv),
+    (0xBFCD, 0xBFE7, GraphemeCat::Lvt),
+    (0xBFE8, 0xBFE8, GraphemeCat::Lv),
+    (0xBFE9, 0xC003, GraphemeCat::Lvt),
+    (0xC004, 0xC004, GraphemeCat::Lv),
+    (0xC005, 0xC01F, GraphemeCat::Lvt),
+    (0xC020, 0xC020, GraphemeCat::Lv),
+    (0xC021, 0xC03B, GraphemeCat::Lvt),
+    (0xC03C, 0xC03C, GraphemeCat::Lv),
+    (0xC03D, 0xC057, GraphemeCat::Lvt),
+    (0xC058, 0xC058, GraphemeCat::Lv),
+    (0xC059, 0xC073, GraphemeCat::Lvt),
+    (0xC074, 0xC074, GraphemeCat::Lv),
+    (0xC075, 0xC08F, GraphemeCat::Lvt),
+    (0xC090, 0xC090, GraphemeCat::Lv),
+    (0xC091, 0xC0AB, GraphemeCat::Lvt),
+    (0xC0AC, 0xC0AC, GraphemeCat::Lv),
+    (0xC0AD, 0xC0C7, GraphemeCat::Lvt),
+    (0xC0C8, 0xC0C8, GraphemeCat::Lv),
+    (0xC0C9, 0xC0E3, GraphemeCat::Lvt),
+    (0xC0E4, 0xC0E4, GraphemeCat::Lv),
+    (0xC0E5, 0xC0FF, GraphemeCat::Lvt),
+    (0xC100, 0xC100, GraphemeCat::Lv),
+    (0xC101, 0xC11B, GraphemeCat::Lvt),
+    (0xC11C, 0xC11C, GraphemeCat::Lv),
+    (0xC11D, 0xC137, GraphemeCat::Lvt),
+    (0xC138, 0xC138, GraphemeCat::Lv),
+    (0xC139, 0xC153, GraphemeCat::Lvt),
+    (0xC154, 0xC154, GraphemeCat::Lv),
+    (0xC155, 0xC16F, GraphemeCat::Lvt),
+    (0xC170, 0xC170, GraphemeCat::Lv),
+    (0xC171, 0xC18B, GraphemeCat::Lvt),
+    (0xC18C, 0xC18C, GraphemeCat::Lv),
+    (0xC18D, 0xC1A7, GraphemeCat::Lvt),
+    (0xC1A8, 0xC1A8, GraphemeCat::Lv),
+    (0xC1A9, 0xC1C3, GraphemeCat::Lvt),
+    (0xC1C4, 0xC1C4, GraphemeCat::Lv),
+    (0xC1C5, 0xC1DF, GraphemeCat::Lvt),
+    (0xC1E0, 0xC1E0, GraphemeCat::Lv),
+    (0xC1E1, 0xC1FB, GraphemeCat::Lvt),
+    (0xC1FC, 0xC1FC, GraphemeCat::Lv),
+    (0xC1FD, 0xC217, GraphemeCat::Lvt),
+    (0xC218, 0xC218, GraphemeCat::Lv),
+    (0xC219, 0xC233, GraphemeCat::Lvt),
+    (0xC234, 0xC234, GraphemeCat::Lv),
+    (0xC235, 0xC24F, GraphemeCat::Lvt),
+    (0xC250, 0xC250, GraphemeCat::Lv),
+    (0xC251, 0xC26B, GraphemeCat::Lvt),
+    (0xC26C, 0xC26C, GraphemeCat::Lv),
+    (0xC26D, 0xC287, GraphemeCat::Lvt),
+    (0xC288, 0xC288, GraphemeCat::Lv),
+    (0xC289, 0xC2A3, GraphemeCat::Lvt),
+    (0xC2A4, 0xC2A4, GraphemeCat::Lv),
+    (0xC2A5, 0xC2BF, GraphemeCat::Lvt),
+    (0xC2C0, 0xC2C0, GraphemeCat::Lv),
+    (0xC2C1, 0xC2DB, GraphemeCat::Lvt),
+    (0xC2DC, 0xC2DC, GraphemeCat::Lv),
+    (0xC2DD, 0xC2F7, GraphemeCat::Lvt),
+    (0xC2F8, 0xC2F8, GraphemeCat::Lv),
+    (0xC2F9, 0xC313, GraphemeCat::Lvt),
+    (0xC314, 0xC314, GraphemeCat::Lv),
+    (0xC315, 0xC32F, GraphemeCat::Lvt),
+    (0xC330, 0xC330, GraphemeCat::Lv),
+    (0xC331, 0xC34B, GraphemeCat::Lvt),
+    (0xC34C, 0xC34C, GraphemeCat::Lv),
+    (0xC34D, 0xC367, GraphemeCat::Lvt),
+    (0xC368, 0xC368, GraphemeCat::Lv),
+    (0xC369, 0xC383, GraphemeCat::Lvt),
+    (0xC384, 0xC384, GraphemeCat::Lv),
+    (0xC385, 0xC39F, GraphemeCat::Lvt),
+    (0xC3A0, 0xC3A0, GraphemeCat::Lv),
+    (0xC3A1, 0xC3BB, GraphemeCat::Lvt),
+    (0xC3BC, 0xC3BC, GraphemeCat::Lv),
+    (0xC3BD, 0xC3D7, GraphemeCat::Lvt),
+    (0xC3D8, 0xC3D8, GraphemeCat::Lv),
+    (0xC3D9, 0xC3F3, GraphemeCat::Lvt),
+    (0xC3F4, 0xC3F4, GraphemeCat::Lv),
+    (0xC3F5, 0xC40F, GraphemeCat::Lvt),
+    (0xC410, 0xC410, GraphemeCat::Lv),
+    (0xC411, 0xC42B, GraphemeCat::Lvt),
+    (0xC42C, 0xC42C, GraphemeCat::Lv),
+    (0xC42D, 0xC447, GraphemeCat::Lvt),
+    (0xC448, 0xC448, GraphemeCat::Lv),
+    (0xC449, 0xC463, GraphemeCat::Lvt),
+    (0xC464, 0xC464, GraphemeCat::Lv),
+    (0xC465, 0xC47F, GraphemeCat::Lvt),
+    (0xC480, 0xC480, GraphemeCat::Lv),
+    (0xC481, 0xC49B, GraphemeCat::Lvt),
+    (0xC49C, 0xC49C, GraphemeCat::Lv),
+    (0xC49D, 0xC4B7, GraphemeCat::Lvt),
+    (0xC4B8, 0xC4B8, GraphemeCat::Lv),
+    (0xC4B9, 0xC4D3, GraphemeCat::Lvt),
+    (0xC4D4, 0xC4D4, GraphemeCat::Lv),
+    (0xC4D5, 0xC4EF, GraphemeCat::Lvt),
+    (0xC4F0, 0xC4F0, GraphemeCat::Lv),
+    (0xC4F1, 0xC50B, GraphemeCat::Lvt),
+    (0xC50C, 0xC50C, GraphemeCat::Lv),
+    (0xC50D, 0xC527, GraphemeCat::Lvt),
+    (0xC528, 0xC528, GraphemeCat::Lv),
+    (0xC529, 0xC543, GraphemeCat::Lvt),
+    (0xC544, 0xC544, GraphemeCat::Lv),
+    (0xC545, 0xC55F, GraphemeCat::Lvt),
+    (0xC560, 0xC560, GraphemeCat::Lv),
+    (0xC561, 0xC57B, GraphemeCat::Lvt),
+    (0xC57C, 0xC57C, GraphemeCat::Lv),
+    (0xC57D, 0xC597, GraphemeCat::Lvt),
+    (0xC598, 0xC598, GraphemeCat::Lv),
+    (0xC599, 0xC5B3, GraphemeCat::Lvt),
+    (0xC5B4, 0xC5B4, GraphemeCat::Lv),
+    (0xC5B5, 0xC5CF, GraphemeCat::Lvt),
+    (0xC5D0, 0xC5D0, GraphemeCat::Lv),
+    (0xC5D1, 0xC5EB, GraphemeCat::Lvt),
+    (0xC5EC, 0xC5EC, GraphemeCat::Lv),
+    (0xC5ED, 0xC607, GraphemeCat::Lvt),
+    (0xC608, 0xC608, GraphemeCat::Lv),
+    (0xC609, 0xC623, GraphemeCat::Lvt),
+    (0xC624, 0xC624, GraphemeCat::Lv),
+    (0xC625, 0xC63F, GraphemeCat::Lvt),
+    (0xC640, 0xC640, GraphemeCat::Lv),
+    (0xC641, 0xC65B, GraphemeCat::Lvt),
+    (0xC65C, 0xC65C, GraphemeCat::Lv),
+    (0xC65D, 0xC677, GraphemeCat::Lvt),
+    (0xC678, 0xC678, GraphemeCat::Lv),
+    (0xC679, 0xC693, GraphemeCat::Lvt),
+    (0xC694, 0xC694, GraphemeCat::Lv),
+    (0xC695, 0xC6AF, GraphemeCat::Lvt),
+    (0xC6B0, 0xC6B0, GraphemeCat::Lv),
+    (0xC6B1, 0xC6CB, GraphemeCat::Lvt),
+    (0xC6CC, 0xC6CC, GraphemeCat::Lv),
+    (0xC6CD, 0xC6E7, GraphemeCat::Lvt),
+    (0xC6E8, 0xC6E8, GraphemeCat::Lv),
+    (0xC6E9, 0xC703, GraphemeCat::Lvt),
+    (0xC704, 0xC704, GraphemeCat::Lv),
+    (0xC705, 0xC71F, GraphemeCat::Lvt),
+    (0xC720, 0xC720, GraphemeCat::Lv),
+    (0xC721, 0xC73B, GraphemeCat::Lvt),
+    (0xC73C, 0xC73C, GraphemeCat::Lv),
+    (0xC73D, 0xC757, GraphemeCat::Lvt),
+    (0xC758, 0xC758, GraphemeCat::Lv),
+    (0xC759, 0xC773, GraphemeCat::Lvt),
+    (0xC774, 0xC774, GraphemeCat::Lv),
+    (0xC775, 0xC78F, GraphemeCat::Lvt),
+    (0xC790, 0xC790, GraphemeCat::Lv),
+    (0xC791, 0xC7AB, GraphemeCat::Lvt),
+    (0xC7AC, 0xC7AC, GraphemeCat::Lv),
+    (0xC7AD, 0xC7C7, GraphemeCat::Lvt),
+    (0xC7C8, 0xC7C8, GraphemeCat::Lv),
+    (0xC7C9, 0xC7E3, GraphemeCat::Lvt),
+    (0xC7E4, 0xC7E4, GraphemeCat::Lv),
+    (0xC7E5, 0xC7FF, GraphemeCat::Lvt),
+    (0xC800, 0xC800, GraphemeCat::Lv),
+    (0xC801, 0xC81B, GraphemeCat::Lvt),
+    (0xC81C, 0xC81C, GraphemeCat::Lv),
+    (0xC81D, 0xC837, GraphemeCat::Lvt),
+    (0xC838, 0xC838, GraphemeCat::Lv),
+    (0xC839, 0xC853, GraphemeCat::Lvt),
+    (0xC854, 0xC854, GraphemeCat::Lv),
+    (0xC855, 0xC86F, GraphemeCat::Lvt),
+    (0xC870, 0xC870, GraphemeCat::Lv),
+    (0xC871, 0xC88B, GraphemeCat::Lvt),
+    (0xC88C, 0xC88C, GraphemeCat::Lv),
+    (0xC88D, 0xC8A7, GraphemeCat::Lvt),
+    (0xC8A8, 0xC8A8, GraphemeCat::Lv),
+    (0xC8A9, 0xC8C3, GraphemeCat::Lvt),
+    (0xC8C4, 0xC8C4, GraphemeCat::Lv),
+    (0xC8C5, 0xC8DF, GraphemeCat::Lvt),
+    (0xC8E0, 0xC8E0, GraphemeCat::Lv),
+    (0xC8E1, 0xC8FB, GraphemeCat::Lvt),
+    (0xC8FC, 0xC8FC, GraphemeCat::Lv),
+    (0xC8FD, 0xC917, GraphemeCat::Lvt),
+    (0xC918, 0xC918, GraphemeCat::Lv),
+    (0xC919, 0xC933, GraphemeCat::Lvt),
+    (0xC934, 0xC934, GraphemeCat::Lv),
+    (0xC935, 0xC94F, GraphemeCat::Lvt),
+    (0xC950, 0xC950, GraphemeCat::Lv),
+    (0xC951, 0xC96B, GraphemeCat::Lvt),
+    (0xC96C, 0xC96C, GraphemeCat::Lv),
+    (0xC96D, 0xC987, GraphemeCat::Lvt),
+    (0xC988, 0xC988, GraphemeCat::Lv),
+    (0xC989, 0xC9A3, GraphemeCat::Lvt),
+    (0xC9A4, 0xC9A4, GraphemeCat::Lv),
+    (0xC9A5, 0xC9BF, GraphemeCat::Lvt),
+    (0xC9C0, 0xC9C0, GraphemeCat::Lv),
+    (0xC9C1, 0xC9DB, GraphemeCat::Lvt),
+    (0xC9DC, 0xC9DC, GraphemeCat::Lv),
+    (0xC9DD, 0xC9F7, GraphemeCat::Lvt),
+    (0xC9F8, 0xC9F8, GraphemeCat::Lv),
+    (0xC9F9, 0xCA13, GraphemeCat::Lvt),
+    (0xCA14, 0xCA14, GraphemeCat::Lv),
+    (0xCA15, 0xCA2F, GraphemeCat::Lvt),
+    (0xCA30, 0xCA30, GraphemeCat::Lv),
+    (0xCA31, 0xCA4B, GraphemeCat::Lvt),
+    (0xCA4C, 0xCA4C, GraphemeCat::Lv),
+    (0xCA4D, 0xCA67, GraphemeCat::Lvt),
+    (0xCA68, 0xCA68, GraphemeCat::Lv),
+    (0xCA69, 0xCA83, GraphemeCat::Lvt),
+    (0xCA84, 0xCA84, GraphemeCat::Lv),
+    (0xCA85, 0xCA9F, GraphemeCat::Lvt),
+    (0xCAA0, 0xCAA0, GraphemeCat::Lv),
+    (0xCAA1, 0xCABB, GraphemeCat::Lvt),
+    (0xCABC, 0xCABC, GraphemeCat::Lv),
+    (0xCABD, 0xCAD7, GraphemeCat::Lvt),
+    (0xCAD8, 0xCAD8, GraphemeCat::Lv),
+    (0xCAD9, 0xCAF3, GraphemeCat::Lvt),
+    (0xCAF4, 0xCAF4, GraphemeCat::Lv),
+    (0xCAF5, 0xCB0F, GraphemeCat::Lvt),
+    (0xCB10, 0xCB10, GraphemeCat::Lv),
+    (0xCB11, 0xCB2B, GraphemeCat::Lvt),
+    (0xCB2C, 0xCB2C, GraphemeCat::Lv),
+    (0xCB2D, 0xCB47, GraphemeCat::Lvt),
+    (0xCB48, 0xCB48, GraphemeCat::Lv),
+    (0xCB49, 0xCB63, GraphemeCat::Lvt),
+    (0xCB64, 0xCB64, GraphemeCat::Lv),
+    (0xCB65, 0xCB7F, GraphemeCat::Lvt),
+    (0xCB80, 0xCB80, GraphemeCat::Lv),
+    (0xCB81, 0xCB9B, GraphemeCat::Lvt),
+    (0xCB9C, 0xCB9C, GraphemeCat::Lv),
+    (0xCB9D, 0xCBB7, GraphemeCat::Lvt),
+    (0xCBB8, 0xCBB8, GraphemeCat::Lv),
+    (0xCBB9, 0xCBD3, GraphemeCat::Lvt),
+    (0xCBD4, 0xCBD4, GraphemeCat::Lv),
+    (0xCBD5, 0xCBEF, GraphemeCat::Lvt),
+    (0xCBF0, 0xCBF0, GraphemeCat::Lv),
+    (0xCBF1, 0xCC0B, GraphemeCat::Lvt),
+    (0xCC0C, 0xCC0C, GraphemeCat::Lv),
+    (0xCC0D, 0xCC27, GraphemeCat::Lvt),
+    (0xCC28, 0xCC28, GraphemeCat::Lv),
+    (0xCC29, 0xCC43, GraphemeCat::Lvt),
+    (0xCC44, 0xCC44, GraphemeCat::Lv),
+    (0xCC45, 0xCC5F, GraphemeCat::Lvt),
+    (0xCC60, 0xCC60, GraphemeCat::Lv),
+    (0xCC61, 0xCC7B, GraphemeCat::Lvt),
+    (0xCC7C, 0xCC7C, GraphemeCat::Lv),
+    (0xCC7D, 0xCC97, GraphemeCat::Lvt),
+    (0xCC98, 0xCC98, GraphemeCat::Lv),
+    (0xCC99, 0xCCB3, GraphemeCat::Lvt),
+    (0xCCB4, 0xCCB4, GraphemeCat::Lv),
+    (0xCCB5, 0xCCCF, GraphemeCat::Lvt),
+    (0xCCD0, 0xCCD0, GraphemeCat::Lv),
+    (0xCCD1, 0xCCEB, GraphemeCat::Lvt),
+    (0xCCEC, 0xCCEC, GraphemeCat::Lv),
+    (0xCCED, 0xCD07, GraphemeCat::Lvt),
+    (0xCD08, 0xCD08, GraphemeCat::Lv),
+    (0xCD09, 0xCD23, GraphemeCat::Lvt),
+    (0xCD24, 0xCD24, GraphemeCat::Lv),
+    (0xCD25, 0xCD3F, GraphemeCat::Lvt),
+    (0xCD40, 0xCD40, GraphemeCat::Lv),
+    (0xCD41, 0xCD5B, GraphemeCat::Lvt),
+    (0xCD5C, 0xCD5C, GraphemeCat::Lv),
+    (0xCD5D, 0xCD77, GraphemeCat::Lvt),
+    (0xCD78, 0xCD78, GraphemeCat::Lv),
+    (0xCD79, 0xCD93, GraphemeCat::Lvt),
+    (0xCD94, 0xCD94, GraphemeCat::Lv),
+    (0xCD95, 0xCDAF, GraphemeCat::Lvt),
+    (0xCDB0, 0xCDB0, GraphemeCat::Lv),
+    (0xCDB1, 0xCDCB, GraphemeCat::Lvt),
+    (0xCDCC, 0xCDCC, GraphemeCat::Lv),
+    (0xCDCD, 0xCDE7, GraphemeCat::Lvt),
+    (0xCDE8, 0xCDE8, GraphemeCat::Lv),
+    (0xCDE9, 0xCE03, GraphemeCat::Lvt),
+    (0xCE04, 0xCE04, GraphemeCat::Lv),
+    (0xCE05, 0xCE1F, GraphemeCat::Lvt),
+    (0xCE20, 0xCE20, GraphemeCat::Lv),
+    (0xCE21, 0xCE3B, GraphemeCat::Lvt),
+    (0xCE3C, 0xCE3C, GraphemeCat::Lv),
+    (0xCE3D, 0xCE57, GraphemeCat::Lvt),
+    (0xCE58, 0xCE58, GraphemeCat::Lv),
+    (0xCE59, 0xCE73, GraphemeCat::Lvt),
+    (0xCE74, 0xCE74, GraphemeCat::Lv),
+    (0xCE75, 0xCE8F, GraphemeCat::Lvt),
+    (0xCE90, 0xCE90, GraphemeCat::Lv),
+    (0xCE91, 0xCEAB, GraphemeCat::Lvt),
+    (0xCEAC, 0xCEAC, GraphemeCat::Lv),
+    (0xCEAD, 0xCEC7, GraphemeCat::Lvt),
+    (0xCEC8, 0xCEC8, GraphemeCat::Lv),
+    (0xCEC9, 0xCEE3, GraphemeCat::Lvt),
+    (0xCEE4, 0xCEE4, GraphemeCat::Lv),
+    (0xCEE5, 0xCEFF, GraphemeCat::Lvt),
+    (0xCF00, 0xCF00, GraphemeCat::Lv),
+    (0xCF01, 0xCF1B, GraphemeCat::Lvt),
+    (0xCF1C, 0xCF1C, GraphemeCat::Lv),
+    (0xCF1D, 0xCF37, GraphemeCat::Lvt),
+    (0xCF38, 0xCF38, GraphemeCat::Lv),
+    (0xCF39, 0xCF53, GraphemeCat::Lvt),
+    (0xCF54, 0xCF54, GraphemeCat::Lv),
+    (0xCF55, 0xCF6F, GraphemeCat::Lvt),
+    (0xCF70, 0xCF70, GraphemeCat::Lv),
+    (0xCF71, 0xCF8B, GraphemeCat::Lvt),
+    (0xCF8C, 0xCF8C, GraphemeCat::Lv),
+    (0xCF8D, 0xCFA7, GraphemeCat::Lvt),
+    (0xCFA8, 0xCFA8, GraphemeCat::Lv),
+    (0xCFA9, 0xCFC3, GraphemeCat::Lvt),
+    (0xCFC4, 0xCFC4, GraphemeCat::Lv),
+    (0xCFC5, 0xCFDF, GraphemeCat::Lvt),
+    (0xCFE0, 0xCFE0, GraphemeCat::Lv),
+    (0xCFE1, 0xCFFB, GraphemeCat::Lvt),
+    (0xCFFC, 0xCFFC, GraphemeCat::Lv),
+    (0xCFFD, 0xD017, GraphemeCat::Lvt),
+    (0xD018, 0xD018, GraphemeCat::Lv),
+    (0xD019, 0xD033, GraphemeCat::Lvt),
+    (0xD034, 0xD034, GraphemeCat::Lv),
+    (0xD035, 0xD04F, GraphemeCat::Lvt),
+    (0xD050, 0xD050, GraphemeCat::Lv),
+    (0xD051, 0xD06B, GraphemeCat::Lvt),
+    (0xD06C, 0xD06C, GraphemeCat::Lv),
+    (0xD06D, 0xD087, GraphemeCat::Lvt),
+    (0xD088, 0xD088, GraphemeCat::Lv),
+    (0xD089, 0xD0A3, GraphemeCat::Lvt),
+    (0xD0A4, 0xD0A4, GraphemeCat::Lv),
+    (0xD0A5, 0xD0BF, GraphemeCat::Lvt),
+    (0xD0C0, 0xD0C0, GraphemeCat::Lv),
+    (0xD0C1, 0xD0DB, GraphemeCat::Lvt),
+    (0xD0DC, 0xD0DC, GraphemeCat::Lv),
+    (0xD0DD, 0xD0F7, GraphemeCat::Lvt),
+    (0xD0F8, 0xD0F8, GraphemeCat::Lv),
+    (0xD0F9, 0xD113, GraphemeCat::Lvt),
+    (0xD114, 0xD114, GraphemeCat::Lv),
+    (0xD115, 0xD12F, GraphemeCat::Lvt),
+    (0xD130, 0xD130, GraphemeCat::Lv),
+    (0xD131, 0xD14B, GraphemeCat::Lvt),
+    (0xD14C, 0xD14C, GraphemeCat::Lv),
+    (0xD14D, 0xD167, GraphemeCat::Lvt),
+    (0xD168, 0xD168, GraphemeCat::Lv),
+    (0xD169, 0xD183, GraphemeCat::Lvt),
+    (0xD184, 0xD184, GraphemeCat::Lv),
+    (0xD185, 0xD19F, GraphemeCat::Lvt),
+    (0xD1A0, 0xD1A0, GraphemeCat::Lv),
+    (0xD1A1, 0xD1BB, GraphemeCat::Lvt),
+    (0xD1BC, 0xD1BC, GraphemeCat::Lv),
+    (0xD1BD, 0xD1D7, GraphemeCat::Lvt),
+    (0xD1D8, 0xD1D8, GraphemeCat::Lv),
+    (0xD1D9, 0xD1F3, GraphemeCat::Lvt),
+    (0xD1F4, 0xD1F4, GraphemeCat::Lv),
+    (0xD1F5, 0xD20F, GraphemeCat::Lvt),
+    (0xD210, 0xD210, GraphemeCat::Lv),
+    (0xD211, 0xD22B, GraphemeCat::Lvt),
+    (0xD22C, 0xD22C, GraphemeCat::Lv),
+    (0xD22D, 0xD247, GraphemeCat::Lvt),
+    (0xD248, 0xD248, GraphemeCat::Lv),
+    (0xD249, 0xD263, GraphemeCat::Lvt),
+    (0xD264, 0xD264, GraphemeCat::Lv),
+    (0xD265, 0xD27F, GraphemeCat::Lvt),
+    (0xD280, 0xD280, GraphemeCat::Lv),
+    (0xD281, 0xD29B, GraphemeCat::Lvt),
+    (0xD29C, 0xD29C, GraphemeCat::Lv),
+    (0xD29D, 0xD2B7, GraphemeCat::Lvt),
+    (0xD2B8, 0xD2B8, GraphemeCat::Lv),
+    (0xD2B9, 0xD2D3, GraphemeCat::Lvt),
+    (0xD2D4, 0xD2D4, GraphemeCat::Lv),
+    (0xD2D5, 0xD2EF, GraphemeCat::Lvt),
+    (0xD2F0, 0xD2F0, GraphemeCat::Lv),
+    (0xD2F1, 0xD30B, GraphemeCat::Lvt),
+    (0xD30C, 0xD30C, GraphemeCat::Lv),
+    (0xD30D, 0xD327, GraphemeCat::Lvt),
+    (0xD328, 0xD328, GraphemeCat::Lv),
+    (0xD329, 0xD343, GraphemeCat::Lvt),
+    (0xD344, 0xD344, GraphemeCat::Lv),
+    (0xD345, 0xD35F, GraphemeCat::Lvt),
+    (0xD360, 0xD360, GraphemeCat::Lv),
+    (0xD361, 0xD37B, GraphemeCat::Lvt),
+    (0xD37C, 0xD37C, GraphemeCat::Lv),
+    (0xD37D, 0xD397, GraphemeCat::Lvt),
+    (0xD398, 0xD398, GraphemeCat::Lv),
+    (0xD399, 0xD3B3, GraphemeCat::Lvt),
+    (0xD3B4, 0xD3B4, GraphemeCat::Lv),
+    (0xD3B5, 0xD3CF, GraphemeCat::Lvt),
+    (0xD3D0, 0xD3D0, GraphemeCat::Lv),
+    (0xD3D1, 0xD3EB, GraphemeCat::Lvt),
+    (0xD3EC, 0xD3EC, GraphemeCat::Lv),
+    (0xD3ED, 0xD407, GraphemeCat::Lvt),
+    (0xD408, 0xD408, GraphemeCat::Lv),
+    (0xD409, 0xD423, GraphemeCat::Lvt),
+    (0xD424, 0xD424, GraphemeCat::Lv),
+    (0xD425, 0xD43F, GraphemeCat::Lvt),
+    (0xD440, 0xD440, GraphemeCat::Lv),
+    (0xD441, 0xD45B, GraphemeCat::Lvt),
+    (0xD45C, 0xD45C, GraphemeCat::Lv),
+    (0xD45D, 0xD477, GraphemeCat::Lvt),
+    (0xD478, 0xD478, GraphemeCat::Lv),
+    (0xD479, 0xD493, GraphemeCat::Lvt),
+    (0xD494, 0xD494, GraphemeCat::Lv),
+    (0xD495, 0xD4AF, GraphemeCat::Lvt),
+    (0xD4B0, 0xD4B0, GraphemeCat::Lv),
+    (0xD4B1, 0xD4CB, GraphemeCat::Lvt),
+    (0xD4CC, 0xD4CC, GraphemeCat::Lv),
+    (0xD4CD, 0xD4E7, GraphemeCat::Lvt),
+    (0xD4E8, 0xD4E8, GraphemeCat::Lv),
+    (0xD4E9, 0xD503, GraphemeCat::Lvt),
+    (0xD504, 0xD504, GraphemeCat::Lv),
+    (0xD505, 0xD51F, GraphemeCat::Lvt),
+    (0xD520, 0xD520, GraphemeCat::Lv),
+    (0xD521, 0xD53B, GraphemeCat::Lvt),
+    (0xD53C, 0xD53C, GraphemeCat::Lv),
+    (0xD53D, 0xD557, GraphemeCat::Lvt),
+    (0xD558, 0xD558, GraphemeCat::Lv),
+    (0xD559, 0xD573, GraphemeCat::Lvt),
+    (0xD574, 0xD574, GraphemeCat::Lv),
+    (0xD575, 0xD58F, GraphemeCat::Lvt),
+    (0xD590, 0xD590, GraphemeCat::Lv),
+    (0xD591, 0xD5AB, GraphemeCat::Lvt),
+    (0xD5AC, 0xD5AC, GraphemeCat::Lv),
+    (0xD5AD, 0xD5C7, GraphemeCat::Lvt),
+    (0xD5C8, 0xD5C8, GraphemeCat::Lv),
+    (0xD5C9, 0xD5E3, GraphemeCat::Lvt),
+    (0xD5E4, 0xD5E4, GraphemeCat::Lv),
+    (0xD5E5, 0xD5FF, GraphemeCat::Lvt),
+    (0xD600, 0xD600, GraphemeCat::Lv),
+    (0xD601, 0xD61B, GraphemeCat::Lvt),
+    (0xD61C, 0xD61C, GraphemeCat::Lv),
+    (0xD61D, 0xD637, GraphemeCat::Lvt),
+    (0xD638, 0xD638, GraphemeCat::Lv),
+    (0xD639, 0xD653, GraphemeCat::Lvt),
+    (0xD654, 0xD654, GraphemeCat::Lv),
+    (0xD655, 0xD66F, GraphemeCat::Lvt),
+    (0xD670, 0xD670, GraphemeCat::Lv),
+    (0xD671, 0xD68B, GraphemeCat::Lvt),
+    (0xD68C, 0xD68C, GraphemeCat::Lv),
+    (0xD68D, 0xD6A7, GraphemeCat::Lvt),
+    (0xD6A8, 0xD6A8, GraphemeCat::Lv),
+    (0xD6A9, 0xD6C3, GraphemeCat::Lvt),
+    (0xD6C4, 0xD6C4, GraphemeCat::Lv),
+    (0xD6C5, 0xD6DF, GraphemeCat::Lvt),
+    (0xD6E0, 0xD6E0, GraphemeCat::Lv),
+    (0xD6E1, 0xD6FB, GraphemeCat::Lvt),
+    (0xD6FC, 0xD6FC, GraphemeCat::Lv),
+    (0xD6FD, 0xD717, GraphemeCat::Lvt),
+    (0xD718, 0xD718, GraphemeCat::Lv),
+    (0xD719, 0xD733, GraphemeCat::Lvt),
+    (0xD734, 0xD734, GraphemeCat::Lv),
+    (0xD735, 0xD74F, GraphemeCat::Lvt),
+    (0xD750, 0xD750, GraphemeCat::Lv),
+    (0xD751, 0xD76B, GraphemeCat::Lvt),
+    (0xD76C, 0xD76C, GraphemeCat::Lv),
+    (0xD76D, 0xD787, GraphemeCat::Lvt),
+    (0xD788, 0xD788, GraphemeCat::Lv),
+    (0xD789, 0xD7A3, GraphemeCat::Lvt),
+    (0xD7B0, 0xD7C6, GraphemeCat::V),
+    (0xD7CB, 0xD7FB, GraphemeCat::T),
+    (0xFB1E, 0xFB1E, GraphemeCat::Extend),
+    (0xFE00, 0xFE0F, GraphemeCat::Extend),
+    (0xFE20, 0xFE2F, GraphemeCat::Extend),
+    (0xFEFF, 0xFEFF, GraphemeCat::Control),
+    (0xFF9E, 0xFF9F, GraphemeCat::Extend),
+    (0xFFF0, 0xFFFB, GraphemeCat::Control),
+    (0x101FD, 0x101FD, GraphemeCat::Extend),
+    (0x102E0, 0x102E0, GraphemeCat::Extend),
+    (0x10376, 0x1037A, GraphemeCat::Extend),
+    (0x10A01, 0x10A03, GraphemeCat::Extend),
+    (0x10A05, 0x10A06, GraphemeCat::Extend),
+    (0x10A0C, 0x10A0F, GraphemeCat::Extend),
+    (0x10A38, 0x10A3A, GraphemeCat::Extend),
+    (0x10A3F, 0x10A3F, GraphemeCat::Extend),
+    (0x10AE5, 0x10AE6, GraphemeCat::Extend),
+    (0x10D24, 0x10D27, GraphemeCat::Extend),
+    (0x10EAB, 0x10EAC, GraphemeCat::Extend),
+    (0x10F46, 0x10F50, GraphemeCat::Extend),
+    (0x10F82, 0x10F85, GraphemeCat::Extend),
+    (0x11000, 0x11000, GraphemeCat::SpacingMark),
+    (0x11001, 0x11001, GraphemeCat::Extend),
+    (0x11002, 0x11002, GraphemeCat::SpacingMark),
+    (0x11038, 0x11046, GraphemeCat::Extend),
+    (0x11070, 0x11070, GraphemeCat::Extend),
+    (0x11073, 0x11074, GraphemeCat::Extend),
+    (0x1107F, 0x11081, GraphemeCat::Extend),
+    (0x11082, 0x11082, GraphemeCat::SpacingMark),
+    (0x110B0, 0x110B2, GraphemeCat::SpacingMark),
+    (0x110B3, 0x110B6, GraphemeCat::Extend),
+    (0x110B7, 0x110B8, GraphemeCat::SpacingMark),
+    (0x110B9, 0x110BA, GraphemeCat::Extend),
+    (0x110BD, 0x110BD, GraphemeCat::Prepend),
+    (0x110C2, 0x110C2, GraphemeCat::Extend),
+    (0x110CD, 0x110CD, GraphemeCat::Prepend),
+    (0x11100, 0x11102, GraphemeCat::Extend),
+    (0x11127, 0x1112B, GraphemeCat::Extend),
+    (0x1112C, 0x1112C, GraphemeCat::SpacingMark),
+    (0x1112D, 0x11134, GraphemeCat::Extend),
+    (0x11145, 0x11146, GraphemeCat::SpacingMark),
+    (0x11173, 0x11173, GraphemeCat::Extend),
+    (0x11180, 0x11181, GraphemeCat::Extend),
+    (0x11182, 0x11182, GraphemeCat::SpacingMark),
+    (0x111B3, 0x111B5, GraphemeCat::SpacingMark),
+    (0x111B6, 0x111BE, GraphemeCat::Extend),
+    (0x111BF, 0x111C0, GraphemeCat::SpacingMark),
+    (0x111C2, 0x111C3, GraphemeCat::Prepend),
+    (0x111C9, 0x111CC, GraphemeCat::Extend),
+    (0x111CE, 0x111CE, GraphemeCat::SpacingMark),
+    (0x111CF, 0x111CF, GraphemeCat::Extend),
+    (0x1122C, 0x1122E, GraphemeCat::SpacingMark),
+    (0x1122F, 0x11231, GraphemeCat::Extend),
+    (0x11232, 0x11233, GraphemeCat::SpacingMark),
+    (0x11234, 0x11234, GraphemeCat::Extend),
+    (0x11235, 0x11235, GraphemeCat::SpacingMark),
+    (0x11236, 0x11237, GraphemeCat::Extend),
+    (0x1123E, 0x1123E, GraphemeCat::Extend),
+    (0x112DF, 0x112DF, GraphemeCat::Extend),
+    (0x112E0, 0x112E2, GraphemeCat::SpacingMark),
+    (0x112E3, 0x112EA, GraphemeCat::Extend),
+    (0x11300, 0x11301, GraphemeCat::Extend),
+    (0x11302, 0x11303, GraphemeCat::SpacingMark),
+    (0x1133B, 0x1133C, GraphemeCat::Extend),
+    (0x1133E, 0x1133E, GraphemeCat::Extend),
+    (0x1133F, 0x1133F, GraphemeCat::SpacingMark),
+    (0x11340, 0x11340, GraphemeCat::Extend),
+    (0x11341, 0x11344, GraphemeCat::SpacingMark),
+    (0x11347, 0x11348, GraphemeCat::SpacingMark),
+    (0x1134B, 0x1134D, GraphemeCat::SpacingMark),
+    (0x11357, 0x11357, GraphemeCat::Extend),
+    (0x11362, 0x11363, GraphemeCat::SpacingMark),
+    (0x11366, 0x1136C, GraphemeCat::Extend),
+    (0x11370, 0x11374, GraphemeCat::Extend),
+    (0x11435, 0x11437, GraphemeCat::SpacingMark),
+    (0x11438, 0x1143F, GraphemeCat::Extend),
+    (0x11440, 0x11441, GraphemeCat::SpacingMark),
+    (0x11442, 0x11444, GraphemeCat::Extend),
+    (0x11445, 0x11445, GraphemeCat::SpacingMark),
+    (0x11446, 0x11446, GraphemeCat::Extend),
+    (0x1145E, 0x1145E, GraphemeCat::Extend),
+    (0x114B0, 0x114B0, GraphemeCat::Extend),
+    (0x114B1, 0x114B2, GraphemeCat::SpacingMark),
+    (0x114B3, 0x114B8, GraphemeCat::Extend),
+    (0x114B9, 0x114B9, GraphemeCat::SpacingMark),
+    (0x114BA, 0x114BA, GraphemeCat::Extend),
+    (0x114BB, 0x114BC, GraphemeCat::SpacingMark),
+    (0x114BD, 0x114BD, GraphemeCat::Extend),
+    (0x114BE, 0x114BE, GraphemeCat::SpacingMark),
+    (0x114BF, 0x114C0, GraphemeCat::Extend),
+    (0x114C1, 0x114C1, GraphemeCat::SpacingMark),
+    (0x114C2, 0x114C3, GraphemeCat::Extend),
+    (0x115AF, 0x115AF, GraphemeCat::Extend),
+    (0x115B0, 0x115B1, GraphemeCat::SpacingMark),
+    (0x115B2, 0x115B5, GraphemeCat::Extend),
+    (0x115B8, 0x115BB, GraphemeCat::SpacingMark),
+    (0x115BC, 0x115BD, GraphemeCat::Extend),
+    (0x115BE, 0x115BE, GraphemeCat::SpacingMark),
+    (0x115BF, 0x115C0, GraphemeCat::Extend),
+    (0x115DC, 0x115DD, GraphemeCat::Extend),
+    (0x11630, 0x11632, GraphemeCat::SpacingMark),
+    (0x11633, 0x1163A, GraphemeCat::Extend),
+    (0x1163B, 0x1163C, GraphemeCat::SpacingMark),
+    (0x1163D, 0x1163D, GraphemeCat::Extend),
+    (0x1163E, 0x1163E, GraphemeCat::SpacingMark),
+    (0x1163F, 0x11640, GraphemeCat::Extend),
+    (0x116AB, 0x116AB, GraphemeCat::Extend),
+    (0x116AC, 0x116AC, GraphemeCat::SpacingMark),
+    (0x116AD, 0x116AD, GraphemeCat::Extend),
+    (0x116AE, 0x116AF, GraphemeCat::SpacingMark),
+    (0x116B0, 0x116B5, GraphemeCat::Extend),
+    (0x116B6, 0x116B6, GraphemeCat::SpacingMark),
+    (0x116B7, 0x116B7, GraphemeCat::Extend),
+    (0x1171D, 0x1171F, GraphemeCat::Extend),
+    (0x11722, 0x11725, GraphemeCat::Extend),
+    (0x11726, 0x11726, GraphemeCat::SpacingMark),
+    (0x11727, 0x1172B, GraphemeCat::Extend),
+    (0x1182C, 0x1182E, GraphemeCat::SpacingMark),
+    (0x1182F, 0x11837, GraphemeCat::Extend),
+    (0x11838, 0x11838, GraphemeCat::SpacingMark),
+    (0x11839, 0x1183A, GraphemeCat::Extend),
+    (0x11930, 0x11930, GraphemeCat::Extend),
+    (0x11931, 0x11935, GraphemeCat::SpacingMark),
+    (0x11937, 0x11938, GraphemeCat::SpacingMark),
+    (0x1193B, 0x1193C, GraphemeCat::Extend),
+    (0x1193D, 0x1193D, GraphemeCat::SpacingMark),
+    (0x1193E, 0x1193E, GraphemeCat::Extend),
+    (0x1193F, 0x1193F, GraphemeCat::Prepend),
+    (0x11940, 0x11940, GraphemeCat::SpacingMark),
+    (0x11941, 0x11941, GraphemeCat::Prepend),
+    (0x11942, 0x11942, GraphemeCat::SpacingMark),
+    (0x11943, 0x11943, GraphemeCat::Extend),
+    (0x119D1, 0x119D3, GraphemeCat::SpacingMark),
+    (0x119D4, 0x119D7, GraphemeCat::Extend),
+    (0x119DA, 0x119DB, GraphemeCat::Extend),
+    (0x119DC, 0x119DF, GraphemeCat::SpacingMark),
+    (0x119E0, 0x119E0, GraphemeCat::Extend),
+    (0x119E4, 0x119E4, GraphemeCat::SpacingMark),
+    (0x11A01, 0x11A0A, GraphemeCat::Extend),
+    (0x11A33, 0x11A38, GraphemeCat::Extend),
+    (0x11A39, 0x11A39, GraphemeCat::SpacingMark),
+    (0x11A3A, 0x11A3A, GraphemeCat::Prepend),
+    (0x11A3B, 0x11A3E, GraphemeCat::Extend),
+    (0x11A47, 0x11A47, GraphemeCat::Extend),
+    (0x11A51, 0x11A56, GraphemeCat::Extend),
+    (0x11A57, 0x11A58, GraphemeCat::SpacingMark),
+    (0x11A59, 0x11A5B, GraphemeCat::Extend),
+    (0x11A84, 0x11A89, GraphemeCat::Prepend),
+    (0x11A8A, 0x11A96, GraphemeCat::Extend),
+    (0x11A97, 0x11A97, GraphemeCat::SpacingMark),
+    (0x11A98, 0x11A99, GraphemeCat::Extend),
+    (0x11C2F, 0x11C2F, GraphemeCat::SpacingMark),
+    (0x11C30, 0x11C36, GraphemeCat::Extend),
+    (0x11C38, 0x11C3D, GraphemeCat::Extend),
+    (0x11C3E, 0x11C3E, GraphemeCat::SpacingMark),
+    (0x11C3F, 0x11C3F, GraphemeCat::Extend),
+    (0x11C92, 0x11CA7, GraphemeCat::Extend),
+    (0x11CA9, 0x11CA9, GraphemeCat::SpacingMark),
+    (0x11CAA, 0x11CB0, GraphemeCat::Extend),
+    (0x11CB1, 0x11CB1, GraphemeCat::SpacingMark),
+    (0x11CB2, 0x11CB3, GraphemeCat::Extend),
+    (0x11CB4, 0x11CB4, GraphemeCat::SpacingMark),
+    (0x11CB5, 0x11CB6, GraphemeCat::Extend),
+    (0x11D31, 0x11D36, GraphemeCat::Extend),
+    (0x11D3A, 0x11D3A, GraphemeCat::Extend),
+    (0x11D3C, 0x11D3D, GraphemeCat::Extend),
+    (0x11D3F, 0x11D45, GraphemeCat::Extend),
+    (0x11D46, 0x11D46, GraphemeCat::Prepend),
+    (0x11D47, 0x11D47, GraphemeCat::Extend),
+    (0x11D8A, 0x11D8E, GraphemeCat::SpacingMark),
+    (0x11D90, 0x11D91, GraphemeCat::Extend),
+    (0x11D93, 0x11D94, GraphemeCat::SpacingMark),
+    (0x11D95, 0x11D95, GraphemeCat::Extend),
+    (0x11D96, 0x11D96, GraphemeCat::SpacingMark),
+    (0x11D97, 0x11D97, GraphemeCat::Extend),
+    (0x11EF3, 0x11EF4, GraphemeCat::Extend),
+    (0x11EF5, 0x11EF6, GraphemeCat::SpacingMark),
+    (0x13430, 0x13438, GraphemeCat::Control),
+    (0x16AF0, 0x16AF4, GraphemeCat::Extend),
+    (0x16B30, 0x16B36, GraphemeCat::Extend),
+    (0x16F4F, 0x16F4F, GraphemeCat::Extend),
+    (0x16F51, 0x16F87, GraphemeCat::SpacingMark),
+    (0x16F8F, 0x16F92, GraphemeCat::Extend),
+    (0x16FE4, 0x16FE4, GraphemeCat::Extend),
+    (0x16FF0, 0x16FF1, GraphemeCat::SpacingMark),
+    (0x1BC9D, 0x1BC9E, GraphemeCat::Extend),
+    (0x1BCA0, 0x1BCA3, GraphemeCat::Control),
+    (0x1CF00, 0x1CF2D, GraphemeCat::Extend),
+    (0x1CF30, 0x1CF46, GraphemeCat::Extend),
+    (0x1D165, 0x1D165, GraphemeCat::Extend),
+    (0x1D166, 0x1D166, GraphemeCat::SpacingMark),
+    (0x1D167, 0x1D169, GraphemeCat::Extend),
+    (0x1D16D, 0x1D16D, GraphemeCat::SpacingMark),
+    (0x1D16E, 0x1D172, GraphemeCat::Extend),
+    (0x1D173, 0x1D17A, GraphemeCat::Control),
+    (0x1D17B, 0x1D182, GraphemeCat::Extend),
+    (0x1D185, 0x1D18B, GraphemeCat::Extend),
+    (0x1D1AA, 0x1D1AD, GraphemeCat::Extend),
+    (0x1D242, 0x1D244, GraphemeCat::Extend),
+    (0x1DA00, 0x1DA36, GraphemeCat::Extend),
+    (0x1DA3B, 0x1DA6C, GraphemeCat::Extend),
+    (0x1DA75, 0x1DA75, GraphemeCat::Extend),
+    (0x1DA84, 0x1DA84, GraphemeCat::Extend),
+    (0x1DA9B, 0x1DA9F, GraphemeCat::Extend),
+    (0x1DAA1, 0x1DAAF, GraphemeCat::Extend),
+    (0x1E000, 0x1E006, GraphemeCat::Extend),
+    (0x1E008, 0x1E018, GraphemeCat::Extend),
+    (0x1E01B, 0x1E021, GraphemeCat::Extend),
+    (0x1E023, 0x1E024, GraphemeCat::Extend),
+    (0x1E026, 0x1E02A, GraphemeCat::Extend),
+    (0x1E130, 0x1E136, GraphemeCat::Extend),
+    (0x1E2AE, 0x1E2AE, GraphemeCat::Extend),
+    (0x1E2EC, 0x1E2EF, GraphemeCat::Extend),
+    (0x1E8D0, 0x1E8D6, GraphemeCat::Extend),
+    (0x1E944, 0x1E94A, GraphemeCat::Extend),
+    (0x1F1E6, 0x1F1FF, GraphemeCat::RegionalIndicator),
+    (0x1F3FB, 0x1F3FF, GraphemeCat::Extend),
+    (0xE0000, 0xE001F, GraphemeCat::Control),
+    (0xE0020, 0xE007F, GraphemeCat::Extend),
+    (0xE0080, 0xE00FF, GraphemeCat::Control),
+    (0xE0100, 0xE01EF, GraphemeCat::Extend),
+    (0xE01F0, 0xE0FFF, GraphemeCat::Control),
+];
@@ -0,0 +1,28 @@
+use termset::core::{assert_transcript, Cell, Color, Screen, Style, TsStdout};
+
+/// The first frame draws everything, the second only what changed
+#[test]
+fn present() {
+    let mut stdout = TsStdout::with_writer(Vec::new());
+    let mut screen = Screen::with_size(4, 20);
+    let title = Style::new().fg(Color::Yellow).bold();
+    screen.draw_text(0, 0, "{hello}", title);
+    screen.draw_text(1, 2, "日本語 text", Style::new());
+    screen.fill(3, 0, 1, 20, Cell::new("-", Style::new().bg(Color::Blue)));
+    screen.set_cursor(Some((2, 4)));
+    screen.present(&mut stdout);
+
+    screen.draw_text(0, 0, "{bye}  ", title);
+    screen.draw_text(1, 9, "TEXT", Style::new().fg(Color::Rgb(0x12, 0x34, 0x56)));
+    screen.set_cursor(None);
+    screen.present(&mut stdout);
+
+    let bytes = stdout.into_writer().unwrap();
+    assert_transcript(
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/snapshots/screen_present.txt"
+        ),
+        &bytes,
+    );
+}